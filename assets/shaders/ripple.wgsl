@@ -0,0 +1,43 @@
+// A custom terminal fragment shader which ripples the glyph colors outward
+// from the center of the terminal.
+#import bevy_sprite::mesh2d_view_bindings::globals
+
+struct TerminalMaterial {
+    clip_color: vec4<f32>,
+    flags: u32,
+};
+const TERMINAL_MATERIAL_FLAGS_TEXTURE_BIT: u32 = 1u;
+
+@group(2) @binding(0)
+var<uniform> material: TerminalMaterial;
+@group(2) @binding(1)
+var texture: texture_2d<f32>;
+@group(2) @binding(2)
+var texture_sampler: sampler;
+
+struct FragmentInput {
+    @builtin(front_facing) is_front: bool,
+    @location(0) world_position: vec4<f32>,
+    @location(1) uv: vec2<f32>,
+    @location(2) bg_color: vec4<f32>,
+    @location(3) fg_color: vec4<f32>,
+};
+
+@fragment
+fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+    let dist = length(in.world_position.xy);
+    let wave = 0.5 + 0.5 * sin(dist * 0.8 - globals.time * 4.0);
+
+    var out_color = in.fg_color;
+
+    if ((material.flags & TERMINAL_MATERIAL_FLAGS_TEXTURE_BIT) != 0u) {
+        let tex_color = textureSample(texture, texture_sampler, in.uv);
+
+        if( all(tex_color.rgb - material.clip_color.rgb < vec3<f32>(0.001, 0.001, 0.001)) ) {
+            out_color = vec4<f32>(in.bg_color.rgb * (0.5 + 0.5 * wave), in.bg_color.a);
+        } else {
+            out_color = vec4<f32>(tex_color.rgb * in.fg_color.rgb * (0.25 + wave), in.fg_color.a);
+        }
+    }
+    return out_color;
+}
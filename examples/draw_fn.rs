@@ -0,0 +1,27 @@
+use bevy::color::palettes::css::{GOLD, WHITE};
+use bevy::prelude::*;
+use bevy_ascii_terminal::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, TerminalPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    let mut frame: usize = 0;
+    commands.spawn((
+        TerminalBundle::from(Terminal::new([30, 5]).with_border(Border::single_line())),
+        TerminalDrawFn::new(move |term| {
+            frame += 1;
+            term.clear();
+            term.put_string([0, 0].pivot(Pivot::TopLeft), "Drawn from a closure!");
+
+            let x = (frame / 4) % term.width();
+            term.put_char([x as i32, 2], '@'.fg(Color::Srgba(GOLD)));
+            term.put_string([0, 0], format!("Frame {}", frame).fg(Color::Srgba(WHITE)));
+        }),
+        AutoCamera,
+    ));
+}
@@ -0,0 +1,54 @@
+//! Press space to hit the '@', shaking it's glyph without moving the rest of
+//! the terminal.
+
+use bevy::prelude::*;
+use bevy_ascii_terminal::*;
+use rand::Rng;
+
+const SHAKE_TIME: f32 = 0.35;
+const SHAKE_STRENGTH: f32 = 0.2;
+const PLAYER: [i32; 2] = [10, 4];
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, TerminalPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (hit, shake).chain())
+        .run();
+}
+
+#[derive(Component, Default)]
+struct Shake(f32);
+
+fn setup(mut commands: Commands) {
+    let mut term = Terminal::new([21, 9]).with_border(Border::single_line());
+    term.put_string([0, 0].pivot(Pivot::TopLeft), "Press space");
+    term.put_char(PLAYER, '@'.bg(Color::srgb(0.2, 0.2, 0.2)));
+
+    commands.spawn((TerminalBundle::from(term), Shake::default(), AutoCamera));
+}
+
+fn hit(input: Res<ButtonInput<KeyCode>>, mut q_shake: Query<&mut Shake>) {
+    if input.just_pressed(KeyCode::Space) {
+        for mut shake in &mut q_shake {
+            shake.0 = SHAKE_TIME;
+        }
+    }
+}
+
+fn shake(time: Res<Time>, mut q_term: Query<(&mut Terminal, &mut Shake)>) {
+    for (mut term, mut shake) in &mut q_term {
+        if shake.0 <= 0.0 {
+            continue;
+        }
+        shake.0 = (shake.0 - time.delta_seconds()).max(0.0);
+
+        let strength = SHAKE_STRENGTH * shake.0 / SHAKE_TIME;
+        let mut rng = rand::thread_rng();
+        let offset = Vec2::new(
+            rng.gen_range(-1.0..=1.0) * strength,
+            rng.gen_range(-1.0..=1.0) * strength,
+        );
+        term.set_glyph_offset(PLAYER, offset);
+    }
+}
@@ -0,0 +1,59 @@
+//! Tints glyphs towards the material's accent color to show a moving heat map.
+//! The tinting is done in the shader, only the per-tile tint values are
+//! updated each frame.
+
+use bevy::prelude::*;
+use bevy_ascii_terminal::*;
+
+const SIZE: [i32; 2] = [40, 20];
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, TerminalPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, (set_accent, heat))
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    let mut term = Terminal::new(SIZE).with_border(Border::single_line());
+    let tile = Tile {
+        glyph: '▒',
+        fg_color: Color::srgb(0.2, 0.3, 0.6),
+        bg_color: Color::BLACK,
+    };
+    term.iter_mut().for_each(|t| *t = tile);
+    commands.spawn((TerminalBundle::from(term), AutoCamera));
+}
+
+/// Give the terminal it's own material with a bright orange accent color.
+fn set_accent(
+    mut materials: ResMut<Assets<TerminalMaterial>>,
+    mut q_term: Query<&mut Handle<TerminalMaterial>, Added<Terminal>>,
+) {
+    for mut handle in &mut q_term {
+        if let Some(material) = materials.get(&*handle) {
+            let material = TerminalMaterial {
+                accent_color: Color::srgb(1.0, 0.6, 0.1),
+                ..material.clone()
+            };
+            *handle = materials.add(material);
+        }
+    }
+}
+
+fn heat(time: Res<Time>, mut q_term: Query<&mut Terminal>) {
+    let t = time.elapsed_seconds();
+    let center = Vec2::new(
+        SIZE[0] as f32 * (0.5 + 0.3 * (t * 0.7).sin()),
+        SIZE[1] as f32 * (0.5 + 0.3 * (t * 1.1).cos()),
+    );
+    for mut term in &mut q_term {
+        for y in 0..SIZE[1] {
+            for x in 0..SIZE[0] {
+                let dist = Vec2::new(x as f32, y as f32).distance(center);
+                term.set_tint([x, y], 1.0 - dist / 12.0);
+            }
+        }
+    }
+}
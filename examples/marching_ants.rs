@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use bevy_ascii_terminal::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, TerminalPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, select)
+        .run();
+}
+
+#[derive(Component)]
+struct Panel(usize);
+
+fn setup(mut commands: Commands) {
+    for i in 0..3 {
+        let mut term = Terminal::new([12, 5]).with_border(Border::single_line());
+        term.put_string([0, 0].pivot(Pivot::Center), format!("Panel {}", i + 1));
+        let x = (i as i32 - 1) * 15;
+
+        let mut panel = commands.spawn((
+            TerminalBundle::from(term).with_position([x, 0]),
+            Panel(i),
+            AutoCamera,
+        ));
+        if i == 0 {
+            panel.insert(MarchingAnts::default());
+        }
+    }
+}
+
+fn select(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    q_panels: Query<(Entity, &Panel)>,
+    mut selected: Local<usize>,
+) {
+    if !input.just_pressed(KeyCode::Tab) {
+        return;
+    }
+    *selected = (*selected + 1) % q_panels.iter().count();
+
+    for (entity, panel) in &q_panels {
+        if panel.0 == *selected {
+            commands.entity(entity).insert(MarchingAnts::default());
+        } else {
+            commands.entity(entity).remove::<MarchingAnts>();
+        }
+    }
+}
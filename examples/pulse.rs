@@ -0,0 +1,25 @@
+//! Pulses the background of a "Press Start" message. The pulse is animated in
+//! the shader, the terminal is only written to once.
+
+use bevy::prelude::*;
+use bevy_ascii_terminal::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, TerminalPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    let mut term = Terminal::new([30, 10]).with_border(Border::double_line());
+    term.put_string([0, 2].pivot(Pivot::Center), "ASCII QUEST");
+    term.put_string(
+        [0, -1].pivot(Pivot::Center),
+        " Press Start "
+            .fg(Color::BLACK)
+            .bg(Color::srgb(0.9, 0.8, 0.2))
+            .pulse(),
+    );
+    commands.spawn((TerminalBundle::from(term), AutoCamera));
+}
@@ -0,0 +1,30 @@
+use bevy::color::palettes::css::{MIDNIGHT_BLUE, WHITE};
+use bevy::prelude::*;
+use bevy_ascii_terminal::*;
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, TerminalPlugin))
+        .add_systems(Startup, setup)
+        .run();
+}
+
+fn setup(mut commands: Commands, server: Res<AssetServer>) {
+    let mut term = Terminal::new([30, 15])
+        .with_clear_tile(' '.fg(Color::Srgba(WHITE)).bg(Color::Srgba(MIDNIGHT_BLUE)))
+        .with_border(Border::single_line());
+
+    for y in 0..term.height() {
+        let line: String = (0..term.width())
+            .map(|x| if (x + y) % 2 == 0 { '░' } else { '▒' })
+            .collect();
+        term.put_string([0, y as i32], line);
+    }
+    term.put_string([0, 0].pivot(Pivot::Center), "Custom shaders!");
+
+    commands.spawn((
+        TerminalBundle::from(term),
+        TerminalShader(server.load("shaders/ripple.wgsl")),
+        AutoCamera,
+    ));
+}
@@ -0,0 +1,45 @@
+//! Press space to throw a dagger at the goblin.
+
+use bevy::color::palettes::css::{GREEN, SILVER, YELLOW};
+use bevy::prelude::*;
+use bevy_ascii_terminal::*;
+
+const PLAYER: [i32; 2] = [2, 2];
+const GOBLIN: [i32; 2] = [24, 7];
+
+fn main() {
+    App::new()
+        .add_plugins((DefaultPlugins, TerminalPlugin))
+        .add_systems(Startup, setup)
+        .add_systems(Update, throw)
+        .run();
+}
+
+fn setup(mut commands: Commands) {
+    commands.spawn((
+        TerminalBundle::from(Terminal::new([28, 10]).with_border(Border::single_line())),
+        TerminalDrawFn::new(|term| {
+            term.put_string([0, 0].pivot(Pivot::TopLeft), "Press space");
+            term.put_char(PLAYER, '@'.fg(Color::Srgba(YELLOW)));
+            term.put_char(GOBLIN, 'g'.fg(Color::Srgba(GREEN)));
+        }),
+        // The tween is drawn every frame, so the terminal must be cleared to
+        // erase its previous positions
+        ClearAfterRender,
+        AutoCamera,
+    ));
+}
+
+fn throw(
+    mut commands: Commands,
+    input: Res<ButtonInput<KeyCode>>,
+    q_term: Query<Entity, With<Terminal>>,
+) {
+    if input.just_pressed(KeyCode::Space) {
+        let term = q_term.single();
+        commands.spawn(
+            TileTween::new(term, '/'.fg(Color::Srgba(SILVER)), PLAYER, GOBLIN, 0.4)
+                .with_easing(TweenEasing::EaseOut),
+        );
+    }
+}
@@ -0,0 +1,307 @@
+//! Importing ANSI art (`.ans` files) into a terminal.
+//!
+//! ANSI art is made of Code Page 437 bytes mixed with escape sequences that
+//! move the cursor and set the 16 DOS colors. See [`Terminal::from_ansi`].
+
+use bevy::prelude::Color;
+
+use crate::{code_page_437, Terminal, Tile};
+
+const LOW: f32 = 1.0 / 3.0;
+const MID: f32 = 2.0 / 3.0;
+
+/// The 16 DOS text mode colors, in the order used by SGR color codes: black,
+/// red, green, brown, blue, magenta, cyan and light grey, followed by their
+/// bright variants.
+pub const DOS_PALETTE: [Color; 16] = [
+    Color::srgb(0.0, 0.0, 0.0),
+    Color::srgb(MID, 0.0, 0.0),
+    Color::srgb(0.0, MID, 0.0),
+    Color::srgb(MID, LOW, 0.0),
+    Color::srgb(0.0, 0.0, MID),
+    Color::srgb(MID, 0.0, MID),
+    Color::srgb(0.0, MID, MID),
+    Color::srgb(MID, MID, MID),
+    Color::srgb(LOW, LOW, LOW),
+    Color::srgb(1.0, LOW, LOW),
+    Color::srgb(LOW, 1.0, LOW),
+    Color::srgb(1.0, 1.0, LOW),
+    Color::srgb(LOW, LOW, 1.0),
+    Color::srgb(1.0, LOW, 1.0),
+    Color::srgb(LOW, 1.0, 1.0),
+    Color::srgb(1.0, 1.0, 1.0),
+];
+
+/// The DOS "end of file" character, anything after it is metadata.
+const EOF: u8 = 0x1A;
+const ESC: u8 = 0x1B;
+
+const DEFAULT_FG: usize = 7;
+const DEFAULT_BG: usize = 0;
+
+/// The most rows a piece of art can have. Cursor moves past the last row are
+/// clamped so malformed files can't allocate unbounded memory.
+const MAX_ROWS: usize = 10_000;
+
+impl Terminal {
+    /// Create a terminal from the contents of an ANSI art (`.ans`) file.
+    ///
+    /// Bytes are mapped to glyphs with [`code_page_437`] and colors are set
+    /// from the [`DOS_PALETTE`]. Bold foregrounds and blinking backgrounds use
+    /// the bright colors. Cursor movement, save/restore and clear screen
+    /// sequences are supported, anything else is ignored.
+    ///
+    /// `width` is the width of the art, usually 80. The terminal will be as
+    /// tall as needed to fit the art, up to 10,000 rows. Anything drawn below
+    /// that is written to the last row. Parsing stops at the DOS end of file
+    /// character, so any SAUCE metadata is skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let bytes = std::fs::read("assets/title.ans").unwrap();
+    /// let term = Terminal::from_ansi(&bytes, 80);
+    /// ```
+    pub fn from_ansi(bytes: &[u8], width: usize) -> Terminal {
+        let width = width.max(1);
+        let rows = parse(bytes, width);
+        let mut term = Terminal::new([width as u32, rows.len().max(1) as u32]);
+        let height = term.height();
+        for (i, row) in rows.into_iter().enumerate() {
+            for (t, tile) in term.iter_row_mut(height - 1 - i).zip(row) {
+                *t = tile;
+            }
+        }
+        term
+    }
+}
+
+/// The state of the virtual screen the art is drawn to. Rows are stored from
+/// the top down.
+struct Screen {
+    width: usize,
+    rows: Vec<Vec<Tile>>,
+    x: usize,
+    y: usize,
+    saved: (usize, usize),
+    fg: usize,
+    bg: usize,
+    bold: bool,
+    blink: bool,
+}
+
+impl Screen {
+    fn new(width: usize) -> Self {
+        Self {
+            width,
+            rows: Vec::new(),
+            x: 0,
+            y: 0,
+            saved: (0, 0),
+            fg: DEFAULT_FG,
+            bg: DEFAULT_BG,
+            bold: false,
+            blink: false,
+        }
+    }
+
+    fn blank() -> Tile {
+        Tile {
+            glyph: ' ',
+            fg_color: DOS_PALETTE[DEFAULT_FG],
+            bg_color: DOS_PALETTE[DEFAULT_BG],
+        }
+    }
+
+    fn put(&mut self, glyph: char) {
+        // Wrapping is deferred until the next glyph so a line return right
+        // after a full line doesn't skip a row
+        if self.x >= self.width {
+            self.x = 0;
+            self.move_down(1);
+        }
+        if self.rows.len() <= self.y {
+            self.rows
+                .resize(self.y + 1, vec![Self::blank(); self.width]);
+        }
+        let fg = self.fg + if self.bold { 8 } else { 0 };
+        let bg = self.bg + if self.blink { 8 } else { 0 };
+        self.rows[self.y][self.x] = Tile {
+            glyph,
+            fg_color: DOS_PALETTE[fg],
+            bg_color: DOS_PALETTE[bg],
+        };
+        self.x += 1;
+    }
+
+    /// Move the cursor down, stopping at the last row.
+    fn move_down(&mut self, n: usize) {
+        self.y = self.y.saturating_add(n).min(MAX_ROWS - 1);
+    }
+
+    fn sgr(&mut self, params: &[Option<usize>]) {
+        // No parameters is the same as a reset
+        let params = if params.is_empty() {
+            &[None][..]
+        } else {
+            params
+        };
+        for p in params {
+            match p.unwrap_or(0) {
+                0 => {
+                    self.fg = DEFAULT_FG;
+                    self.bg = DEFAULT_BG;
+                    self.bold = false;
+                    self.blink = false;
+                }
+                1 => self.bold = true,
+                5 => self.blink = true,
+                22 => self.bold = false,
+                25 => self.blink = false,
+                p @ 30..=37 => self.fg = p - 30,
+                39 => self.fg = DEFAULT_FG,
+                p @ 40..=47 => self.bg = p - 40,
+                49 => self.bg = DEFAULT_BG,
+                p @ 90..=97 => {
+                    self.fg = p - 90;
+                    self.bold = true;
+                }
+                p @ 100..=107 => {
+                    self.bg = p - 100;
+                    self.blink = true;
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Apply a control sequence with the given parameters and final byte.
+    fn csi(&mut self, params: &[Option<usize>], action: u8) {
+        let n = |i: usize| params.get(i).copied().flatten().unwrap_or(1).max(1);
+        match action {
+            b'm' => self.sgr(params),
+            b'H' | b'f' => {
+                self.y = (n(0) - 1).min(MAX_ROWS - 1);
+                self.x = (n(1) - 1).min(self.width - 1);
+            }
+            b'A' => self.y = self.y.saturating_sub(n(0)),
+            b'B' => self.move_down(n(0)),
+            b'C' => self.x = self.x.saturating_add(n(0)).min(self.width - 1),
+            b'D' => self.x = self.x.saturating_sub(n(0)),
+            b's' => self.saved = (self.x, self.y),
+            b'u' => (self.x, self.y) = self.saved,
+            b'J' if params.first().copied().flatten() == Some(2) => {
+                self.rows.clear();
+                self.x = 0;
+                self.y = 0;
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Parse ANSI art into rows of tiles, from the top down.
+fn parse(bytes: &[u8], width: usize) -> Vec<Vec<Tile>> {
+    let mut screen = Screen::new(width);
+    let mut bytes = bytes.iter().copied().peekable();
+    while let Some(b) = bytes.next() {
+        match b {
+            EOF => break,
+            b'\r' => screen.x = 0,
+            b'\n' => {
+                screen.x = 0;
+                screen.move_down(1);
+            }
+            ESC => {
+                // Anything other than a control sequence is skipped
+                if bytes.next_if_eq(&b'[').is_none() {
+                    continue;
+                }
+                let mut params = vec![None];
+                for b in bytes.by_ref() {
+                    match b {
+                        b'0'..=b'9' => {
+                            let p = params.last_mut().unwrap();
+                            let digit = (b - b'0') as usize;
+                            *p = Some(p.unwrap_or(0).saturating_mul(10).saturating_add(digit));
+                        }
+                        b';' => params.push(None),
+                        // Final byte
+                        0x40..=0x7E => {
+                            if params == [None] {
+                                params.clear();
+                            }
+                            screen.csi(&params, b);
+                            break;
+                        }
+                        // Private mode and intermediate bytes are ignored
+                        0x20..=0x3F => {}
+                        // Malformed sequence, drop it
+                        _ => break,
+                    }
+                }
+            }
+            _ => screen.put(code_page_437::index_to_glyph(b)),
+        }
+    }
+    screen.rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_ansi() {
+        let bytes = b"\x1b[0;1;31mHi\x1b[0m!\r\n\x1b[44m\xdb\x1b[2C\x1b[5;32mx\x1a\x1b[33mSAUCE";
+        let term = Terminal::from_ansi(bytes, 5);
+
+        assert_eq!([5, 2], term.size().to_array());
+        assert_eq!("Hi!  ", term.get_string([0, 1], 5));
+        assert_eq!(DOS_PALETTE[9], term.get_tile([0, 1]).fg_color);
+        assert_eq!(DOS_PALETTE[7], term.get_tile([2, 1]).fg_color);
+        assert_eq!("█  x ", term.get_string([0, 0], 5));
+        assert_eq!(DOS_PALETTE[4], term.get_tile([0, 0]).bg_color);
+        // Blinking uses the bright background, skipped tiles are left blank
+        assert_eq!(DOS_PALETTE[2], term.get_tile([3, 0]).fg_color);
+        assert_eq!(DOS_PALETTE[12], term.get_tile([3, 0]).bg_color);
+        assert_eq!(DOS_PALETTE[0], term.get_tile([1, 0]).bg_color);
+    }
+
+    #[test]
+    fn cursor_position() {
+        let term = Terminal::from_ansi(b"\x1b[3;2Hx\x1b[1;1Hy\x1b[2Az", 4);
+
+        assert_eq!([4, 3], term.size().to_array());
+        assert_eq!("yz  ", term.get_string([0, 2], 4));
+        assert_eq!(" x  ", term.get_string([0, 0], 4));
+    }
+
+    #[test]
+    fn deferred_wrap() {
+        // A full line followed by a line return shouldn't leave an empty row
+        let term = Terminal::from_ansi(b"abc\r\ndef", 3);
+
+        assert_eq!([3, 2], term.size().to_array());
+        assert_eq!("abc", term.get_string([0, 1], 3));
+        assert_eq!("def", term.get_string([0, 0], 3));
+    }
+
+    #[test]
+    fn huge_parameters() {
+        let bytes = b"a\x1b[99999999Hb\x1b[99999999999999999999999Bc\x1b[99999999999999999999999Cd";
+        let term = Terminal::from_ansi(bytes, 3);
+
+        assert_eq!([3, MAX_ROWS as u32], term.size().to_array());
+        assert_eq!("a  ", term.get_string([0, MAX_ROWS as i32 - 1], 3));
+        assert_eq!("bcd", term.get_string([0, 0], 3));
+    }
+
+    #[test]
+    fn malformed() {
+        let term = Terminal::from_ansi(b"a\x1bb\x1b[1;\x07c\x1b[", 3);
+        assert_eq!("abc", term.get_string([0, 0], 3));
+    }
+}
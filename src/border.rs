@@ -2,7 +2,12 @@
 
 // use crate::{Tile, FormattedTile, TileFormatter};
 
-use bevy::{prelude::Color, utils::HashMap};
+use std::borrow::Cow;
+
+use bevy::{math::IVec2, prelude::Color, utils::HashMap};
+use sark_grids::{grid::Side, Pivot};
+
+use crate::{FormattedTile, Tile, TileFormatter};
 
 /// Specifies the style of lines to use along the border of a box.
 #[derive(Debug, Clone, PartialEq)]
@@ -15,9 +20,32 @@ pub struct Border {
     pub top_right: char,
     pub bottom_left: char,
     pub bottom_right: char,
+    /// Whether strings on horizontal edges are surrounded by junction glyphs.
+    ///
+    /// See [`Border::with_title_connectors`].
+    pub title_connectors: bool,
+    /// Glyphs drawn either side of strings on horizontal edges, ie: `[title]`.
+    /// Takes priority over [`Border::title_connectors`].
+    ///
+    /// See [`Border::with_title_brackets`].
+    pub title_brackets: Option<[char; 2]>,
+    /// Optional light and dark shading for the border glyphs, see
+    /// [`Border::with_bevel`].
+    pub bevel: Option<Bevel>,
     pub(crate) edge_strings: HashMap<Edge, AlignedString>,
 }
 
+/// Light and dark colors applied to opposite sides of a border to give it a
+/// raised or sunken look.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Bevel {
+    pub light: Color,
+    pub dark: Color,
+    /// If true the top and left sides are dark and the bottom and right sides
+    /// are light, making the panel appear sunken rather than raised.
+    pub sunken: bool,
+}
+
 #[derive(Debug, Default, Clone, PartialEq)]
 pub struct AlignedString {
     pub align: f32,
@@ -67,6 +95,9 @@ impl Border {
             top_right: '┐',
             bottom_left: '└',
             bottom_right: '┘',
+            title_connectors: false,
+            title_brackets: None,
+            bevel: None,
             edge_strings: Default::default(),
         }
     }
@@ -139,6 +170,59 @@ impl Border {
         }
     }
 
+    /// Replace the glyph used along one side of the border.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// // A double line border with a single line bottom edge
+    /// let border = Border::double_line()
+    ///     .with_edge(Side::Bottom, '─')
+    ///     .with_corner(Pivot::BottomLeft, '╘')
+    ///     .with_corner(Pivot::BottomRight, '╛');
+    /// ```
+    pub fn with_edge(mut self, side: Side, glyph: char) -> Self {
+        match side {
+            Side::Left => self.left = glyph,
+            Side::Top => self.top = glyph,
+            Side::Right => self.right = glyph,
+            Side::Bottom => self.bottom = glyph,
+        }
+        self
+    }
+
+    /// Replace the glyph used for one corner of the border.
+    ///
+    /// Only the four corner pivots are valid, any other pivot is ignored. See
+    /// [`Border::with_edge`].
+    pub fn with_corner(mut self, corner: Pivot, glyph: char) -> Self {
+        match corner {
+            Pivot::TopLeft => self.top_left = glyph,
+            Pivot::TopRight => self.top_right = glyph,
+            Pivot::BottomLeft => self.bottom_left = glyph,
+            Pivot::BottomRight => self.bottom_right = glyph,
+            _ => {}
+        }
+        self
+    }
+
+    /// Draw a title along the top edge of the border.
+    ///
+    /// The title's alignment determines where it's placed along the edge. If
+    /// the title doesn't fit on the edge it will be truncated with an ellipsis.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// // A centered title surrounded by brackets, ie: `┌──[Inventory]──┐`
+    /// let border = Border::single_line()
+    ///     .with_title("Inventory".aligned(0.5))
+    ///     .with_title_brackets('[', ']');
+    /// ```
     pub fn with_title(mut self, title: impl Into<AlignedString>) -> Self {
         self.edge_strings.insert(Edge::Top, title.into());
         self
@@ -148,9 +232,266 @@ impl Border {
         let string = self.edge_strings.entry(Edge::Top).or_default();
         string.string = title.into();
     }
+
+    /// Draw a string along the given edge of the border.
+    ///
+    /// The string's alignment determines where it's placed along the edge,
+    /// where `0.0` is left (or top) aligned and `1.0` is right (or bottom)
+    /// aligned. Strings on corner edges are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// // A border with a right aligned status string on the bottom edge.
+    /// let border = Border::single_line().with_edge_string(Edge::Bottom, "1/5".aligned(1.0));
+    /// ```
+    pub fn with_edge_string(mut self, edge: Edge, string: impl Into<AlignedString>) -> Self {
+        self.set_edge_string(edge, string);
+        self
+    }
+
+    /// Set the string drawn along the given edge of the border.
+    ///
+    /// See [`Border::with_edge_string`].
+    pub fn set_edge_string(&mut self, edge: Edge, string: impl Into<AlignedString>) {
+        self.edge_strings.insert(edge, string.into());
+    }
+
+    /// Remove the string drawn along the given edge of the border.
+    pub fn clear_edge_string(&mut self, edge: Edge) {
+        self.edge_strings.remove(&edge);
+    }
+
+    /// Returns the string drawn along the given edge of the border, if any.
+    pub fn edge_string(&self, edge: Edge) -> Option<&AlignedString> {
+        self.edge_strings.get(&edge)
+    }
+
+    /// Surround strings on the top and bottom edges with junction glyphs
+    /// that connect them to the border, ie: `┤title├`.
+    ///
+    /// Junction glyphs are chosen based on the edge glyph, and are only
+    /// supported for single and double line edges.
+    pub fn with_title_connectors(mut self) -> Self {
+        self.title_connectors = true;
+        self
+    }
+
+    /// Surround strings on the top and bottom edges with the given glyphs,
+    /// ie: `[title]`.
+    ///
+    /// The brackets take up a tile on either side of the string, so strings
+    /// longer than the edge minus two will be truncated.
+    pub fn with_title_brackets(mut self, left: char, right: char) -> Self {
+        self.title_brackets = Some([left, right]);
+        self
+    }
+
+    /// The glyphs surrounding strings on the given edge, if any.
+    fn string_brackets(&self, edge: Edge) -> Option<[char; 2]> {
+        if !matches!(edge, Edge::Top | Edge::Bottom) {
+            return None;
+        }
+        self.title_brackets
+            .or_else(|| connector_glyphs(self.edge_glyph(edge)).filter(|_| self.title_connectors))
+    }
+
+    /// Shade the border so it appears raised - the top and left sides will use
+    /// the `light` color and the bottom and right sides the `dark` color.
+    ///
+    /// Corners share the color of the top or bottom side they're on.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let border = Border::single_line().with_bevel(Color::WHITE, Color::srgb(0.3, 0.3, 0.3));
+    /// ```
+    pub fn with_bevel(mut self, light: Color, dark: Color) -> Self {
+        self.bevel = Some(Bevel {
+            light,
+            dark,
+            sunken: false,
+        });
+        self
+    }
+
+    /// Shade the border so it appears sunken - the top and left sides will use
+    /// the `dark` color and the bottom and right sides the `light` color.
+    ///
+    /// See [`Border::with_bevel`].
+    pub fn with_sunken_bevel(mut self, light: Color, dark: Color) -> Self {
+        self.bevel = Some(Bevel {
+            light,
+            dark,
+            sunken: true,
+        });
+        self
+    }
+
+    /// The foreground color for the glyphs on the given edge, if the border
+    /// has a [`Bevel`].
+    pub fn edge_color(&self, edge: Edge) -> Option<Color> {
+        let bevel = self.bevel?;
+        let top_left = matches!(
+            edge,
+            Edge::Top | Edge::Left | Edge::TopLeft | Edge::TopRight
+        );
+        Some(if top_left != bevel.sunken {
+            bevel.light
+        } else {
+            bevel.dark
+        })
+    }
+
+    /// Returns the positions and formatting for any strings drawn along the
+    /// border edges.
+    ///
+    /// `size` is the size of the border, including the corners. Positions
+    /// are relative to the bottom left corner of the border.
+    pub(crate) fn edge_string_tiles(&self, size: IVec2) -> Vec<(IVec2, FormattedTile)> {
+        let [w, h] = (size - 2).to_array();
+        let mut tiles = Vec::new();
+        for (edge, string) in self.edge_strings.iter() {
+            // Edge origin, direction and the available length
+            let (origin, dir, len) = match edge {
+                Edge::Top => (IVec2::new(1, h + 1), IVec2::X, w),
+                Edge::Bottom => (IVec2::new(1, 0), IVec2::X, w),
+                Edge::Left => (IVec2::new(0, h), IVec2::NEG_Y, h),
+                Edge::Right => (IVec2::new(w + 1, h), IVec2::NEG_Y, h),
+                _ => continue,
+            };
+            let brackets = self.string_brackets(*edge);
+            let max_len = if brackets.is_some() { len - 2 } else { len };
+            let text = truncate(&string.string, max_len);
+            let count = text.chars().count() as i32;
+            let offset = if brackets.is_some() {
+                // Keep the brackets on the edge
+                let free = (len - 2 - count).max(0);
+                1 + (string.align * free as f32).round() as i32
+            } else {
+                (string.align * len as f32).round() as i32
+                    - (count as f32 * string.align).round() as i32
+            };
+            let in_edge = |i: i32| i >= 0 && i < len;
+
+            for (i, ch) in text.chars().enumerate() {
+                let i = offset + i as i32;
+                if !in_edge(i) {
+                    continue;
+                }
+                let mut fmt = FormattedTile::new().glyph(ch);
+                if let Some(col) = string.fg_col {
+                    fmt = fmt.fg(col);
+                }
+                if let Some(col) = string.bg_col {
+                    fmt = fmt.bg(col);
+                }
+                tiles.push((origin + dir * i, fmt));
+            }
+
+            if count == 0 {
+                continue;
+            }
+            if let Some([left, right]) = brackets {
+                for (i, glyph) in [(offset - 1, left), (offset + count, right)] {
+                    if in_edge(i) {
+                        tiles.push((origin + dir * i, FormattedTile::new().glyph(glyph)));
+                    }
+                }
+            }
+        }
+        tiles
+    }
+}
+
+/// The glyphs used to draw a rectangle with [`Terminal::draw_box`](crate::Terminal::draw_box).
+///
+/// Unlike a [`Border`] both horizontal sides share a glyph, as do both
+/// vertical sides. Any [`Border`] can be converted to box glyphs, using it's
+/// top and left edge glyphs for the sides.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxGlyphs {
+    pub top_left: char,
+    pub top_right: char,
+    pub bottom_left: char,
+    pub bottom_right: char,
+    pub horizontal: char,
+    pub vertical: char,
+    /// An optional tile the inside of the box is filled with. If `None` the
+    /// inside of the box is left untouched.
+    pub fill: Option<Tile>,
+}
+
+impl Default for BoxGlyphs {
+    fn default() -> Self {
+        BoxGlyphs::single_line()
+    }
+}
+
+impl BoxGlyphs {
+    /// Box glyphs with single-line glyphs, the same as [`Border::single_line`].
+    pub fn single_line() -> Self {
+        Self::from(&Border::single_line())
+    }
+
+    /// Box glyphs with double-line glyphs, the same as [`Border::double_line`].
+    pub fn double_line() -> Self {
+        Self::from(&Border::double_line())
+    }
+
+    /// Fill the inside of the box with the given tile.
+    pub fn with_fill(mut self, fill: impl Into<Tile>) -> Self {
+        self.fill = Some(fill.into());
+        self
+    }
+}
+
+impl From<&Border> for BoxGlyphs {
+    fn from(border: &Border) -> Self {
+        BoxGlyphs {
+            top_left: border.top_left,
+            top_right: border.top_right,
+            bottom_left: border.bottom_left,
+            bottom_right: border.bottom_right,
+            horizontal: border.top,
+            vertical: border.left,
+            fill: None,
+        }
+    }
+}
+
+/// Shorten a string to at most `max_len` characters, replacing the end with
+/// "..." if it doesn't fit.
+fn truncate(string: &str, max_len: i32) -> Cow<str> {
+    const ELLIPSIS: &str = "...";
+    let max_len = max_len.max(0) as usize;
+    if string.chars().count() <= max_len {
+        return Cow::Borrowed(string);
+    }
+    if max_len <= ELLIPSIS.len() {
+        return string.chars().take(max_len).collect();
+    }
+    let mut truncated: String = string.chars().take(max_len - ELLIPSIS.len()).collect();
+    truncated.push_str(ELLIPSIS);
+    Cow::Owned(truncated)
+}
+
+/// Junction glyphs for connecting a horizontal border line to a string.
+fn connector_glyphs(line: char) -> Option<[char; 2]> {
+    match line {
+        '─' => Some(['┤', '├']),
+        '═' => Some(['╡', '╞']),
+        _ => None,
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Edge {
     Top,
     Left,
@@ -247,6 +588,7 @@ mod test {
     // use bevy::prelude::*;
 
     use super::*;
+    use crate::Tile;
 
     #[test]
     fn format_test() {
@@ -273,4 +615,129 @@ mod test {
         assert_eq!(glyphs.bottom_left, '└');
         assert_eq!(glyphs.bottom_right, '┘');
     }
+
+    #[test]
+    fn edge_and_corner() {
+        let border = Border::double_line()
+            .with_edge(Side::Left, '╟')
+            .with_corner(Pivot::TopLeft, '╤')
+            .with_corner(Pivot::BottomRight, '╧')
+            .with_corner(Pivot::Center, 'x');
+
+        assert_eq!('╟', border.left);
+        assert_eq!('╤', border.top_left);
+        assert_eq!('╧', border.bottom_right);
+        // Everything else is unchanged
+        let double = Border::double_line();
+        assert_eq!(double.right, border.right);
+        assert_eq!(double.top, border.top);
+        assert_eq!(double.top_right, border.top_right);
+        assert_eq!(double.bottom_left, border.bottom_left);
+    }
+
+    #[test]
+    fn box_glyphs() {
+        let glyphs = BoxGlyphs::double_line();
+        assert_eq!('╔', glyphs.top_left);
+        assert_eq!('═', glyphs.horizontal);
+        assert_eq!('║', glyphs.vertical);
+        assert_eq!(None, glyphs.fill);
+        let glyphs = BoxGlyphs::single_line().with_fill('.');
+        assert_eq!('┘', glyphs.bottom_right);
+        assert_eq!(Some(Tile::from('.')), glyphs.fill);
+    }
+
+    #[test]
+    fn bevel() {
+        let light = Color::WHITE;
+        let dark = Color::BLACK;
+        let raised = Border::single_line().with_bevel(light, dark);
+        for edge in [Edge::Top, Edge::Left, Edge::TopLeft, Edge::TopRight] {
+            assert_eq!(Some(light), raised.edge_color(edge));
+        }
+        for edge in [
+            Edge::Bottom,
+            Edge::Right,
+            Edge::BottomLeft,
+            Edge::BottomRight,
+        ] {
+            assert_eq!(Some(dark), raised.edge_color(edge));
+        }
+
+        let sunken = Border::single_line().with_sunken_bevel(light, dark);
+        assert_eq!(Some(dark), sunken.edge_color(Edge::Top));
+        assert_eq!(Some(light), sunken.edge_color(Edge::Right));
+
+        assert_eq!(None, Border::single_line().edge_color(Edge::Top));
+    }
+
+    #[test]
+    fn bottom_edge_string() {
+        let border = Border::single_line().with_edge_string(Edge::Bottom, "1/5".aligned(1.0));
+        let mut tiles: Vec<_> = border
+            .edge_string_tiles(IVec2::new(10, 5))
+            .into_iter()
+            .map(|(p, t)| (p.to_array(), Tile::from(t).glyph))
+            .collect();
+        tiles.sort_by_key(|(p, _)| p[0]);
+
+        assert_eq!(vec![([6, 0], '1'), ([7, 0], '/'), ([8, 0], '5')], tiles);
+    }
+
+    #[test]
+    fn title_connectors() {
+        let border = Border::single_line()
+            .with_title("Hi".aligned(0.5))
+            .with_title_connectors();
+        let mut tiles: Vec<_> = border
+            .edge_string_tiles(IVec2::new(10, 5))
+            .into_iter()
+            .map(|(p, t)| (p.to_array(), Tile::from(t).glyph))
+            .collect();
+        tiles.sort_by_key(|(p, _)| p[0]);
+
+        let expected = vec![([3, 4], '┤'), ([4, 4], 'H'), ([5, 4], 'i'), ([6, 4], '├')];
+        assert_eq!(expected, tiles);
+    }
+
+    fn top_edge(border: &Border, width: i32) -> String {
+        let mut tiles: Vec<_> = border
+            .edge_string_tiles(IVec2::new(width, 5))
+            .into_iter()
+            .map(|(p, t)| (p.x, Tile::from(t).glyph))
+            .collect();
+        tiles.sort_by_key(|(x, _)| *x);
+        let mut edge = vec![border.top; width as usize - 2];
+        for (x, ch) in tiles {
+            edge[x as usize - 1] = ch;
+        }
+        edge.into_iter().collect()
+    }
+
+    #[test]
+    fn title_brackets() {
+        let border = Border::single_line().with_title_brackets('[', ']');
+        let left = border.clone().with_title("Hi");
+        assert_eq!("[Hi]────", top_edge(&left, 10));
+        let center = border.clone().with_title("Hi".aligned(0.5));
+        assert_eq!("──[Hi]──", top_edge(&center, 10));
+        let right = border.clone().with_title("Hi".aligned(1.0));
+        assert_eq!("────[Hi]", top_edge(&right, 10));
+
+        // Brackets take priority over connectors
+        let border = border.with_title_connectors().with_title("Hi");
+        assert_eq!("[Hi]────", top_edge(&border, 10));
+    }
+
+    #[test]
+    fn truncated_title() {
+        let border = Border::single_line()
+            .with_title("Inventory".aligned(0.5))
+            .with_title_brackets('[', ']');
+        assert_eq!("[Inv...]", top_edge(&border, 10));
+        assert_eq!("[Inventory]", top_edge(&border, 13));
+
+        let border = Border::single_line().with_title("Inventory");
+        assert_eq!("Inven...", top_edge(&border, 10));
+    }
 }
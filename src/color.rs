@@ -0,0 +1,188 @@
+//! Color interpolation utilities.
+//!
+//! All interpolation is done in linear color space, which avoids the dark
+//! bands that appear when blending between bright colors in srgb space.
+
+use bevy::prelude::Color;
+
+/// Linearly interpolate between two colors in linear color space.
+///
+/// `t` is clamped to the range 0..=1.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::Color;
+/// use bevy_ascii_terminal::color::lerp_linear;
+///
+/// let grey = lerp_linear(Color::BLACK, Color::WHITE, 0.5);
+/// ```
+pub fn lerp_linear(a: Color, b: Color, t: f32) -> Color {
+    let t = t.clamp(0.0, 1.0);
+    let (a, b) = (a.to_linear(), b.to_linear());
+    let [r, g, bl, al] = [
+        (a.red, b.red),
+        (a.green, b.green),
+        (a.blue, b.blue),
+        (a.alpha, b.alpha),
+    ]
+    .map(|(a, b)| a + (b - a) * t);
+    Color::linear_rgba(r, g, bl, al)
+}
+
+/// A color gradient made of any number of color stops.
+///
+/// Each stop is a position along the gradient, usually in the range 0..=1,
+/// and a color. Sampling between two stops interpolates between their colors
+/// with [`lerp_linear`]. Sampling before the first stop or after the last stop
+/// returns the color of that stop.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::Color;
+/// use bevy_ascii_terminal::prelude::*;
+///
+/// let fire = Gradient::new([
+///     Color::srgb(0.2, 0.0, 0.0),
+///     Color::srgb(1.0, 0.3, 0.0),
+///     Color::srgb(1.0, 1.0, 0.6),
+/// ]);
+/// let mut term = Terminal::new([20, 1]);
+/// for x in 0..20 {
+///     term.put_char([x, 0], '█'.fg(fire.sample(x as f32 / 19.0)));
+/// }
+/// ```
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Gradient {
+    stops: Vec<(f32, Color)>,
+}
+
+impl Gradient {
+    /// Create a gradient from colors spaced evenly from 0 to 1.
+    pub fn new(colors: impl IntoIterator<Item = Color>) -> Self {
+        let colors: Vec<_> = colors.into_iter().collect();
+        let step = 1.0 / (colors.len().max(2) - 1) as f32;
+        let stops = colors
+            .into_iter()
+            .enumerate()
+            .map(|(i, c)| (i as f32 * step, c))
+            .collect();
+        Self { stops }
+    }
+
+    /// Create a gradient from `(position, color)` stops. The stops don't need
+    /// to be in order.
+    pub fn from_stops(stops: impl IntoIterator<Item = (f32, Color)>) -> Self {
+        let mut stops: Vec<_> = stops.into_iter().collect();
+        stops.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self { stops }
+    }
+
+    /// Add a color stop to the gradient.
+    pub fn with_stop(mut self, position: f32, color: Color) -> Self {
+        self.add_stop(position, color);
+        self
+    }
+
+    /// Add a color stop to the gradient.
+    pub fn add_stop(&mut self, position: f32, color: Color) {
+        let i = self.stops.partition_point(|(p, _)| *p <= position);
+        self.stops.insert(i, (position, color));
+    }
+
+    /// The color stops of the gradient, in order.
+    pub fn stops(&self) -> &[(f32, Color)] {
+        &self.stops
+    }
+
+    /// Sample the color of the gradient at the given position.
+    ///
+    /// An empty gradient is always white.
+    pub fn sample(&self, t: f32) -> Color {
+        let (Some(first), Some(last)) = (self.stops.first(), self.stops.last()) else {
+            return Color::WHITE;
+        };
+        if t <= first.0 {
+            return first.1;
+        }
+        if t >= last.0 {
+            return last.1;
+        }
+        let i = self.stops.partition_point(|(p, _)| *p <= t);
+        let ((p0, c0), (p1, c1)) = (self.stops[i - 1], self.stops[i]);
+        let width = p1 - p0;
+        if width <= 0.0 {
+            return c1;
+        }
+        lerp_linear(c0, c1, (t - p0) / width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Color;
+
+    use super::*;
+
+    fn approx_eq(a: Color, b: Color) -> bool {
+        let (a, b) = (a.to_linear(), b.to_linear());
+        [
+            a.red - b.red,
+            a.green - b.green,
+            a.blue - b.blue,
+            a.alpha - b.alpha,
+        ]
+        .iter()
+        .all(|d| d.abs() < 0.0001)
+    }
+
+    #[test]
+    fn lerp_is_linear() {
+        let mid = lerp_linear(Color::BLACK, Color::WHITE, 0.5).to_linear();
+        assert_eq!(0.5, mid.red);
+        // Brighter than an srgb midpoint
+        assert!(Color::from(mid).to_srgba().red > 0.5);
+        assert!(approx_eq(
+            Color::WHITE,
+            lerp_linear(Color::BLACK, Color::WHITE, 2.0)
+        ));
+    }
+
+    #[test]
+    fn multi_stop_gradient() {
+        let red = Color::srgb(1.0, 0.0, 0.0);
+        let green = Color::srgb(0.0, 1.0, 0.0);
+        let blue = Color::srgb(0.0, 0.0, 1.0);
+        let gradient = Gradient::new([red, green, blue]);
+
+        // Stop boundaries
+        assert!(approx_eq(red, gradient.sample(0.0)));
+        assert!(approx_eq(green, gradient.sample(0.5)));
+        assert!(approx_eq(blue, gradient.sample(1.0)));
+        assert!(approx_eq(red, gradient.sample(-1.0)));
+        assert!(approx_eq(blue, gradient.sample(3.0)));
+
+        // Midpoints
+        assert!(approx_eq(
+            lerp_linear(red, green, 0.5),
+            gradient.sample(0.25)
+        ));
+        assert!(approx_eq(
+            lerp_linear(green, blue, 0.5),
+            gradient.sample(0.75)
+        ));
+    }
+
+    #[test]
+    fn unordered_stops() {
+        let gradient = Gradient::from_stops([(1.0, Color::WHITE), (0.0, Color::BLACK)])
+            .with_stop(0.5, Color::BLACK);
+        assert_eq!(Color::BLACK, gradient.stops()[0].1);
+        assert!(approx_eq(Color::BLACK, gradient.sample(0.5)));
+        assert!(approx_eq(
+            lerp_linear(Color::BLACK, Color::WHITE, 0.5),
+            gradient.sample(0.75)
+        ));
+    }
+}
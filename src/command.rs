@@ -0,0 +1,136 @@
+//! Recordable drawing operations which can be replayed on a terminal.
+
+use bevy::{
+    math::{IVec2, UVec2},
+    prelude::Color,
+};
+
+use crate::{formatting::StringFormatter, Terminal, Tile};
+
+/// A single drawing operation which can be applied to a [`Terminal`].
+///
+/// Lists of commands can be recorded and replayed later with
+/// [`Terminal::apply_commands`], for example to implement an undo log or to
+/// mirror a terminal from a network stream. With the `serde` feature enabled
+/// commands can be serialized.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrawCommand {
+    /// Change the glyph of a tile without affecting it's colors.
+    PutChar { xy: IVec2, glyph: char },
+    /// Replace a tile.
+    PutTile { xy: IVec2, tile: Tile },
+    /// Write a string, optionally changing it's colors.
+    /// See [`Terminal::put_string`].
+    PutString {
+        xy: IVec2,
+        string: String,
+        fg: Option<Color>,
+        bg: Option<Color>,
+    },
+    /// Clear the entire terminal to it's `clear_tile`.
+    Clear,
+    /// Clear an area of the terminal to it's `clear_tile`.
+    ClearBox { xy: IVec2, size: UVec2 },
+    /// Replace every tile in the terminal.
+    Fill(Tile),
+    /// Change the foreground color of every tile.
+    FillFg(Color),
+    /// Change the background color of every tile.
+    FillBg(Color),
+}
+
+impl Terminal {
+    /// Apply a single [`DrawCommand`] to the terminal.
+    pub fn apply_command(&mut self, command: &DrawCommand) {
+        match command {
+            DrawCommand::PutChar { xy, glyph } => self.put_char(*xy, *glyph),
+            DrawCommand::PutTile { xy, tile } => self.put_tile(*xy, *tile),
+            DrawCommand::PutString { xy, string, fg, bg } => {
+                let mut fmt = string.as_str().formatted();
+                if let Some(fg) = fg {
+                    fmt = fmt.fg(*fg);
+                }
+                if let Some(bg) = bg {
+                    fmt = fmt.bg(*bg);
+                }
+                self.put_string(*xy, fmt);
+            }
+            DrawCommand::Clear => self.clear(),
+            DrawCommand::ClearBox { xy, size } => self.clear_box(*xy, *size),
+            DrawCommand::Fill(tile) => self.iter_mut().for_each(|t| *t = *tile),
+            DrawCommand::FillFg(color) => self.fill_fg(*color),
+            DrawCommand::FillBg(color) => self.fill_bg(*color),
+        }
+    }
+
+    /// Apply a list of [`DrawCommand`]s to the terminal, in order.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::math::IVec2;
+    ///
+    /// let commands = [
+    ///     DrawCommand::Clear,
+    ///     DrawCommand::PutChar { xy: IVec2::new(1, 1), glyph: '@' },
+    /// ];
+    /// let mut term = Terminal::new([10,10]);
+    /// term.apply_commands(&commands);
+    /// assert_eq!('@', term.get_char([1,1]));
+    /// ```
+    pub fn apply_commands(&mut self, commands: &[DrawCommand]) {
+        for command in commands {
+            self.apply_command(command);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        color::palettes::basic::{BLUE, RED},
+        math::{IVec2, UVec2},
+        prelude::Color,
+    };
+
+    use super::DrawCommand;
+    use crate::{formatting::StringFormatter, Terminal, Tile};
+
+    #[test]
+    fn apply_commands() {
+        let commands = vec![
+            DrawCommand::Fill(Tile::from('.')),
+            DrawCommand::FillBg(Color::Srgba(BLUE)),
+            DrawCommand::ClearBox {
+                xy: IVec2::new(0, 0),
+                size: UVec2::new(3, 3),
+            },
+            DrawCommand::PutChar {
+                xy: IVec2::new(5, 5),
+                glyph: '@',
+            },
+            DrawCommand::PutString {
+                xy: IVec2::new(1, 8),
+                string: "Hello".to_string(),
+                fg: Some(Color::Srgba(RED)),
+                bg: None,
+            },
+        ];
+        let mut replayed = Terminal::new([10, 10]);
+        replayed.apply_commands(&commands);
+
+        let mut expected = Terminal::new([10, 10]);
+        expected.iter_mut().for_each(|t| *t = Tile::from('.'));
+        expected.fill_bg(Color::Srgba(BLUE));
+        expected.clear_box([0, 0], [3, 3]);
+        expected.put_char([5, 5], '@');
+        expected.put_string([1, 8], "Hello".fg(Color::Srgba(RED)));
+
+        assert_eq!(expected.slice(), replayed.slice());
+        assert_eq!('@', replayed.get_char([5, 5]));
+        assert_eq!(' ', replayed.get_char([1, 1]));
+        assert_eq!(Color::Srgba(BLUE), replayed.get_tile([5, 5]).bg_color);
+    }
+}
@@ -0,0 +1,188 @@
+//! A temporary context for drawing to a terminal with clipping.
+
+use bevy::math::IVec2;
+use sark_grids::{GridPoint, Size2d};
+
+use crate::{formatting::StringFormatter, Terminal, Tile, TileFormatter};
+
+/// A rectangular clip region in terminal local space.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ClipRect {
+    min: IVec2,
+    /// Exclusive max
+    max: IVec2,
+}
+
+impl ClipRect {
+    fn intersect(&self, other: &ClipRect) -> ClipRect {
+        let min = self.min.max(other.min);
+        let max = self.max.min(other.max).max(min);
+        ClipRect { min, max }
+    }
+
+    fn contains(&self, xy: IVec2) -> bool {
+        xy.cmpge(self.min).all() && xy.cmplt(self.max).all()
+    }
+}
+
+/// A context for drawing to a terminal with a stack of clip regions.
+///
+/// Any clip regions pushed onto the context will be intersected with all
+/// currently active clip regions. Writes to positions outside the active clip
+/// region are ignored. This allows nested widgets to draw without spilling
+/// outside their parent's area.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy_ascii_terminal::prelude::*;
+///
+/// let mut term = Terminal::new([20,10]);
+/// let mut ctx = term.draw_context();
+///
+/// ctx.push_clip([2,2], [5,5]);
+/// // Only "Hello" will be written, the rest is clipped
+/// ctx.put_string([2,2], "Hello world");
+/// ctx.pop_clip();
+/// ```
+pub struct DrawContext<'a> {
+    term: &'a mut Terminal,
+    clips: Vec<ClipRect>,
+}
+
+impl<'a> DrawContext<'a> {
+    pub fn new(term: &'a mut Terminal) -> Self {
+        Self {
+            term,
+            clips: Vec::new(),
+        }
+    }
+
+    /// Push a clip region onto the stack. Subsequent writes will be clipped
+    /// to the intersection of this region and all active regions.
+    pub fn push_clip(&mut self, xy: impl GridPoint, size: impl Size2d) {
+        let min = xy.as_ivec2();
+        let clip = ClipRect {
+            min,
+            max: min + size.as_ivec2(),
+        };
+        let clip = match self.clips.last() {
+            Some(top) => top.intersect(&clip),
+            None => clip,
+        };
+        self.clips.push(clip);
+    }
+
+    /// Remove the most recently pushed clip region, restoring the previous one.
+    ///
+    /// Returns false if there was no clip region to remove.
+    pub fn pop_clip(&mut self) -> bool {
+        self.clips.pop().is_some()
+    }
+
+    /// The number of clip regions currently on the stack.
+    pub fn clip_depth(&self) -> usize {
+        self.clips.len()
+    }
+
+    /// Returns true if the given position can be written to - it's inside the
+    /// terminal and inside the active clip region.
+    ///
+    /// The position is converted to local space first, see
+    /// [`Terminal::local_point`].
+    pub fn is_visible(&self, xy: impl GridPoint) -> bool {
+        let xy = self.term.local_point(xy);
+        self.term.in_bounds(xy) && self.clips.last().is_none_or(|c| c.contains(xy))
+    }
+
+    /// Insert a formatted character into the terminal, if it's not clipped.
+    ///
+    /// See [`Terminal::put_char`].
+    pub fn put_char(&mut self, xy: impl GridPoint, writer: impl TileFormatter) {
+        if self.is_visible(xy) {
+            self.term.put_char(xy, writer);
+        }
+    }
+
+    /// Insert a [Tile] into the terminal, if it's not clipped.
+    pub fn put_tile(&mut self, xy: impl GridPoint, tile: Tile) {
+        if self.is_visible(xy) {
+            self.term.put_tile(xy, tile);
+        }
+    }
+
+    /// Write a formatted string to the terminal. Any characters outside the
+    /// active clip region will be skipped.
+    ///
+    /// See [`Terminal::put_string`].
+    pub fn put_string<'b>(&mut self, xy: impl GridPoint, writer: impl StringFormatter<'b> + 'b) {
+        let clip = self.clips.last().copied();
+        self.term.write_string(
+            xy,
+            writer,
+            |p| clip.is_none_or(|c| c.contains(p)),
+            |_, _, _| {},
+        );
+    }
+
+    /// Access the underlying terminal.
+    pub fn terminal(&self) -> &Terminal {
+        &*self.term
+    }
+}
+
+impl Terminal {
+    /// Begin drawing to the terminal through a [`DrawContext`].
+    pub fn draw_context(&mut self) -> DrawContext<'_> {
+        DrawContext::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sark_grids::Pivot;
+
+    use crate::Terminal;
+
+    #[test]
+    fn clip_stack() {
+        let mut term = Terminal::new([20, 10]);
+        let mut ctx = term.draw_context();
+
+        ctx.push_clip([2, 2], [5, 5]);
+        ctx.put_char([1, 1], 'a');
+        ctx.put_string([2, 2], "Hello world");
+
+        ctx.push_clip([4, 0], [10, 10]);
+        ctx.put_char([2, 3], 'b');
+        ctx.put_char([4, 3], 'c');
+        assert_eq!(2, ctx.clip_depth());
+
+        ctx.pop_clip();
+        ctx.put_char([2, 3], 'd');
+        ctx.pop_clip();
+        ctx.put_char([1, 1], 'e');
+        assert!(!ctx.pop_clip());
+
+        assert_eq!("Hello      ", term.get_string([2, 2], 11));
+        assert_eq!('d', term.get_char([2, 3]));
+        assert_eq!('c', term.get_char([4, 3]));
+        assert_eq!('e', term.get_char([1, 1]));
+    }
+
+    #[test]
+    fn clip_default_pivot() {
+        let mut term = Terminal::new([10, 10]).with_default_pivot(Pivot::TopLeft);
+        let mut ctx = term.draw_context();
+
+        // The top left corner of the terminal, in local space
+        ctx.push_clip([0, 8], [5, 2]);
+        assert!(ctx.is_visible([0, 0]));
+        assert!(!ctx.is_visible([6, 0]));
+        ctx.put_char([1, 1], 'a');
+        ctx.put_char([1, 2], 'b');
+
+        assert_eq!('a', term.get_char([1, 8]));
+        assert_eq!(' ', term.get_char([1, 7]));
+    }
+}
@@ -1,14 +1,18 @@
-use bevy::prelude::{default, Bundle, Changed, Component, Query, With};
+use bevy::prelude::{default, Bundle, Changed, Color, Component, Query, Vec2, Visibility, With};
 use sark_grids::{GridPoint, Pivot, Size2d};
 
 use crate::{
     renderer::{self, TileScaling},
-    Border, Terminal, TerminalFont, TerminalLayout,
+    Border, Terminal, TerminalFont, TerminalLayout, Tile,
 };
 
 /// A bundle with all the required components for a terminal.
 ///
 /// Can specify some properties of the terminal on initilaization.
+///
+/// The terminal respects bevy's [Visibility] component - setting it to
+/// [Visibility::Hidden] will stop the terminal and it's border from rendering
+/// without despawning it.
 #[derive(Bundle, Default)]
 pub struct TerminalBundle {
     pub terminal: Terminal,
@@ -36,7 +40,7 @@ impl TerminalBundle {
     /// Set the initial size of the terminal.
     pub fn with_size(mut self, size: impl Size2d) -> Self {
         self.terminal.resize(size.as_array());
-        self.layout.set_size(size.as_ivec2());
+        self.layout.set_size(self.terminal.size().as_ivec2());
         self
     }
 
@@ -69,21 +73,185 @@ impl TerminalBundle {
         self
     }
 
+    /// Sets the terminal's clear tile to a fully transparent tile and clears
+    /// the terminal. Useful for overlay terminals.
+    pub fn with_transparent_clear_tile(mut self) -> Self {
+        let tile = Tile::transparent();
+        self.terminal.clear_tile = tile;
+        self.terminal.clear();
+        self.layout.set_clear_tile(tile);
+        self
+    }
+
+    /// Sets the initial [Visibility] for the terminal.
+    pub fn with_visibility(mut self, visibility: Visibility) -> Self {
+        self.renderer.render_bundle.visibility = visibility;
+        self
+    }
+
+    /// Offset every glyph within its tile, in tiles.
+    ///
+    /// See [`TerminalLayout::glyph_offset`].
+    pub fn with_glyph_offset(mut self, offset: Vec2) -> Self {
+        self.layout.glyph_offset = offset;
+        self
+    }
+
+    /// Set the size of each tile in world units when using world scaling.
+    ///
+    /// See [`TerminalLayout::units_per_tile`].
+    pub fn with_units_per_tile(mut self, units: Vec2) -> Self {
+        self.layout.units_per_tile = units;
+        self
+    }
+
+    /// Only update the terminal mesh for tiles that changed since the last
+    /// frame.
+    ///
+    /// See [`TerminalLayout::dirty_tracking`].
+    pub fn with_dirty_tracking(mut self, dirty_tracking: bool) -> Self {
+        self.layout.dirty_tracking = dirty_tracking;
+        self
+    }
+
+    /// Draw a single quad of the given color behind the terminal's tiles.
+    ///
+    /// See [`TerminalLayout::backdrop`].
+    pub fn with_backdrop(mut self, color: Color) -> Self {
+        self.layout.backdrop = Some(color);
+        self
+    }
+
     /// Sets the [TileScaling] for the terminal.
     pub fn with_tile_scaling(mut self, scaling: TileScaling) -> Self {
         self.layout.scaling = scaling;
         self
     }
+
+    /// Leave invisible tiles out of the terminal mesh.
+    ///
+    /// See [`TerminalLayout::skip_invisible_tiles`].
+    pub fn with_skip_invisible_tiles(mut self, skip: bool) -> Self {
+        self.layout.skip_invisible_tiles = skip;
+        self
+    }
 }
 
 /// If this component is added to a terminal the terminal will automatically be
 /// cleared after every render.
+///
+/// Tiles marked as sticky with [`Terminal::set_sticky`] will not be cleared.
 #[derive(Default, Debug, Component)]
 pub struct ClearAfterRender;
 
+/// A component holding a closure which will be called every frame to draw
+/// to the terminal. A quick way to draw something without writing a system.
+///
+/// The closure is called during [`Update`](bevy::prelude::Update) and the
+/// terminal is always flagged as changed afterwards.
+///
+/// The closure must be `'static`, `Send` and `Sync`, so any captured state must
+/// be moved into it. State that's modified by the closure can be captured as
+/// normal - the closure is `FnMut`. For state that must be shared with other
+/// systems, write a regular system instead.
+///
+/// # Example
+///
+/// ```rust no_run
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::*;
+///
+/// fn setup(mut commands: Commands) {
+///     let mut frame = 0;
+///     commands.spawn((
+///         TerminalBundle::from(Terminal::new([20, 3])),
+///         TerminalDrawFn::new(move |term| {
+///             frame += 1;
+///             term.clear();
+///             term.put_string([0, 0], format!("Frame {}", frame));
+///         }),
+///         AutoCamera,
+///     ));
+/// }
+/// ```
+#[derive(Component)]
+pub struct TerminalDrawFn(Box<dyn FnMut(&mut Terminal) + Send + Sync>);
+
+impl TerminalDrawFn {
+    pub fn new(draw: impl FnMut(&mut Terminal) + Send + Sync + 'static) -> Self {
+        Self(Box::new(draw))
+    }
+}
+
+pub(crate) fn run_draw_fns(mut q_term: Query<(&mut Terminal, &mut TerminalDrawFn)>) {
+    for (mut term, mut draw) in &mut q_term {
+        (draw.0)(&mut term);
+    }
+}
+
 pub(crate) fn clear_after_render(
     mut q_term: Query<&mut Terminal, (Changed<Terminal>, With<ClearAfterRender>)>,
 ) {
-    q_term.iter_mut().for_each(|mut t| t.clear());
+    q_term.iter_mut().for_each(|mut t| t.clear_non_sticky());
 }
 
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        prelude::{App, Assets, BuildWorldChildren, InheritedVisibility, Mesh, Visibility},
+        render::view::VisibilityPlugin,
+    };
+
+    use super::TerminalBundle;
+    use crate::{renderer::TerminalRenderBundle, Terminal, Tile};
+
+    #[test]
+    fn with_visibility() {
+        let bundle = TerminalBundle::new().with_visibility(Visibility::Hidden);
+        assert_eq!(Visibility::Hidden, bundle.renderer.render_bundle.visibility);
+
+        let bundle = bundle.with_visibility(Visibility::Inherited);
+        assert_eq!(
+            Visibility::Inherited,
+            bundle.renderer.render_bundle.visibility
+        );
+    }
+
+    #[test]
+    fn hidden_terminal_hides_border() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Mesh>>()
+            .add_plugins(VisibilityPlugin);
+
+        let term = app
+            .world_mut()
+            .spawn(TerminalBundle::new().with_visibility(Visibility::Hidden))
+            .id();
+        // The border is spawned as a child of the terminal
+        let border = app.world_mut().spawn(TerminalRenderBundle::default()).id();
+        app.world_mut().entity_mut(term).push_children(&[border]);
+        let visible = |app: &App| {
+            [term, border].map(|e| app.world().get::<InheritedVisibility>(e).unwrap().get())
+        };
+
+        app.update();
+        assert_eq!([false, false], visible(&app));
+
+        *app.world_mut().get_mut::<Visibility>(term).unwrap() = Visibility::Visible;
+        app.update();
+        assert_eq!([true, true], visible(&app));
+    }
+
+    #[test]
+    fn with_transparent_clear_tile() {
+        let mut term = Terminal::new([5, 5]);
+        term.put_char([1, 1], 'a');
+        let bundle = TerminalBundle::from(term)
+            .with_transparent_clear_tile()
+            .with_visibility(Visibility::Hidden);
+
+        assert_eq!(Tile::transparent(), bundle.terminal.clear_tile);
+        assert_eq!(Tile::transparent(), bundle.layout.clear_tile());
+        assert!(bundle.terminal.iter().all(|t| *t == Tile::transparent()));
+    }
+}
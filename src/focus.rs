@@ -0,0 +1,251 @@
+//! Keyboard focus for interactive terminal widgets.
+//!
+//! Only one [`Focusable`] entity can have focus at a time, tracked by the
+//! [`TerminalFocus`] resource. Key presses are routed to the focused entity
+//! as [`FocusedKeyInput`] events, and `Tab`/`Shift+Tab` move focus between
+//! focusable entities.
+
+use bevy::{
+    input::{ButtonInput, InputSystem},
+    prelude::{
+        App, Component, Entity, Event, EventWriter, IntoSystemConfigs, KeyCode, Plugin, PreUpdate,
+        Query, Res, ResMut, Resource,
+    },
+};
+
+pub(crate) struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TerminalFocus>()
+            .add_event::<FocusedKeyInput>()
+            .add_systems(
+                PreUpdate,
+                (validate_focus, route_keyboard_input)
+                    .chain()
+                    .after(InputSystem),
+            );
+    }
+}
+
+/// Tracks which entity currently has keyboard focus.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Resource)]
+pub struct TerminalFocus {
+    pub entity: Option<Entity>,
+}
+
+impl TerminalFocus {
+    /// Give keyboard focus to the given entity.
+    pub fn focus(&mut self, entity: Entity) {
+        self.entity = Some(entity);
+    }
+
+    /// Remove keyboard focus from any entity.
+    pub fn clear(&mut self) {
+        self.entity = None;
+    }
+
+    /// Returns true if the given entity has keyboard focus.
+    pub fn is_focused(&self, entity: Entity) -> bool {
+        self.entity == Some(entity)
+    }
+
+    /// Move focus to the entity after the currently focused one in `order`,
+    /// wrapping around to the start. If nothing is focused the first entity
+    /// gains focus.
+    pub fn focus_next(&mut self, order: &[Entity]) {
+        self.step(order, 1);
+    }
+
+    /// Move focus to the entity before the currently focused one in `order`,
+    /// wrapping around to the end. If nothing is focused the last entity
+    /// gains focus.
+    pub fn focus_prev(&mut self, order: &[Entity]) {
+        self.step(order, -1);
+    }
+
+    fn step(&mut self, order: &[Entity], dir: isize) {
+        if order.is_empty() {
+            self.entity = None;
+            return;
+        }
+        let len = order.len() as isize;
+        let current = self
+            .entity
+            .and_then(|e| order.iter().position(|o| *o == e))
+            .map(|i| i as isize);
+        let next = match current {
+            Some(i) => (i + dir).rem_euclid(len),
+            None if dir > 0 => 0,
+            None => len - 1,
+        };
+        self.entity = Some(order[next as usize]);
+    }
+}
+
+/// Marks an entity as able to receive keyboard focus.
+///
+/// Focus moves between focusable entities in order of their `tab_index`.
+/// Entities with the same `tab_index` are ordered by when they were spawned.
+#[derive(Debug, Default, Clone, Copy, Component)]
+pub struct Focusable {
+    pub tab_index: i32,
+}
+
+/// A key press routed to the entity with keyboard focus.
+///
+/// Input consuming widgets should read these events and only react to the
+/// ones targeting their own entity.
+///
+/// # Example
+///
+/// ```rust
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::prelude::*;
+///
+/// #[derive(Component)]
+/// struct InputField(String);
+///
+/// fn input_field(
+///     mut events: EventReader<FocusedKeyInput>,
+///     mut q_fields: Query<&mut InputField>,
+/// ) {
+///     for ev in events.read() {
+///         if let Ok(mut field) = q_fields.get_mut(ev.target) {
+///             if ev.key == KeyCode::Backspace {
+///                 field.0.pop();
+///             }
+///         }
+///     }
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub struct FocusedKeyInput {
+    /// The entity with keyboard focus.
+    pub target: Entity,
+    pub key: KeyCode,
+}
+
+/// The focus order of all focusable entities.
+fn tab_order(q_focusable: &Query<(Entity, &Focusable)>) -> Vec<Entity> {
+    let mut order: Vec<_> = q_focusable.iter().collect();
+    order.sort_by_key(|(e, f)| (f.tab_index, *e));
+    order.into_iter().map(|(e, _)| e).collect()
+}
+
+/// Drop focus from entities which are no longer focusable.
+fn validate_focus(mut focus: ResMut<TerminalFocus>, q_focusable: Query<(Entity, &Focusable)>) {
+    if let Some(entity) = focus.entity {
+        if !q_focusable.contains(entity) {
+            focus.clear();
+        }
+    }
+}
+
+fn route_keyboard_input(
+    keys: Option<Res<ButtonInput<KeyCode>>>,
+    mut focus: ResMut<TerminalFocus>,
+    q_focusable: Query<(Entity, &Focusable)>,
+    mut events: EventWriter<FocusedKeyInput>,
+) {
+    let Some(keys) = keys else {
+        return;
+    };
+
+    if keys.just_pressed(KeyCode::Tab) {
+        let order = tab_order(&q_focusable);
+        if keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]) {
+            focus.focus_prev(&order);
+        } else {
+            focus.focus_next(&order);
+        }
+        return;
+    }
+
+    let Some(target) = focus.entity else {
+        return;
+    };
+    for key in keys.get_just_pressed() {
+        events.send(FocusedKeyInput { target, key: *key });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use super::*;
+
+    #[derive(Component, Default)]
+    struct Presses(usize);
+
+    fn consume_keys(mut events: EventReader<FocusedKeyInput>, mut q: Query<&mut Presses>) {
+        for ev in events.read() {
+            if let Ok(mut presses) = q.get_mut(ev.target) {
+                presses.0 += 1;
+            }
+        }
+    }
+
+    fn app() -> App {
+        let mut app = App::new();
+        app.init_resource::<ButtonInput<KeyCode>>()
+            .add_plugins(FocusPlugin)
+            .add_systems(Update, consume_keys);
+        app
+    }
+
+    #[test]
+    fn only_focused_consumes() {
+        let mut app = app();
+        let a = app
+            .world_mut()
+            .spawn((Focusable::default(), Presses(0)))
+            .id();
+        let b = app
+            .world_mut()
+            .spawn((Focusable::default(), Presses(0)))
+            .id();
+        app.world_mut().resource_mut::<TerminalFocus>().focus(b);
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::KeyA);
+        app.update();
+
+        assert_eq!(0, app.world().get::<Presses>(a).unwrap().0);
+        assert_eq!(1, app.world().get::<Presses>(b).unwrap().0);
+    }
+
+    #[test]
+    fn tab_moves_focus() {
+        let mut app = app();
+        let a = app.world_mut().spawn(Focusable { tab_index: 1 }).id();
+        let b = app.world_mut().spawn(Focusable { tab_index: 0 }).id();
+
+        app.world_mut()
+            .resource_mut::<ButtonInput<KeyCode>>()
+            .press(KeyCode::Tab);
+        app.update();
+        assert!(app.world().resource::<TerminalFocus>().is_focused(b));
+
+        let mut keys = app.world_mut().resource_mut::<ButtonInput<KeyCode>>();
+        keys.clear();
+        keys.release(KeyCode::Tab);
+        keys.press(KeyCode::Tab);
+        app.update();
+        assert!(app.world().resource::<TerminalFocus>().is_focused(a));
+    }
+
+    #[test]
+    fn focus_wraps() {
+        let order = [Entity::from_raw(0), Entity::from_raw(1)];
+        let mut focus = TerminalFocus::default();
+        focus.focus_prev(&order);
+        assert_eq!(Some(order[1]), focus.entity);
+        focus.focus_next(&order);
+        assert_eq!(Some(order[0]), focus.entity);
+        focus.focus_next(&[]);
+        assert_eq!(None, focus.entity);
+    }
+}
@@ -22,6 +22,61 @@ pub trait StringFormatter<'a>: Clone {
     /// Get the formatted string.
     fn formatted(self) -> FormattedString<'a>;
 
+    /// Underline the string.
+    ///
+    /// The line is drawn as an overlay glyph (`_`) in the foreground color of
+    /// each character, see [`Terminal::put_overlay`](crate::Terminal::put_overlay).
+    /// A tile can only have one overlay, so this replaces any strikethrough.
+    fn underline(self) -> FormattedString<'a> {
+        let mut fmt = self.formatted();
+        fmt.decoration = Some('_');
+        fmt
+    }
+
+    /// Draw a line through the string.
+    ///
+    /// The line is drawn as an overlay glyph (`─`) in the foreground color of
+    /// each character, see [`Terminal::put_overlay`](crate::Terminal::put_overlay).
+    /// A tile can only have one overlay, so this replaces any underline.
+    fn strikethrough(self) -> FormattedString<'a> {
+        let mut fmt = self.formatted();
+        fmt.decoration = Some('─');
+        fmt
+    }
+
+    /// Word wrap the string to fit in the terminal.
+    ///
+    /// Lines are broken at whitespace and continue on the row below, words
+    /// too long to fit on a line are split at the edge of the terminal. The
+    /// width available depends on the write position and pivot, a string
+    /// with no pivot wraps at the right edge of the terminal.
+    fn wrapped(self) -> FormattedString<'a> {
+        let mut fmt = self.formatted();
+        fmt.wrap = true;
+        fmt
+    }
+
+    /// Continue any part of a line that runs past the edge of the terminal on
+    /// the row below, starting at the same x position.
+    ///
+    /// Unlike [`StringFormatter::wrapped`] lines are split at the edge of the
+    /// terminal no matter where words begin or end, which keeps fixed width
+    /// columns aligned. Rows past the bottom of the terminal are cut off.
+    /// Ignored if the string is also wrapped.
+    fn continue_on_overflow(self) -> FormattedString<'a> {
+        let mut fmt = self.formatted();
+        fmt.overflow = true;
+        fmt
+    }
+
+    /// Make the background of the string pulse over time, see
+    /// [`Terminal::set_pulse`](crate::Terminal::set_pulse).
+    fn pulse(self) -> FormattedString<'a> {
+        let mut fmt = self.formatted();
+        fmt.pulse = true;
+        fmt
+    }
+
     fn apply(&self, tile: &mut Tile);
 }
 
@@ -29,6 +84,12 @@ pub trait StringFormatter<'a>: Clone {
 pub struct FormattedString<'a> {
     pub string: Cow<'a, str>,
     pub writes: ArrayVec<StringModifier, 3>,
+    /// The overlay glyph drawn over each character, see
+    /// [`StringFormatter::underline`].
+    decoration: Option<char>,
+    wrap: bool,
+    overflow: bool,
+    pulse: bool,
 }
 
 impl<'a> FormattedString<'a> {
@@ -38,6 +99,48 @@ impl<'a> FormattedString<'a> {
             ..Default::default()
         }
     }
+
+    /// The same formatting applied to a different string.
+    pub(crate) fn with_string<'b>(&self, string: impl Into<Cow<'b, str>>) -> FormattedString<'b> {
+        FormattedString {
+            string: string.into(),
+            writes: self.writes.clone(),
+            decoration: self.decoration,
+            wrap: self.wrap,
+            overflow: self.overflow,
+            pulse: self.pulse,
+        }
+    }
+
+    /// Add a color modifier, replacing any earlier one of the same kind.
+    fn set_color(&mut self, modifier: StringModifier) {
+        let same = std::mem::discriminant(&modifier);
+        self.writes.retain(|w| std::mem::discriminant(w) != same);
+        self.writes.push(modifier);
+    }
+
+    /// The overlay glyph used to draw the string's line decoration, if any.
+    pub fn decoration_glyph(&self) -> Option<char> {
+        self.decoration
+    }
+
+    /// Returns true if the string should be word wrapped, see
+    /// [`StringFormatter::wrapped`].
+    pub fn is_wrapped(&self) -> bool {
+        self.wrap
+    }
+
+    /// Returns true if lines that overflow should continue on the next row,
+    /// see [`StringFormatter::continue_on_overflow`].
+    pub fn continues_on_overflow(&self) -> bool {
+        self.overflow
+    }
+
+    /// Returns true if the string's background should pulse, see
+    /// [`StringFormatter::pulse`].
+    pub fn is_pulsing(&self) -> bool {
+        self.pulse
+    }
 }
 
 impl<'a> StringFormatter<'a> for FormattedString<'a> {
@@ -46,7 +149,7 @@ impl<'a> StringFormatter<'a> for FormattedString<'a> {
     }
 
     fn fg(mut self, color: Color) -> FormattedString<'a> {
-        self.writes.push(StringModifier::FgColor(color));
+        self.set_color(StringModifier::FgColor(color));
         self
     }
 
@@ -55,7 +158,7 @@ impl<'a> StringFormatter<'a> for FormattedString<'a> {
     }
 
     fn bg(mut self, color: Color) -> FormattedString<'a> {
-        self.writes.push(StringModifier::BgColor(color));
+        self.set_color(StringModifier::BgColor(color));
         self
     }
 
@@ -95,9 +198,7 @@ impl<'a> StringFormatter<'a> for String {
     }
 
     fn fg(self, color: Color) -> FormattedString<'a> {
-        let mut fmt = FormattedString::new(self);
-        fmt.writes.push(StringModifier::FgColor(color));
-        fmt
+        FormattedString::new(self).fg(color)
     }
 
     fn formatted(self) -> FormattedString<'a> {
@@ -155,6 +256,87 @@ mod test {
         assert_eq!(term.get_tile([5, 5]).fg_color, Color::Srgba(GREEN));
     }
 
+    #[test]
+    fn underline() {
+        let mut term = Terminal::new([15, 15]);
+        term.put_string([5, 5], "Hello".fg(Color::Srgba(GREEN)).underline());
+
+        assert_eq!("Hello", term.get_string([5, 5], 5));
+        for x in 5..10 {
+            assert_eq!(Some(('_', Color::Srgba(GREEN))), term.overlay([x, 5]));
+        }
+        assert_eq!(None, term.overlay([10, 5]));
+    }
+
+    #[test]
+    fn strikethrough_replaces_underline() {
+        let mut term = Terminal::new([15, 15]);
+        term.put_string([0, 0], "Hi".underline().strikethrough());
+
+        assert_eq!('─', term.overlay([1, 0]).unwrap().0);
+        assert_eq!('H', term.get_char([0, 0]));
+    }
+
+    #[test]
+    fn all_modifiers() {
+        let mut term = Terminal::new([15, 15]);
+        let text = "Hi"
+            .fg(Color::WHITE)
+            .fg(Color::Srgba(GREEN))
+            .bg(Color::BLACK)
+            .bg(Color::WHITE)
+            .underline()
+            .wrapped()
+            .continue_on_overflow()
+            .pulse();
+        assert_eq!(2, text.writes.len());
+
+        term.put_string([0, 0], text);
+        let tile = term.get_tile([0, 0]);
+        assert_eq!(Color::Srgba(GREEN), tile.fg_color);
+        assert_eq!(Color::WHITE, tile.bg_color);
+    }
+
+    #[test]
+    fn wrapped() {
+        let mut term = Terminal::new([10, 5]);
+        let text = "The quick brown fox".fg(Color::Srgba(GREEN)).wrapped();
+        let rows = term.put_string([2, 0].pivot(Pivot::TopLeft), text);
+
+        assert_eq!(4, rows);
+        assert_eq!("The     ", term.get_string([2, 4], 8));
+        assert_eq!("quick   ", term.get_string([2, 3], 8));
+        assert_eq!("brown   ", term.get_string([2, 2], 8));
+        assert_eq!("fox", term.get_string([2, 1], 3));
+        assert_eq!(Color::Srgba(GREEN), term.get_tile([2, 1]).fg_color);
+
+        // Long words are split at the edge
+        term.clear();
+        let rows = term.put_string([0, 0].pivot(Pivot::TopRight), "abcdefghijklm".wrapped());
+        assert_eq!(2, rows);
+        assert_eq!("abcdefghij", term.get_string([0, 4], 10));
+        assert_eq!("klm", term.get_string([7, 3], 3));
+    }
+
+    #[test]
+    fn continue_on_overflow() {
+        let mut term = Terminal::new([10, 5]);
+        let text = "abc defghijkl".continue_on_overflow();
+        let rows = term.put_string([3, 0].pivot(Pivot::TopLeft), text);
+
+        assert_eq!(2, rows);
+        assert_eq!("abc def", term.get_string([3, 4], 7));
+        assert_eq!("ghijkl ", term.get_string([3, 3], 7));
+
+        // Rows past the bottom of the terminal are cut off
+        term.clear();
+        let text = "0123456789abcdefghijABCDE".continue_on_overflow();
+        term.put_string([0, 3].pivot(Pivot::TopLeft), text);
+        assert_eq!("0123456789", term.get_string([0, 1], 10));
+        assert_eq!("abcdefghij", term.get_string([0, 0], 10));
+        assert_eq!("          ", term.get_string([0, 4], 10));
+    }
+
     #[test]
     fn pivot_top_right() {
         let mut term = Terminal::new([20, 20]);
@@ -39,14 +39,14 @@ pub trait TileFormatter: Clone {
 ///
 /// Formatting allows you to create an object that specifies certain aspects
 /// to modify without necessarily replacing an entire tile.
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Default, Clone, PartialEq)]
 pub struct FormattedTile {
     /// Modifications to be applied to a tile.
     modifications: ArrayVec<TileModification, 3>,
 }
 
 /// Modifications that can be applied to a terminal tile.
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TileModification {
     /// Change the glyph of a tile.
     Glyph(char),
@@ -89,6 +89,10 @@ impl FormattedTile {
     pub(crate) fn draw(&self, xy: impl GridPoint, term: &mut Terminal) {
         let t = term.get_tile_mut(xy);
         self.apply(t);
+        if self.iter().any(|m| matches!(m, TileModification::Glyph(_))) {
+            let glyph = term.substitute_glyph(term.get_tile(xy).glyph);
+            term.get_tile_mut(xy).glyph = glyph;
+        }
     }
 }
 
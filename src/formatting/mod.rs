@@ -1,8 +1,10 @@
 pub(crate) mod fmt_string;
 pub(crate) mod fmt_tile;
+pub(crate) mod wrap;
 
 pub use fmt_string::FormattedString;
 pub use fmt_string::StringFormatter;
 pub use fmt_tile::ColorFormatter;
 pub use fmt_tile::FormattedTile;
 pub use fmt_tile::TileFormatter;
+pub use fmt_tile::TileModification;
@@ -0,0 +1,78 @@
+//! Utilities for word wrapping strings to a fixed width.
+
+/// Split a string into lines no wider than `width` characters.
+///
+/// Lines are broken at whitespace where possible, words that are longer than
+/// `width` are split across multiple lines. Existing line returns are
+/// preserved. The returned lines are slices of the original string with
+/// trailing whitespace removed.
+///
+/// Non-breaking spaces (`'\u{A0}'`) are never broken on or trimmed, so words
+/// joined by them, like "Level\u{A0}1", always end up on the same line. They
+/// render as a normal space.
+pub(crate) fn wrap_words(string: &str, width: usize) -> Vec<&str> {
+    let mut lines = Vec::new();
+    if width == 0 {
+        return lines;
+    }
+
+    for line in string.lines() {
+        let mut rest = line;
+        loop {
+            if rest.chars().count() <= width {
+                lines.push(rest.trim_end_matches(is_break));
+                break;
+            }
+
+            // Byte index of the first char that doesn't fit on this line
+            let (end, ch) = rest.char_indices().nth(width).unwrap();
+            let split = match rest[..end + ch.len_utf8()].rfind(is_break) {
+                Some(i) if i > 0 => i,
+                _ => end,
+            };
+
+            lines.push(rest[..split].trim_end_matches(is_break));
+            rest = rest[split..].trim_start_matches(is_break);
+            if rest.is_empty() {
+                break;
+            }
+        }
+    }
+
+    lines
+}
+
+/// Whitespace that a line can be broken on.
+pub(crate) fn is_break(c: char) -> bool {
+    c.is_whitespace() && c != '\u{A0}'
+}
+
+#[cfg(test)]
+mod tests {
+    use super::wrap_words;
+
+    #[test]
+    fn wrap_at_whitespace() {
+        let lines = wrap_words("Hello there how are you", 10);
+        assert_eq!(vec!["Hello", "there how", "are you"], lines);
+    }
+
+    #[test]
+    fn wrap_long_word() {
+        let lines = wrap_words("abcdefghij klm", 4);
+        assert_eq!(vec!["abcd", "efgh", "ij", "klm"], lines);
+    }
+
+    #[test]
+    fn wrap_non_breaking_space() {
+        // "Level" would fit on the first line if it could be split from the 1
+        let lines = wrap_words("Go to Level\u{A0}1 now", 12);
+        assert_eq!(vec!["Go to", "Level\u{A0}1 now"], lines);
+    }
+
+    #[test]
+    fn wrap_preserves_line_returns() {
+        let lines = wrap_words("Hi\nHow are you", 20);
+        assert_eq!(vec!["Hi", "How are you"], lines);
+    }
+}
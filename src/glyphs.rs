@@ -0,0 +1,79 @@
+//! Tiles for common roguelike map elements.
+//!
+//! These follow the conventions of traditional roguelikes and are meant to
+//! speed up prototyping. Use struct update syntax to change their colors.
+//!
+//! # Example
+//!
+//! ```rust
+//! use bevy::prelude::Color;
+//! use bevy_ascii_terminal::{glyphs, Terminal, Tile};
+//!
+//! let mut term = Terminal::new([10, 10]);
+//! term.clear_tile = glyphs::FLOOR;
+//! term.clear();
+//! term.put_tile([0, 5], glyphs::WALL);
+//! term.put_tile([3, 5], glyphs::DOOR);
+//! let lava = Tile {
+//!     fg_color: Color::srgb(1.0, 0.4, 0.0),
+//!     ..glyphs::WATER
+//! };
+//! term.put_tile([6, 6], lava);
+//! ```
+
+use bevy::prelude::Color;
+
+use crate::Tile;
+
+/// A grey `.` floor tile.
+pub const FLOOR: Tile = Tile {
+    glyph: '.',
+    fg_color: Color::srgb(0.4, 0.4, 0.4),
+    bg_color: Color::BLACK,
+};
+
+/// A light grey `#` wall tile.
+pub const WALL: Tile = Tile {
+    glyph: '#',
+    fg_color: Color::srgb(0.75, 0.75, 0.75),
+    bg_color: Color::BLACK,
+};
+
+/// A brown `+` door tile.
+pub const DOOR: Tile = Tile {
+    glyph: '+',
+    fg_color: Color::srgb(0.6, 0.4, 0.2),
+    bg_color: Color::BLACK,
+};
+
+/// A blue `~` water tile.
+pub const WATER: Tile = Tile {
+    glyph: '~',
+    fg_color: Color::srgb(0.2, 0.5, 1.0),
+    bg_color: Color::srgb(0.0, 0.1, 0.3),
+};
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Color;
+
+    use super::*;
+    use crate::Terminal;
+
+    #[test]
+    fn map_tiles() {
+        let mut term = Terminal::new([5, 5]).with_clear_tile(FLOOR);
+        term.put_tile([1, 1], WALL);
+        let lava = Tile {
+            fg_color: Color::srgb(1.0, 0.4, 0.0),
+            ..WATER
+        };
+        term.put_tile([2, 1], lava);
+
+        assert_eq!(FLOOR, *term.get_tile([0, 0]));
+        assert_eq!(WALL, *term.get_tile([1, 1]));
+        assert_eq!("#~", term.get_string([1, 1], 2));
+        assert_eq!(WATER.bg_color, term.get_tile([2, 1]).bg_color);
+        assert_eq!(23, term.iter().filter(|t| **t == FLOOR).count());
+    }
+}
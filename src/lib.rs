@@ -52,22 +52,42 @@
 //! | 0.8.1 | 0.11.1-4            |
 //! | 0.8   | 0.11                |
 //! | 0.7   | 0.9-0.10            |
+pub mod ansi;
 mod border;
+pub mod color;
+mod command;
+mod draw_context;
 mod entity;
+mod focus;
 mod formatting;
+pub mod glyphs;
+mod line;
 mod renderer;
+#[cfg(feature = "serde")]
+mod serialize;
 mod terminal;
+mod tile_layer;
 mod to_world;
+mod tween;
 
-use bevy::prelude::{App, IntoSystemConfigs, Last, Plugin};
+use bevy::{
+    ecs::schedule::InternedSystemSet,
+    prelude::{App, IntoSystemConfigs, Last, Plugin, SystemSet, Update},
+};
 #[cfg(feature = "camera")]
-pub use renderer::{AutoCamera, TiledCamera, TiledCameraBundle};
+pub use renderer::{AutoCamera, AutoCameraSettings, TiledCamera, TiledCameraBundle};
 
-pub use renderer::{code_page_437, TerminalFont, TerminalLayout, TerminalMaterial};
+pub use renderer::{
+    code_page_437, TerminalFont, TerminalLayout, TerminalMaterial, TerminalMeshData,
+    TerminalShader, UvMapping,
+};
 
-pub use to_world::ToWorld;
+pub use terminal::InvalidSizeError;
+#[cfg(feature = "debug_writes")]
+pub use terminal::WriteConflict;
+pub use to_world::{TerminalLinkClicked, ToWorld};
 
-pub use sark_grids::{grid::Side, GridPoint, Pivot, Size2d};
+pub use sark_grids::{geometry::GridRect, grid::Side, GridPoint, Pivot, Size2d};
 
 /// The primary terminal rendering function labels
 pub use crate::renderer::{
@@ -79,24 +99,144 @@ pub use prelude::*;
 
 pub mod prelude {
     #[cfg(feature = "camera")]
-    pub use crate::renderer::{AutoCamera, TileScaling};
+    pub use crate::renderer::{AutoCamera, AutoCameraSettings, TileScaling};
     pub use crate::{
-        border::{AlignedStringFormatter, Border, Edge},
+        border::{AlignedString, AlignedStringFormatter, Bevel, Border, BoxGlyphs, Edge},
+        color::Gradient,
+        command::DrawCommand,
+        draw_context::DrawContext,
         entity::ClearAfterRender,
         entity::TerminalBundle,
+        entity::TerminalDrawFn,
+        focus::{Focusable, FocusedKeyInput, TerminalFocus},
         formatting::*,
-        terminal::{Terminal, Tile},
-        TerminalPlugin,
+        renderer::MarchingAnts,
+        terminal::{ColorSource, DownscaleMode, Terminal, Tile, TileOrientation, VerticalAlign},
+        tween::{TileTween, TweenEasing},
+        TerminalPlugin, TerminalPluginConfig,
     };
     pub use sark_grids::{grid::Side, GridPoint, Pivot, Size2d};
 }
 
 /// Plugin for terminal rendering and related components and systems.
+///
+/// Add it as is to use the defaults, or configure it through
+/// [`TerminalPluginConfig`].
+///
+/// # Example
+///
+/// ```rust no_run
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((
+///             DefaultPlugins,
+///             TerminalPlugin.with_default_font(TerminalFont::Pastiche8x8),
+///         ))
+///         .run();
+/// }
+/// ```
+#[derive(Debug, Clone, Copy, Default)]
 pub struct TerminalPlugin;
 
+impl TerminalPlugin {
+    /// Configure the plugin, starting from the defaults.
+    pub fn config(self) -> TerminalPluginConfig {
+        TerminalPluginConfig::default()
+    }
+
+    /// Set the font used by terminals with the default [`TerminalFont`]. See
+    /// [`TerminalPluginConfig::default_font`].
+    pub fn with_default_font(self, font: TerminalFont) -> TerminalPluginConfig {
+        self.config().with_default_font(font)
+    }
+
+    /// Set whether the [`ToWorld`] systems are added. See
+    /// [`TerminalPluginConfig::add_to_world`].
+    pub fn with_to_world(self, add_to_world: bool) -> TerminalPluginConfig {
+        self.config().with_to_world(add_to_world)
+    }
+
+    /// Run the [`TerminalDrawFn`] systems in the given set. See
+    /// [`TerminalPluginConfig::draw_set`].
+    pub fn with_draw_set(self, set: impl SystemSet) -> TerminalPluginConfig {
+        self.config().with_draw_set(set)
+    }
+}
+
 impl Plugin for TerminalPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins((renderer::TerminalRendererPlugin, to_world::ToWorldPlugin))
-            .add_systems(Last, entity::clear_after_render.after(TerminalRender));
+        self.config().build(app);
+    }
+}
+
+/// A [`TerminalPlugin`] with non default settings.
+///
+/// The rendering systems always run in the [`Last`] schedule, in the public
+/// sets such as [`TerminalRender`], which can be ordered against your own
+/// systems with `configure_sets`.
+#[derive(Debug, Clone)]
+pub struct TerminalPluginConfig {
+    /// The font used by terminals with the default [`TerminalFont`], which
+    /// includes any [`TerminalBundle`] that wasn't given a font.
+    pub default_font: TerminalFont,
+    /// Whether to add the systems which update [`ToWorld`] components and send
+    /// [`TerminalLinkClicked`] events. If this is false the
+    /// [`TerminalLinkClicked`] event is not registered.
+    pub add_to_world: bool,
+    /// A system set in the [`Update`] schedule to run the [`TerminalDrawFn`]
+    /// systems in, so they can be ordered against your own systems.
+    pub draw_set: Option<InternedSystemSet>,
+}
+
+impl Default for TerminalPluginConfig {
+    fn default() -> Self {
+        Self {
+            default_font: TerminalFont::default(),
+            add_to_world: true,
+            draw_set: None,
+        }
+    }
+}
+
+impl TerminalPluginConfig {
+    /// Set the font used by terminals with the default [`TerminalFont`].
+    pub fn with_default_font(mut self, font: TerminalFont) -> Self {
+        self.default_font = font;
+        self
+    }
+
+    /// Set whether the [`ToWorld`] systems are added.
+    pub fn with_to_world(mut self, add_to_world: bool) -> Self {
+        self.add_to_world = add_to_world;
+        self
+    }
+
+    /// Run the [`TerminalDrawFn`] systems in the given set.
+    pub fn with_draw_set(mut self, set: impl SystemSet) -> Self {
+        self.draw_set = Some(set.intern());
+        self
+    }
+}
+
+impl Plugin for TerminalPluginConfig {
+    fn build(&self, app: &mut App) {
+        // Must be inserted before the renderer plugin builds the default material
+        app.insert_resource(renderer::DefaultTerminalFont(self.default_font.clone()));
+        app.add_plugins((
+            renderer::TerminalRendererPlugin,
+            focus::FocusPlugin,
+            tween::TweenPlugin,
+        ))
+        .add_systems(Last, entity::clear_after_render.after(TerminalRender));
+        match self.draw_set {
+            Some(set) => app.add_systems(Update, entity::run_draw_fns.in_set(set)),
+            None => app.add_systems(Update, entity::run_draw_fns),
+        };
+        if self.add_to_world {
+            app.add_plugins(to_world::ToWorldPlugin);
+        }
     }
 }
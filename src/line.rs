@@ -0,0 +1,85 @@
+//! Utilities for walking lines between grid positions.
+
+use bevy::math::IVec2;
+
+/// An iterator over the grid positions along a line, from `from` to `to`
+/// inclusive, using Bresenham's line algorithm.
+pub(crate) struct BresenhamLine {
+    pos: IVec2,
+    end: IVec2,
+    delta: IVec2,
+    step: IVec2,
+    err: i32,
+    done: bool,
+}
+
+impl BresenhamLine {
+    pub(crate) fn new(from: IVec2, to: IVec2) -> Self {
+        let delta = IVec2::new((to.x - from.x).abs(), -(to.y - from.y).abs());
+        let step = IVec2::new(
+            if from.x < to.x { 1 } else { -1 },
+            if from.y < to.y { 1 } else { -1 },
+        );
+        Self {
+            pos: from,
+            end: to,
+            delta,
+            step,
+            err: delta.x + delta.y,
+            done: false,
+        }
+    }
+}
+
+impl Iterator for BresenhamLine {
+    type Item = IVec2;
+
+    fn next(&mut self) -> Option<IVec2> {
+        if self.done {
+            return None;
+        }
+        let current = self.pos;
+        if current == self.end {
+            self.done = true;
+            return Some(current);
+        }
+
+        let e2 = self.err * 2;
+        if e2 >= self.delta.y {
+            self.err += self.delta.y;
+            self.pos.x += self.step.x;
+        }
+        if e2 <= self.delta.x {
+            self.err += self.delta.x;
+            self.pos.y += self.step.y;
+        }
+        Some(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::IVec2;
+
+    use super::BresenhamLine;
+
+    #[test]
+    fn horizontal() {
+        let points: Vec<_> = BresenhamLine::new(IVec2::new(0, 0), IVec2::new(3, 0)).collect();
+        let expected: Vec<_> = (0..=3).map(|x| IVec2::new(x, 0)).collect();
+        assert_eq!(expected, points);
+    }
+
+    #[test]
+    fn diagonal() {
+        let points: Vec<_> = BresenhamLine::new(IVec2::new(2, 2), IVec2::new(0, 0)).collect();
+        let expected = vec![IVec2::new(2, 2), IVec2::new(1, 1), IVec2::new(0, 0)];
+        assert_eq!(expected, points);
+    }
+
+    #[test]
+    fn single_point() {
+        let points: Vec<_> = BresenhamLine::new(IVec2::ONE, IVec2::ONE).collect();
+        assert_eq!(vec![IVec2::ONE], points);
+    }
+}
@@ -3,7 +3,8 @@
 use bevy::{
     prelude::{
         Added, Assets, BuildChildren, Changed, Children, Commands, Component, Entity, Handle,
-        IVec2, IntoSystemConfigs, Last, Plugin, PostUpdate, Query, Res, Vec2,
+        IVec2, IntoSystemConfigs, Last, Plugin, PostUpdate, Query, Ref, RemovedComponents, Res,
+        Time, Vec2,
     },
     utils::HashMap,
 };
@@ -21,6 +22,88 @@ use super::{
 pub struct BorderTile {
     pub pos: IVec2,
     pub tile: Tile,
+    /// The glyph for the tile when it's not being animated.
+    pub glyph: char,
+    /// Whether the tile is part of the border line and can be animated.
+    pub animated: bool,
+}
+
+/// A component which animates the border of a terminal with "marching ants" -
+/// dashes that move around the border over time.
+///
+/// The dashes use the border's own glyphs, gaps between dashes are drawn with
+/// the `gap_glyph`. Strings drawn on the border are not animated.
+///
+/// # Example
+///
+/// ```rust no_run
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::*;
+///
+/// fn setup(mut commands: Commands) {
+///     commands.spawn((
+///         TerminalBundle::from(Terminal::new([10, 5]).with_border(Border::single_line())),
+///         MarchingAnts::default(),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone)]
+pub struct MarchingAnts {
+    /// The length of each dash, in tiles.
+    pub dash: usize,
+    /// The length of the gap between dashes, in tiles.
+    pub gap: usize,
+    /// The glyph drawn in gaps between dashes.
+    pub gap_glyph: char,
+    /// How many tiles the dashes move per second.
+    pub speed: f32,
+    step: Option<usize>,
+}
+
+impl Default for MarchingAnts {
+    fn default() -> Self {
+        Self {
+            dash: 2,
+            gap: 2,
+            gap_glyph: ' ',
+            speed: 8.0,
+            step: None,
+        }
+    }
+}
+
+impl MarchingAnts {
+    pub fn new(dash: usize, gap: usize, speed: f32) -> Self {
+        Self {
+            dash,
+            gap,
+            speed,
+            ..Default::default()
+        }
+    }
+
+    /// Whether the tile at the given index along the border is part of a dash
+    /// for the given animation step.
+    fn is_dash(&self, index: usize, step: usize) -> bool {
+        let period = (self.dash + self.gap).max(1);
+        (index + period - step % period) % period < self.dash
+    }
+}
+
+/// The index of a border tile, moving counter-clockwise around the border
+/// from the bottom left corner.
+fn perimeter_index(xy: IVec2, size: IVec2) -> usize {
+    let [w, h] = (size - 1).max(IVec2::ZERO).to_array();
+    let i = if xy.y == 0 {
+        xy.x
+    } else if xy.x == w {
+        w + xy.y
+    } else if xy.y == h {
+        w + h + (w - xy.x)
+    } else {
+        w * 2 + h + (h - xy.y)
+    };
+    i as usize
 }
 
 #[derive(Component)]
@@ -60,9 +143,19 @@ impl BorderMesh {
         let tile = BorderTile {
             pos: xy.as_ivec2(),
             tile,
+            glyph: tile.glyph,
+            animated: true,
         };
         self.tiles.insert(xy.as_ivec2(), tile);
     }
+
+    /// Insert a tile which will never be animated.
+    pub fn put_static_tile(&mut self, xy: impl GridPoint, tile: Tile) {
+        self.put_tile(xy, tile);
+        if let Some(t) = self.tiles.get_mut(&xy.as_ivec2()) {
+            t.animated = false;
+        }
+    }
 }
 
 fn init(mut q: Query<(Entity, &TerminalLayout), Added<TerminalLayout>>, mut commands: Commands) {
@@ -122,42 +215,59 @@ fn update(
                 }
 
                 let border = layout.border().unwrap();
+                for (xy, fmt) in border.edge_string_tiles(mesh.size) {
+                    let mut tile = mesh.clear_tile;
+                    fmt.apply(&mut tile);
+                    mesh.put_static_tile(xy, tile);
+                }
+            }
+        }
+    }
+}
 
-                for (edge, aligned_string) in border.edge_strings.iter() {
-                    match edge {
-                        Edge::Top => {
-                            let align = aligned_string.align;
-                            let string = &aligned_string.string;
-                            let w = mesh.size.x - 2;
-                            let len = string.chars().count();
-                            let x = (align * w as f32).round() as i32;
-                            let x = x - (len as f32 * align).round() as i32;
-
-                            for (i, ch) in string.chars().enumerate() {
-                                let i = i as i32 + 1;
-                                let x = x + i;
-                                let mut tile = mesh.clear_tile;
-                                tile.glyph = ch;
-                                if let Some(col) = aligned_string.fg_col {
-                                    tile.fg_color = col;
-                                }
-                                if let Some(col) = aligned_string.bg_col {
-                                    tile.bg_color = col;
-                                }
-                                mesh.put_tile([x, h], tile);
-                            }
-
-                            //let mut tile =
-                            //mesh.put_tile(xy, tile)
-                        }
-                        Edge::Left => todo!(),
-                        Edge::Right => todo!(),
-                        Edge::Bottom => todo!(),
-                        Edge::TopLeft => todo!(),
-                        Edge::TopRight => todo!(),
-                        Edge::BottomLeft => todo!(),
-                        Edge::BottomRight => todo!(),
-                    }
+fn animate(
+    time: Res<Time>,
+    mut q_term: Query<(Ref<TerminalLayout>, &mut MarchingAnts, &Children)>,
+    mut q_border: Query<&mut BorderMesh>,
+) {
+    for (layout, mut ants, children) in &mut q_term {
+        let step = (time.elapsed_seconds() * ants.speed).max(0.0) as usize;
+        // The border tiles are rebuilt when the layout changes
+        if ants.step == Some(step) && !layout.is_changed() {
+            continue;
+        }
+        ants.step = Some(step);
+
+        for child in children {
+            if let Ok(mut mesh) = q_border.get_mut(*child) {
+                let size = mesh.size;
+                for t in mesh.tiles.values_mut().filter(|t| t.animated) {
+                    let i = perimeter_index(t.pos, size);
+                    t.tile.glyph = if ants.is_dash(i, step) {
+                        t.glyph
+                    } else {
+                        ants.gap_glyph
+                    };
+                }
+            }
+        }
+    }
+}
+
+/// Restore border glyphs when [`MarchingAnts`] is removed from a terminal.
+fn stop_animation(
+    mut removed: RemovedComponents<MarchingAnts>,
+    q_term: Query<&Children>,
+    mut q_border: Query<&mut BorderMesh>,
+) {
+    for entity in removed.read() {
+        let Ok(children) = q_term.get(entity) else {
+            continue;
+        };
+        for child in children {
+            if let Ok(mut mesh) = q_border.get_mut(*child) {
+                for t in mesh.tiles.values_mut() {
+                    t.tile.glyph = t.glyph;
                 }
             }
         }
@@ -202,7 +312,11 @@ fn update_tile_data(
 
 fn get_tile(edge: Edge, layout: &TerminalLayout) -> Tile {
     let mut tile = layout.clear_tile();
-    tile.glyph = layout.border().unwrap().edge_glyph(edge);
+    let border = layout.border().unwrap();
+    tile.glyph = border.edge_glyph(edge);
+    if let Some(color) = border.edge_color(edge) {
+        tile.fg_color = color;
+    }
     tile
 }
 
@@ -213,9 +327,47 @@ impl Plugin for BorderMeshPlugin {
         app.add_systems(PostUpdate, init.in_set(TerminalInit))
             .add_systems(
                 Last,
-                (update, update_tile_data)
+                (update, stop_animation, animate, update_tile_data)
+                    .chain()
                     .after(TerminalUpdateTiles)
                     .before(TerminalRender),
             );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::math::IVec2;
+
+    use super::{perimeter_index, MarchingAnts};
+
+    #[test]
+    fn perimeter_indices() {
+        let size = IVec2::new(4, 3);
+        let path = [
+            [0, 0],
+            [1, 0],
+            [2, 0],
+            [3, 0],
+            [3, 1],
+            [3, 2],
+            [2, 2],
+            [1, 2],
+            [0, 2],
+            [0, 1],
+        ];
+        for (i, p) in path.iter().enumerate() {
+            assert_eq!(i, perimeter_index(IVec2::from(*p), size));
+        }
+    }
+
+    #[test]
+    fn ants_march() {
+        let ants = MarchingAnts::new(2, 1, 1.0);
+        let dashes: Vec<_> = (0..6).map(|i| ants.is_dash(i, 0)).collect();
+        assert_eq!(vec![true, true, false, true, true, false], dashes);
+
+        let dashes: Vec<_> = (0..6).map(|i| ants.is_dash(i, 1)).collect();
+        assert_eq!(vec![false, true, true, false, true, true], dashes);
+    }
+}
@@ -21,8 +21,14 @@ use bevy::prelude::Last;
 use bevy::prelude::Plugin;
 use bevy::prelude::Query;
 use bevy::prelude::Res;
+use bevy::prelude::ResMut;
+use bevy::prelude::Resource;
 use bevy::prelude::Transform;
+use bevy::prelude::UVec2;
 use bevy::prelude::With;
+use bevy::window::PrimaryWindow;
+use bevy::window::Window;
+use bevy::window::WindowResized;
 
 use bevy::prelude::App;
 pub use bevy_tiled_camera::TiledCamera;
@@ -55,6 +61,58 @@ use bevy_tiled_camera::TiledCameraPlugin;
 #[derive(Component)]
 pub struct AutoCamera;
 
+/// Settings for the camera controlled by [`AutoCamera`].
+///
+/// # Example
+///
+/// ```rust no_run
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::*;
+///
+/// fn main() {
+///     App::new()
+///         .add_plugins((DefaultPlugins, TerminalPlugin))
+///         .insert_resource(AutoCameraSettings::integer_scaling())
+///         .run();
+/// }
+/// ```
+#[derive(Resource, Debug, Default, Clone)]
+pub struct AutoCameraSettings {
+    /// If true the camera will only scale terminals by whole numbers (1x, 2x,
+    /// 3x...), using the largest scale that fits the window. This avoids
+    /// shimmering on pixel fonts. The area around the terminals will show
+    /// more of the world to fill the window.
+    ///
+    /// If the window is too small for the terminals at 1x the camera will fall
+    /// back to fitting them to the window.
+    pub integer_scaling: bool,
+    scale: Option<u32>,
+}
+
+impl AutoCameraSettings {
+    /// Settings with `integer_scaling` enabled.
+    pub fn integer_scaling() -> Self {
+        Self {
+            integer_scaling: true,
+            ..Default::default()
+        }
+    }
+
+    /// The integer scale chosen by the camera, if `integer_scaling` is enabled
+    /// and the window is large enough to fit the terminals.
+    pub fn scale(&self) -> Option<u32> {
+        self.scale
+    }
+}
+
+/// The largest whole number scale where `content` fits inside `window`, or
+/// `None` if it doesn't fit at 1x.
+fn integer_scale(window: UVec2, content: UVec2) -> Option<u32> {
+    let content = content.max(UVec2::ONE);
+    let scale = (window / content).min_element();
+    (scale >= 1).then_some(scale)
+}
+
 fn init_camera(
     mut commands: Commands,
     q_term: Query<Entity, (With<Terminal>, With<AutoCamera>)>,
@@ -82,8 +140,10 @@ fn init_camera(
 fn update(
     q_terminals: Query<(&TerminalLayout, &Handle<TerminalMaterial>), With<AutoCamera>>,
     mut q_cam: Query<(&mut TiledCamera, &mut Transform), With<TerminalCamera>>,
+    q_window: Query<&Window, With<PrimaryWindow>>,
     images: Res<Assets<Image>>,
     materials: Res<Assets<TerminalMaterial>>,
+    mut settings: ResMut<AutoCameraSettings>,
 ) {
     if let Ok((mut cam, mut transform)) = q_cam.get_single_mut() {
         //println!("UPDATING CAMERA");
@@ -110,6 +170,18 @@ fn update(
 
             //println!("Updating camera bounds. Final Rect {}", rect);
             cam.tile_count = rect.size().as_uvec2();
+            settings.scale = None;
+            if settings.integer_scaling {
+                if let Ok(window) = q_window.get_single() {
+                    let window = UVec2::new(window.physical_width(), window.physical_height());
+                    let ppu = cam.pixels_per_tile.max(UVec2::ONE);
+                    settings.scale = integer_scale(window, cam.tile_count * ppu);
+                    // Show enough tiles to fill the window at the chosen scale
+                    if let Some(scale) = settings.scale {
+                        cam.tile_count = (window / (ppu * scale)).max(cam.tile_count);
+                    }
+                }
+            }
             let z = transform.translation.z;
             transform.translation = rect.center.as_vec2().extend(z);
         }
@@ -120,8 +192,14 @@ fn update_cam_conditions(
     q_cam_added: Query<Entity, (With<TiledCamera>, Added<TerminalCamera>)>,
     q_layout_changed: Query<&TerminalLayout, Changed<TerminalLayout>>,
     ev_asset: EventReader<AssetEvent<Image>>,
+    ev_resize: EventReader<WindowResized>,
+    settings: Res<AutoCameraSettings>,
 ) -> bool {
-    !q_cam_added.is_empty() || !q_layout_changed.is_empty() || !ev_asset.is_empty()
+    !q_cam_added.is_empty()
+        || !q_layout_changed.is_empty()
+        || !ev_asset.is_empty()
+        || (settings.integer_scaling && !ev_resize.is_empty())
+        || settings.is_changed()
 }
 
 /// Will track changes to a terminal and update the viewport so the
@@ -133,7 +211,8 @@ pub(crate) struct TerminalCameraPlugin;
 
 impl Plugin for TerminalCameraPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(TiledCameraPlugin);
+        app.add_plugins(TiledCameraPlugin)
+            .init_resource::<AutoCameraSettings>();
         app.add_systems(First, init_camera).add_systems(
             Last,
             update
@@ -142,3 +221,19 @@ impl Plugin for TerminalCameraPlugin {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::UVec2;
+
+    use super::integer_scale;
+
+    #[test]
+    fn integer_scale_fits() {
+        let content = UVec2::new(320, 200);
+        assert_eq!(Some(2), integer_scale(content * 2, content));
+        assert_eq!(Some(2), integer_scale(UVec2::new(1000, 500), content));
+        assert_eq!(Some(1), integer_scale(content, content));
+        assert_eq!(None, integer_scale(UVec2::new(319, 800), content));
+    }
+}
@@ -302,6 +302,7 @@ pub fn glyph_to_index(c: char) -> u8 {
         'ⁿ' => 252,
         '²' => 253,
         '■' => 254,
+        '\u{A0}' => 255,
 
         _ => 0,
     }
@@ -1,7 +1,7 @@
 //! Terminal components
 
 use bevy::{
-    prelude::{Bundle, Component, Deref, Handle, UVec2},
+    prelude::{Bundle, Component, Deref, Handle, UVec2, Vec2},
     sprite::MaterialMesh2dBundle,
 };
 
@@ -16,15 +16,26 @@ use super::{
 pub struct TerminalSize(pub UVec2);
 
 /// Terminal component specifying how terminal mesh tiles will be scaled.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum TileScaling {
     /// Each tile will take up 1 unit of world space vertically. This is the default setting.
+    ///
+    /// The width of each tile is set automatically from the aspect ratio of
+    /// the font's tiles, so tall or wide fonts aren't stretched into squares.
+    /// See [`TerminalLayout::units_per_tile`](crate::TerminalLayout::units_per_tile)
+    /// to change the size of tiles in world units.
     World,
     /// Scale terminal tiles based on the size of their texture.
     ///
     /// With this setting, 1 pixel == 1 world unit. This matches the expected
     /// defaults for bevy's orthographic camera.
     Pixels,
+    /// Each tile will be exactly this size in world units, regardless of the
+    /// size or aspect ratio of the font's tiles.
+    ///
+    /// Useful for fixed non-square tiles, for example `Vec2::new(8.0, 16.0)`
+    /// for text mode style tiles.
+    Custom(Vec2),
 }
 
 /// Bundle for a rendering a terminal.
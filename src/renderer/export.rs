@@ -0,0 +1,236 @@
+//! Rendering a terminal to an image on the cpu, without the render pipeline.
+
+use bevy::{
+    color::LinearRgba,
+    math::{IVec2, UVec2, Vec2},
+    prelude::{Color, Image},
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+    },
+};
+
+use crate::Terminal;
+
+use super::{material::TerminalMaterial, uv_mapping::UvMapping};
+
+fn to_rgba8(color: Color) -> [u8; 4] {
+    let c = color.to_srgba();
+    [c.red, c.green, c.blue, c.alpha].map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+impl Terminal {
+    /// Render the terminal's tiles to an image, using the same font texture
+    /// and uv mapping the renderer would.
+    ///
+    /// Font pixels matching the default [`TerminalMaterial::bg_clip_color`]
+    /// are replaced by the tile's background color, see
+    /// [`Terminal::to_image_with_clip_color`] to use a different clip color.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy::prelude::Image;
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// fn thumbnail(term: &Terminal, font: &Image) -> Image {
+    ///     term.to_image(font, &UvMapping::default())
+    /// }
+    /// ```
+    pub fn to_image(&self, font: &Image, mapping: &UvMapping) -> Image {
+        self.to_image_with_clip_color(font, mapping, TerminalMaterial::default().bg_clip_color)
+    }
+
+    /// Render the terminal's tiles to an image, replacing font pixels that
+    /// match `clip_color` with the tile's background color.
+    ///
+    /// Glyph pixels are the font color multiplied by the tile's foreground
+    /// color, as in the terminal shader. Glyphs must all be the same size on
+    /// the font texture and tile orientations are applied. The border, overlays,
+    /// glyph offsets, tints and pulses are not drawn. The top row of the image
+    /// is the top row of the terminal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the font isn't an 8 bit rgba image, or if a glyph in the
+    /// terminal is missing from the uv mapping.
+    pub fn to_image_with_clip_color(
+        &self,
+        font: &Image,
+        mapping: &UvMapping,
+        clip_color: Color,
+    ) -> Image {
+        let srgb = match font.texture_descriptor.format {
+            TextureFormat::Rgba8UnormSrgb => true,
+            TextureFormat::Rgba8Unorm => false,
+            format => panic!("Font image must be 8 bit rgba, found {:?}", format),
+        };
+        let font_size = UVec2::new(
+            font.texture_descriptor.size.width,
+            font.texture_descriptor.size.height,
+        );
+        let texel = |uv: Vec2| -> [u8; 4] {
+            let p = (uv * font_size.as_vec2())
+                .floor()
+                .as_ivec2()
+                .clamp(IVec2::ZERO, font_size.as_ivec2() - 1);
+            let i = (p.y as usize * font_size.x as usize + p.x as usize) * 4;
+            [
+                font.data[i],
+                font.data[i + 1],
+                font.data[i + 2],
+                font.data[i + 3],
+            ]
+        };
+        let to_linear = |[r, g, b, _]: [u8; 4]| -> LinearRgba {
+            if srgb {
+                Color::srgb_u8(r, g, b).to_linear()
+            } else {
+                LinearRgba::rgb(r as f32 / 255.0, g as f32 / 255.0, b as f32 / 255.0)
+            }
+        };
+        let clip = to_rgba8(clip_color);
+
+        let [width, height] = self.size().to_array();
+        let tile_uvs = mapping.uvs_from_glyph(self.get_tile([0, 0]).glyph);
+        let tile_size = ((Vec2::from(tile_uvs[3]) - Vec2::from(tile_uvs[0])).abs()
+            * font_size.as_vec2())
+        .round()
+        .as_uvec2()
+        .max(UVec2::ONE);
+        let image_width = width * tile_size.x;
+        let image_height = height * tile_size.y;
+
+        let mut data = vec![0; (image_width * image_height * 4) as usize];
+        for (row, y) in (0..height as i32).rev().enumerate() {
+            for x in 0..width as i32 {
+                let tile = self.get_tile([x, y]);
+                let uvs = *mapping.uvs_from_glyph(tile.glyph);
+                let [tl, bl, tr, _] = self.orientation([x, y]).apply_to_uvs(uvs).map(Vec2::from);
+                let fg = tile.fg_color.to_linear();
+                let bg = to_rgba8(tile.bg_color);
+                for py in 0..tile_size.y {
+                    for px in 0..tile_size.x {
+                        // Sample at the pixel center
+                        let s = (px as f32 + 0.5) / tile_size.x as f32;
+                        let t = (py as f32 + 0.5) / tile_size.y as f32;
+                        let tex = texel(tl + (tr - tl) * s + (bl - tl) * t);
+                        // Same test as the shader, anything at or below the
+                        // clip color is background
+                        let color = if tex.iter().zip(clip).take(3).all(|(t, c)| *t <= c) {
+                            bg
+                        } else {
+                            let tex = to_linear(tex);
+                            to_rgba8(
+                                LinearRgba::new(
+                                    tex.red * fg.red,
+                                    tex.green * fg.green,
+                                    tex.blue * fg.blue,
+                                    fg.alpha,
+                                )
+                                .into(),
+                            )
+                        };
+                        let ox = x as u32 * tile_size.x + px;
+                        let oy = row as u32 * tile_size.y + py;
+                        let i = ((oy * image_width + ox) * 4) as usize;
+                        data[i..i + 4].copy_from_slice(&color);
+                    }
+                }
+            }
+        }
+
+        Image::new(
+            Extent3d {
+                width: image_width,
+                height: image_height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        prelude::{Color, Image},
+        render::{
+            render_asset::RenderAssetUsages,
+            render_resource::{Extent3d, TextureDimension, TextureFormat},
+        },
+    };
+
+    use crate::{renderer::uv_mapping::UvMapping, Terminal, TileFormatter, TileOrientation};
+
+    const WHITE: [u8; 4] = [255, 255, 255, 255];
+    const BLACK: [u8; 4] = [0, 0, 0, 255];
+
+    /// A 4x2 font with two 2x2 glyphs. 'a' has a single white pixel in its top
+    /// left corner and ' ' is empty.
+    fn font() -> (Image, UvMapping) {
+        let pixels = [WHITE, BLACK, BLACK, BLACK, BLACK, BLACK, BLACK, BLACK];
+        let image = Image::new(
+            Extent3d {
+                width: 4,
+                height: 2,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            pixels.concat(),
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        let mapping = UvMapping::from_grid([2, 1], ['a', ' '].into_iter());
+        (image, mapping)
+    }
+
+    fn pixels(image: &Image) -> Vec<[u8; 4]> {
+        image
+            .data
+            .chunks_exact(4)
+            .map(|p| [p[0], p[1], p[2], p[3]])
+            .collect()
+    }
+
+    #[test]
+    fn to_image() {
+        let (font, mapping) = font();
+        let mut term = Terminal::new([2, 2]);
+        term.put_char([0, 1], 'a'.fg(Color::srgb(1.0, 0.0, 0.0)).bg(Color::BLACK));
+        term.put_char([1, 1], ' '.bg(Color::srgb(0.0, 1.0, 0.0)));
+        term.put_char([0, 0], 'a'.fg(Color::WHITE).bg(Color::srgb(0.0, 0.0, 1.0)));
+        term.set_orientation([0, 0], TileOrientation::FlipX);
+        term.put_char([1, 0], ' '.bg(Color::BLACK));
+
+        let image = term.to_image(&font, &mapping);
+
+        assert_eq!(4, image.texture_descriptor.size.width);
+        assert_eq!(4, image.texture_descriptor.size.height);
+        let red = [255, 0, 0, 255];
+        let green = [0, 255, 0, 255];
+        let blue = [0, 0, 255, 255];
+        #[rustfmt::skip]
+        let expected = vec![
+            red,   BLACK, green, green,
+            BLACK, BLACK, green, green,
+            blue,  WHITE, BLACK, BLACK,
+            blue,  blue,  BLACK, BLACK,
+        ];
+        assert_eq!(expected, pixels(&image));
+    }
+
+    #[test]
+    fn clip_color() {
+        let (font, mapping) = font();
+        let mut term = Terminal::new([1, 1]);
+        term.put_char([0, 0], 'a'.fg(Color::WHITE).bg(Color::srgb(0.0, 0.0, 1.0)));
+
+        // Clipping white shows the background everywhere
+        let image = term.to_image_with_clip_color(&font, &mapping, Color::WHITE);
+        assert_eq!(vec![[0, 0, 255, 255]; 4], pixels(&image));
+    }
+}
@@ -114,20 +114,36 @@ impl BuiltInFontHandles {
             .get(font)
             .unwrap_or_else(|| panic!("Error retrieving built in font: {:#?} not found", font))
     }
+
+    /// Retrieve the image handle for any font, built in or custom.
+    pub(crate) fn handle<'a>(&'a self, font: &'a TerminalFont) -> &'a Handle<Image> {
+        match font {
+            TerminalFont::Custom(handle) => handle,
+            _ => self.get(font),
+        }
+    }
 }
 
+/// The font used in place of [`TerminalFont::default`], set from
+/// [`TerminalPluginConfig::default_font`](crate::TerminalPluginConfig::default_font).
+#[derive(Resource, Debug, Clone, Default)]
+pub(crate) struct DefaultTerminalFont(pub TerminalFont);
+
 fn terminal_renderer_change_font(
     built_in_fonts: Res<BuiltInFontHandles>,
+    default_font: Res<DefaultTerminalFont>,
     mut q_change: Query<(Entity, &mut Handle<TerminalMaterial>, &TerminalFont)>,
     mut materials: ResMut<Assets<TerminalMaterial>>,
     mut commands: Commands,
     images: ResMut<Assets<Image>>,
 ) {
     for (e, mut mat, font) in q_change.iter_mut() {
-        let handle = match font {
-            TerminalFont::Custom(handle) => handle,
-            _ => built_in_fonts.get(font),
+        let font = if *font == TerminalFont::default() {
+            &default_font.0
+        } else {
+            font
         };
+        let handle = built_in_fonts.handle(font);
 
         // The requested font might still be loading, this is why we remove
         // the TerminalFont component rather than using change detection
@@ -179,7 +195,8 @@ impl Plugin for TerminalFontPlugin {
         let font = include_font!(TerminalFont::TaritusCurses8x12, "taritus_curses_8x12.png");
         add_font_resource(font, &mut images, font_map);
 
-        app.insert_resource(fonts);
+        app.insert_resource(fonts)
+            .init_resource::<DefaultTerminalFont>();
 
         app.add_systems(
             Update,
@@ -189,3 +206,45 @@ impl Plugin for TerminalFontPlugin {
         );
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+    use bevy::utils::HashMap;
+
+    use super::*;
+
+    #[test]
+    fn configured_default_font() {
+        let mut app = App::new();
+        app.init_resource::<Assets<Image>>()
+            .init_resource::<Assets<TerminalMaterial>>()
+            .insert_resource(DefaultTerminalFont(TerminalFont::Pastiche8x8))
+            .add_systems(Update, terminal_renderer_change_font);
+
+        let mut images = app.world_mut().resource_mut::<Assets<Image>>();
+        let mut map = HashMap::default();
+        for font in [TerminalFont::Pastiche8x8, TerminalFont::default()] {
+            map.insert(font, images.add(Image::default()));
+        }
+        let pastiche = map[&TerminalFont::Pastiche8x8].clone();
+        let px437 = map[&TerminalFont::Px4378x8].clone();
+        app.insert_resource(BuiltInFontHandles { map });
+
+        let term = app
+            .world_mut()
+            .spawn((
+                Handle::<TerminalMaterial>::default(),
+                TerminalFont::default(),
+            ))
+            .id();
+        app.update();
+
+        let handle = app.world().get::<Handle<TerminalMaterial>>(term).unwrap();
+        let materials = app.world().resource::<Assets<TerminalMaterial>>();
+        let texture = materials.get(handle).unwrap().texture.clone();
+        assert_eq!(Some(pastiche), texture);
+        assert_ne!(Some(px437), texture);
+        assert!(app.world().get::<TerminalFont>(term).is_none());
+    }
+}
@@ -1,6 +1,6 @@
 use bevy::{
     math::uvec2,
-    prelude::{Component, IVec2, UVec2, Vec2},
+    prelude::{Color, Component, IVec2, UVec2, Vec2},
 };
 use sark_grids::{geometry::GridRect, GridPoint, Pivot};
 
@@ -15,6 +15,46 @@ use super::TileScaling;
 #[derive(Debug, Component, Clone)]
 pub struct TerminalLayout {
     pub scaling: TileScaling,
+    /// If true, tiles that would render nothing (see [`Tile::is_invisible`])
+    /// will be left out of the terminal mesh.
+    ///
+    /// This can greatly reduce the vertex count of mostly empty overlay
+    /// terminals, at the cost of rebuilding the mesh vertices whenever the
+    /// terminal changes.
+    pub skip_invisible_tiles: bool,
+    /// An offset applied to every glyph within its tile, in tiles. Backgrounds
+    /// are unaffected.
+    ///
+    /// Useful for fonts where glyphs sit too high or low in their cells.
+    /// Adds to any per-tile offset set with [`Terminal::set_glyph_offset`],
+    /// and is clipped to the tile the same way.
+    pub glyph_offset: Vec2,
+    /// If true, the terminal mesh will only be updated for tiles that changed
+    /// since the previous frame, rather than being rebuilt from scratch.
+    ///
+    /// Changed tiles are read from [`Terminal::take_dirty_rect`], which is
+    /// much cheaper than rebuilding every tile for large terminals where only
+    /// a few tiles change each frame. The renderer takes the dirty rect, so
+    /// it can't be read by other systems while this is enabled. Note that bevy
+    /// will still upload the whole mesh to the GPU when it changes.
+    ///
+    /// The mesh is always fully rebuilt if the layout changes, if the
+    /// terminal has overlays, glyph offsets, tints, pulses or orientations,
+    /// or if `skip_invisible_tiles` or `backdrop` is set.
+    pub dirty_tracking: bool,
+    /// If set, a single quad of this color is drawn behind all the terminal's
+    /// tiles, covering the terminal and its border.
+    ///
+    /// This gives panels with lots of transparent tiles a solid background
+    /// without filling the background of every tile.
+    pub backdrop: Option<Color>,
+    /// The size of a tile in world units when using [`TileScaling::World`].
+    /// The width is further adjusted by the aspect ratio of the font.
+    ///
+    /// Useful for fitting a terminal into an existing world scaled scene, for
+    /// example `Vec2::splat(0.5)` makes every tile half a world unit tall.
+    /// Ignored when using [`TileScaling::Pixels`] or [`TileScaling::Custom`].
+    pub units_per_tile: Vec2,
     pub(crate) pivot: Pivot,
     //pub(crate) border_entity: Option<Entity>,
     border: Option<Border>,
@@ -30,6 +70,11 @@ impl Default for TerminalLayout {
         Self {
             tile_size: Vec2::ONE,
             scaling: TileScaling::World,
+            skip_invisible_tiles: false,
+            glyph_offset: Vec2::ZERO,
+            dirty_tracking: false,
+            backdrop: None,
+            units_per_tile: Vec2::ONE,
             pixels_per_tile: uvec2(8, 8),
             pivot: Pivot::Center,
             border: None,
@@ -110,6 +155,19 @@ impl TerminalLayout {
         self.border = border;
     }
 
+    pub(crate) fn set_clear_tile(&mut self, clear_tile: Tile) {
+        self.clear_tile = clear_tile;
+    }
+
+    /// The local bottom left corner and size of the backdrop quad, covering the
+    /// terminal and its border.
+    pub(crate) fn backdrop_rect(&self) -> (Vec2, Vec2) {
+        let border = if self.has_border() { 1.0 } else { 0.0 };
+        let origin = self.origin() - self.tile_size * border;
+        let size = (self.term_size().as_vec2() + border * 2.0) * self.tile_size;
+        (origin, size)
+    }
+
     /// Returns the world space position of the given pivot on the terminal.
     pub fn pivot_pos(&self, pivot: Pivot) -> IVec2 {
         self.bounds().pivot_point(pivot)
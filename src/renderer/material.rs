@@ -7,8 +7,8 @@
 use bevy::{
     math::Vec4,
     prelude::{
-        default, Asset, Assets, Changed, Color, Handle, Image, Mesh, Or, Plugin, Query, Res,
-        Shader, Vec2,
+        default, Asset, AssetEvent, Assets, Changed, Color, Component, EventReader, Handle, Image,
+        Mesh, Or, ParamSet, Plugin, Query, RemovedComponents, Res, ResMut, Shader, Vec2, Without,
     },
     reflect::TypePath,
     render::{
@@ -23,13 +23,17 @@ use bevy::{
     sprite::{Material2d, Material2dKey, Material2dPlugin},
 };
 
-use crate::{TerminalFont, TerminalLayout};
+use crate::{Terminal, TerminalFont, TerminalLayout};
 
 use super::{
     font::TerminalFontPlugin,
-    mesh_data::{ATTRIBUTE_COLOR_BG, ATTRIBUTE_COLOR_FG, ATTRIBUTE_UV},
+    mesh_data::{
+        ATTRIBUTE_COLOR_BG, ATTRIBUTE_COLOR_FG, ATTRIBUTE_GLYPH_OFFSET, ATTRIBUTE_PULSE,
+        ATTRIBUTE_TINT, ATTRIBUTE_UV,
+    },
     //mesh::{ATTRIBUTE_COLOR_BG, ATTRIBUTE_COLOR_FG, ATTRIBUTE_UV},
     BuiltInFontHandles,
+    DefaultTerminalFont,
     TileScaling,
 };
 
@@ -56,11 +60,16 @@ impl Plugin for TerminalMaterialPlugin {
             Shader::from_wgsl(include_str!("terminal.wgsl"), "terminal.wgsl"),
         );
 
+        let default_font = app
+            .world()
+            .get_resource::<DefaultTerminalFont>()
+            .cloned()
+            .unwrap_or_default();
         let fonts = app
             .world_mut()
             .get_resource::<BuiltInFontHandles>()
             .expect("Couldn't get font handles");
-        let font = fonts.get(&TerminalFont::default());
+        let font = fonts.handle(&default_font.0);
         let material = TerminalMaterial::from(font.clone());
 
         app.world_mut()
@@ -71,20 +80,51 @@ impl Plugin for TerminalMaterialPlugin {
 
 #[derive(AsBindGroup, Asset, Debug, Clone, TypePath)]
 #[uniform(0, TerminalMaterialUniform)]
+#[bind_group_data(TerminalMaterialKey)]
 pub struct TerminalMaterial {
     /// This determines the "background color" for the texture,
     /// which will be clipped and replaced with a tile color.
     pub bg_clip_color: Color,
+    /// The color tile foregrounds are tinted towards, see
+    /// [`Terminal::set_tint`](crate::Terminal::set_tint).
+    pub accent_color: Color,
     #[texture(1)]
     #[sampler(2)]
     pub texture: Option<Handle<Image>>,
+    /// An optional fragment shader to use in place of the default terminal
+    /// shader. See [`TerminalShader`].
+    pub fragment_shader: Option<Handle<Shader>>,
+    /// If true the material writes to the depth buffer, so tiles with a
+    /// lower z position drawn afterwards are hidden. This is set
+    /// automatically for terminals with tile depths, see
+    /// [`Terminal::set_depth`](crate::Terminal::set_depth).
+    pub depth_write: bool,
 }
 
 impl Default for TerminalMaterial {
     fn default() -> Self {
         Self {
             bg_clip_color: Color::BLACK,
+            accent_color: Color::srgb(1.0, 0.0, 0.0),
             texture: None,
+            fragment_shader: None,
+            depth_write: false,
+        }
+    }
+}
+
+/// Pipeline specialization key for a [`TerminalMaterial`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TerminalMaterialKey {
+    fragment_shader: Option<Handle<Shader>>,
+    depth_write: bool,
+}
+
+impl From<&TerminalMaterial> for TerminalMaterialKey {
+    fn from(material: &TerminalMaterial) -> Self {
+        Self {
+            fragment_shader: material.fragment_shader.clone(),
+            depth_write: material.depth_write,
         }
     }
 }
@@ -113,16 +153,27 @@ bitflags::bitflags! {
 struct TerminalMaterialUniform {
     pub color: Vec4,
     pub flags: u32,
+    pub accent_color: Vec4,
+}
+
+fn color_to_vec4(color: Color) -> Vec4 {
+    let linear = color.to_linear();
+    Vec4::from_array([linear.red, linear.green, linear.blue, linear.alpha])
 }
 
 impl TerminalMaterialUniform {
     fn from_color(color: Color, flags: u32) -> TerminalMaterialUniform {
-        let linear = color.to_linear();
         TerminalMaterialUniform {
-            color: Vec4::from_array([linear.red, linear.green, linear.blue, linear.alpha]),
+            color: color_to_vec4(color),
             flags,
+            ..Default::default()
         }
     }
+
+    fn with_accent(mut self, accent: Color) -> TerminalMaterialUniform {
+        self.accent_color = color_to_vec4(accent);
+        self
+    }
 }
 
 impl AsBindGroupShaderType<TerminalMaterialUniform> for TerminalMaterial {
@@ -133,6 +184,7 @@ impl AsBindGroupShaderType<TerminalMaterialUniform> for TerminalMaterial {
         }
 
         TerminalMaterialUniform::from_color(self.bg_clip_color, flags.bits())
+            .with_accent(self.accent_color)
     }
 }
 
@@ -148,13 +200,29 @@ impl Material2d for TerminalMaterial {
     fn specialize(
         descriptor: &mut RenderPipelineDescriptor,
         layout: &MeshVertexBufferLayoutRef,
-        _: Material2dKey<Self>,
+        key: Material2dKey<Self>,
     ) -> Result<(), SpecializedMeshPipelineError> {
+        if let (Some(shader), Some(fragment)) = (
+            key.bind_group_data.fragment_shader,
+            descriptor.fragment.as_mut(),
+        ) {
+            fragment.shader = shader;
+        }
+
+        if key.bind_group_data.depth_write {
+            if let Some(depth_stencil) = descriptor.depth_stencil.as_mut() {
+                depth_stencil.depth_write_enabled = true;
+            }
+        }
+
         let vertex_layout = layout.0.get_layout(&[
             Mesh::ATTRIBUTE_POSITION.at_shader_location(0),
             ATTRIBUTE_UV.at_shader_location(1),
             ATTRIBUTE_COLOR_BG.at_shader_location(2),
             ATTRIBUTE_COLOR_FG.at_shader_location(3),
+            ATTRIBUTE_GLYPH_OFFSET.at_shader_location(4),
+            ATTRIBUTE_TINT.at_shader_location(5),
+            ATTRIBUTE_PULSE.at_shader_location(6),
         ])?;
         descriptor.vertex.buffers = vec![vertex_layout];
 
@@ -162,13 +230,173 @@ impl Material2d for TerminalMaterial {
     }
 }
 
+/// A component for rendering a terminal with a custom fragment shader.
+///
+/// The terminal mesh and material bindings are unchanged, so the shader must
+/// declare a `fragment` entry point which matches the default terminal shader:
+///
+/// ```wgsl
+/// struct TerminalMaterial {
+///     clip_color: vec4<f32>,
+///     flags: u32,
+/// };
+///
+/// @group(2) @binding(0) var<uniform> material: TerminalMaterial;
+/// @group(2) @binding(1) var texture: texture_2d<f32>;
+/// @group(2) @binding(2) var texture_sampler: sampler;
+///
+/// struct FragmentInput {
+///     @builtin(front_facing) is_front: bool,
+///     @location(0) world_position: vec4<f32>,
+///     @location(1) uv: vec2<f32>,
+///     @location(2) bg_color: vec4<f32>,
+///     @location(3) fg_color: vec4<f32>,
+///     // Optional, required to support glyph offsets
+///     @location(4) glyph_offset: vec4<f32>,
+///     @location(5) tile_position: vec2<f32>,
+///     // Optional, required to support tints
+///     @location(6) tint: f32,
+///     // Optional, required to support pulses
+///     @location(7) pulse: f32,
+/// };
+///
+/// @fragment
+/// fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
+///     // ...
+/// }
+/// ```
+///
+/// Note this will replace the terminal's material with a new one when added
+/// or changed.
+///
+/// # Example
+///
+/// ```rust no_run
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::*;
+///
+/// fn setup(mut commands: Commands, server: Res<AssetServer>) {
+///     commands.spawn((
+///         TerminalBundle::from(Terminal::new([10,10])),
+///         TerminalShader(server.load("shaders/my_shader.wgsl")),
+///     ));
+/// }
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct TerminalShader(pub Handle<Shader>);
+
+/// Apply any custom [`TerminalShader`] to it's terminal's material.
+///
+/// This also runs when the material handle changes, so the shader is preserved
+/// when the terminal font is changed.
+#[allow(clippy::type_complexity)]
+pub(crate) fn terminal_shader_change(
+    mut materials: ResMut<Assets<TerminalMaterial>>,
+    mut q_term: ParamSet<(
+        Query<
+            (&TerminalShader, &mut Handle<TerminalMaterial>),
+            Or<(Changed<TerminalShader>, Changed<Handle<TerminalMaterial>>)>,
+        >,
+        Query<&mut Handle<TerminalMaterial>, Without<TerminalShader>>,
+    )>,
+    mut removed: RemovedComponents<TerminalShader>,
+) {
+    for (shader, mut handle) in &mut q_term.p0() {
+        set_fragment_shader(&mut materials, &mut handle, Some(shader.0.clone()));
+    }
+    let mut q_removed = q_term.p1();
+    for entity in removed.read() {
+        if let Ok(mut handle) = q_removed.get_mut(entity) {
+            set_fragment_shader(&mut materials, &mut handle, None);
+        }
+    }
+}
+
+/// Switch terminals to a depth writing material when they have tile depths,
+/// and back again when their depths are cleared.
+#[allow(clippy::type_complexity)]
+pub(crate) fn terminal_depth_change(
+    mut materials: ResMut<Assets<TerminalMaterial>>,
+    mut q_term: Query<
+        (&Terminal, &mut Handle<TerminalMaterial>),
+        Or<(Changed<Terminal>, Changed<Handle<TerminalMaterial>>)>,
+    >,
+) {
+    for (term, mut handle) in &mut q_term {
+        let depth_write = !term.depths().is_empty();
+        let Some(material) = materials.get(&*handle) else {
+            continue;
+        };
+        if material.depth_write == depth_write {
+            continue;
+        }
+        let material = TerminalMaterial {
+            depth_write,
+            ..material.clone()
+        };
+        *handle = materials.add(material);
+    }
+}
+
+fn set_fragment_shader(
+    materials: &mut Assets<TerminalMaterial>,
+    handle: &mut Handle<TerminalMaterial>,
+    shader: Option<Handle<Shader>>,
+) {
+    let Some(material) = materials.get(&*handle) else {
+        return;
+    };
+    if material.fragment_shader == shader {
+        return;
+    }
+    let material = TerminalMaterial {
+        fragment_shader: shader,
+        ..material.clone()
+    };
+    *handle = materials.add(material);
+}
+
+/// Flag terminal materials as changed when their font image is modified or
+/// finishes loading, so the layout and mesh are rebuilt for the new image.
+///
+/// This allows fonts loaded from external files to be hot reloaded.
+pub(crate) fn font_image_change(
+    mut events: EventReader<AssetEvent<Image>>,
+    materials: Res<Assets<TerminalMaterial>>,
+    mut q_term: Query<&mut Handle<TerminalMaterial>>,
+) {
+    let changed: Vec<_> = events
+        .read()
+        .filter_map(|ev| match ev {
+            AssetEvent::Modified { id } | AssetEvent::LoadedWithDependencies { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    if changed.is_empty() {
+        return;
+    }
+    for mut handle in &mut q_term {
+        let uses_image = materials
+            .get(&*handle)
+            .and_then(|m| m.texture.as_ref())
+            .is_some_and(|tex| changed.contains(&tex.id()));
+        if uses_image {
+            handle.set_changed();
+        }
+    }
+}
+
 #[allow(clippy::type_complexity)]
 pub(crate) fn material_change(
     materials: Res<Assets<TerminalMaterial>>,
     images: Res<Assets<Image>>,
     mut q_term: Query<
         (&Handle<TerminalMaterial>, &mut TerminalLayout),
-        Or<(Changed<Handle<TerminalMaterial>>, Changed<TerminalFont>)>,
+        Or<(
+            Changed<Handle<TerminalMaterial>>,
+            Changed<TerminalFont>,
+            Changed<TerminalLayout>,
+        )>,
     >,
 ) {
     for (handle, mut layout) in &mut q_term {
@@ -178,17 +406,168 @@ pub(crate) fn material_change(
                     // TODO: Should be derived from image size, can't assume 16x16 tilesheet for
                     // graphical terminals
                     let font_size = image.size().as_vec2() / 16.0;
-                    layout.pixels_per_tile = font_size.as_uvec2();
-                    layout.tile_size = match layout.scaling {
+                    let tile_size = match layout.scaling {
                         TileScaling::World => {
                             let aspect = font_size.x / font_size.y;
-                            Vec2::new(aspect, 1.0)
+                            Vec2::new(aspect, 1.0) * layout.units_per_tile
                         }
                         TileScaling::Pixels => font_size,
+                        TileScaling::Custom(size) => size,
                     };
+                    // Only write when needed, this system also runs when the
+                    // layout changes
+                    if layout.pixels_per_tile != font_size.as_uvec2() {
+                        layout.pixels_per_tile = font_size.as_uvec2();
+                    }
+                    if layout.tile_size != tile_size {
+                        layout.tile_size = tile_size;
+                    }
                     //info!("Updating layout ppt. Now {}", layout.pixels_per_tile);
                 }
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::*;
+
+    use crate::Terminal;
+
+    use crate::{TerminalFont, TerminalLayout};
+
+    use super::{
+        font_image_change, material_change, terminal_depth_change, TerminalMaterial, TileScaling,
+    };
+
+    #[derive(Resource, Default)]
+    struct ChangedTerminals(Vec<Entity>);
+
+    fn track_changes(
+        q: Query<Entity, Changed<Handle<TerminalMaterial>>>,
+        mut changed: ResMut<ChangedTerminals>,
+    ) {
+        changed.0 = q.iter().collect();
+    }
+
+    #[test]
+    fn modified_font_image() {
+        let mut app = App::new();
+        app.add_event::<AssetEvent<Image>>()
+            .init_resource::<Assets<TerminalMaterial>>()
+            .init_resource::<ChangedTerminals>()
+            .add_systems(Update, (font_image_change, track_changes).chain());
+
+        let font_a = Handle::<Image>::weak_from_u128(1);
+        let font_b = Handle::<Image>::weak_from_u128(2);
+        let mut materials = app.world_mut().resource_mut::<Assets<TerminalMaterial>>();
+        let mat_a = materials.add(TerminalMaterial::from(font_a.clone()));
+        let mat_b = materials.add(TerminalMaterial::from(font_b));
+
+        let a = app.world_mut().spawn(mat_a).id();
+        app.world_mut().spawn(mat_b);
+        // Flush the initial "added" changes
+        app.update();
+
+        app.world_mut()
+            .send_event(AssetEvent::Modified { id: font_a.id() });
+        app.update();
+
+        assert_eq!(vec![a], app.world().resource::<ChangedTerminals>().0);
+
+        app.update();
+        assert!(app.world().resource::<ChangedTerminals>().0.is_empty());
+    }
+
+    #[test]
+    fn font_aspect() {
+        use bevy::render::{
+            render_asset::RenderAssetUsages,
+            render_resource::{Extent3d, TextureDimension, TextureFormat},
+        };
+
+        let mut app = App::new();
+        app.init_resource::<Assets<Image>>()
+            .init_resource::<Assets<TerminalMaterial>>()
+            .add_systems(Update, material_change);
+
+        // A 16x16 tile sheet of 8x16 pixel tiles
+        let size = Extent3d {
+            width: 128,
+            height: 256,
+            depth_or_array_layers: 1,
+        };
+        let image = Image::new_fill(
+            size,
+            TextureDimension::D2,
+            &[0, 0, 0, 255],
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        );
+        let image = app.world_mut().resource_mut::<Assets<Image>>().add(image);
+        let material = app
+            .world_mut()
+            .resource_mut::<Assets<TerminalMaterial>>()
+            .add(TerminalMaterial::from(image));
+        let term = app
+            .world_mut()
+            .spawn((material, TerminalLayout::default(), TerminalFont::default()))
+            .id();
+
+        app.update();
+        let layout = app.world().get::<TerminalLayout>(term).unwrap();
+        assert_eq!(UVec2::new(8, 16), layout.pixels_per_tile());
+        assert_eq!(Vec2::new(0.5, 1.0), layout.tile_size);
+
+        // Custom tile sizes ignore the font's aspect ratio
+        let mut layout = app.world_mut().get_mut::<TerminalLayout>(term).unwrap();
+        layout.scaling = TileScaling::Custom(Vec2::new(2.0, 3.0));
+        app.update();
+        let layout = app.world().get::<TerminalLayout>(term).unwrap();
+        assert_eq!(UVec2::new(8, 16), layout.pixels_per_tile());
+        assert_eq!(Vec2::new(2.0, 3.0), layout.tile_size);
+    }
+
+    #[test]
+    fn depth_write_material() {
+        let mut app = App::new();
+        app.init_resource::<Assets<TerminalMaterial>>()
+            .add_systems(Update, terminal_depth_change);
+
+        let handle = app
+            .world_mut()
+            .resource_mut::<Assets<TerminalMaterial>>()
+            .add(TerminalMaterial::default());
+        let term = app
+            .world_mut()
+            .spawn((Terminal::new([3, 3]), handle.clone()))
+            .id();
+        let depth_write = |app: &App| {
+            let handle = app.world().get::<Handle<TerminalMaterial>>(term).unwrap();
+            let materials = app.world().resource::<Assets<TerminalMaterial>>();
+            materials.get(handle).unwrap().depth_write
+        };
+
+        app.update();
+        assert!(!depth_write(&app));
+        assert_eq!(
+            &handle,
+            app.world().get::<Handle<TerminalMaterial>>(term).unwrap()
+        );
+
+        app.world_mut()
+            .get_mut::<Terminal>(term)
+            .unwrap()
+            .set_depth([1, 1], 1.0);
+        app.update();
+        assert!(depth_write(&app));
+
+        app.world_mut()
+            .get_mut::<Terminal>(term)
+            .unwrap()
+            .clear_depths();
+        app.update();
+        assert!(!depth_write(&app));
+    }
+}
@@ -5,27 +5,37 @@ use bevy::{
     sprite::Mesh2dHandle,
 };
 
-use super::mesh_data::{TileData, VertData, ATTRIBUTE_COLOR_BG, ATTRIBUTE_COLOR_FG, ATTRIBUTE_UV};
+use super::mesh_data::{
+    TileData, VertData, ATTRIBUTE_COLOR_BG, ATTRIBUTE_COLOR_FG, ATTRIBUTE_GLYPH_OFFSET,
+    ATTRIBUTE_PULSE, ATTRIBUTE_TINT, ATTRIBUTE_UV,
+};
 
 pub(crate) fn init_mesh(
     mut q_mesh: Query<&mut Mesh2dHandle, Added<VertData>>,
     mut meshes: ResMut<Assets<Mesh>>,
 ) {
     for mut handle in &mut q_mesh {
-        // Initialize terminal mesh
-        let mut mesh = Mesh::new(
-            PrimitiveTopology::TriangleList,
-            RenderAssetUsages::default(),
-        );
-        mesh.insert_indices(Indices::U32(Vec::new()));
-        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<[f32; 3]>::new());
-        mesh.insert_attribute(ATTRIBUTE_UV, Vec::<[f32; 2]>::new());
-        mesh.insert_attribute(ATTRIBUTE_COLOR_FG, Vec::<[f32; 4]>::new());
-        mesh.insert_attribute(ATTRIBUTE_COLOR_BG, Vec::<[f32; 4]>::new());
-        *handle = Mesh2dHandle(meshes.add(mesh));
+        *handle = Mesh2dHandle(meshes.add(new_terminal_mesh()));
     }
 }
 
+/// An empty mesh with all the attributes used by the terminal renderer.
+pub(crate) fn new_terminal_mesh() -> Mesh {
+    let mut mesh = Mesh::new(
+        PrimitiveTopology::TriangleList,
+        RenderAssetUsages::default(),
+    );
+    mesh.insert_indices(Indices::U32(Vec::new()));
+    mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, Vec::<[f32; 3]>::new());
+    mesh.insert_attribute(ATTRIBUTE_UV, Vec::<[f32; 2]>::new());
+    mesh.insert_attribute(ATTRIBUTE_COLOR_FG, Vec::<[f32; 4]>::new());
+    mesh.insert_attribute(ATTRIBUTE_COLOR_BG, Vec::<[f32; 4]>::new());
+    mesh.insert_attribute(ATTRIBUTE_GLYPH_OFFSET, Vec::<[f32; 4]>::new());
+    mesh.insert_attribute(ATTRIBUTE_TINT, Vec::<f32>::new());
+    mesh.insert_attribute(ATTRIBUTE_PULSE, Vec::<f32>::new());
+    mesh
+}
+
 pub(crate) fn update_mesh_verts(
     mut q_mesh: Query<(&mut VertData, &Mesh2dHandle), Changed<VertData>>,
     mut meshes: ResMut<Assets<Mesh>>,
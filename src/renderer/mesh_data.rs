@@ -7,6 +7,8 @@ use bevy::{
 };
 use sark_grids::{point::Point2d, GridPoint};
 
+use crate::TileOrientation;
+
 use super::uv_mapping::UvMapping;
 
 pub const ATTRIBUTE_UV: MeshVertexAttribute =
@@ -15,6 +17,16 @@ pub const ATTRIBUTE_COLOR_BG: MeshVertexAttribute =
     MeshVertexAttribute::new("Vertex_Color_Bg", 2, VertexFormat::Float32x4);
 pub const ATTRIBUTE_COLOR_FG: MeshVertexAttribute =
     MeshVertexAttribute::new("Vertex_Color_Fg", 3, VertexFormat::Float32x4);
+/// The glyph offset of a tile. The first two components are the offset in
+/// tiles, the last two are the same offset in uv space.
+pub const ATTRIBUTE_GLYPH_OFFSET: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Glyph_Offset", 4, VertexFormat::Float32x4);
+/// How far to tint a tile's foreground towards the material's accent color.
+pub const ATTRIBUTE_TINT: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Tint", 5, VertexFormat::Float32);
+/// Whether a tile's background should pulse over time, either 0.0 or 1.0.
+pub const ATTRIBUTE_PULSE: MeshVertexAttribute =
+    MeshVertexAttribute::new("Vertex_Pulse", 6, VertexFormat::Float32);
 
 #[derive(Debug, Default, Component)]
 pub struct VertData {
@@ -59,6 +71,15 @@ pub struct TileData {
     pub uvs: Vec<[f32; 2]>,
     pub fg: Vec<[f32; 4]>,
     pub bg: Vec<[f32; 4]>,
+    pub glyph_offsets: Vec<[f32; 4]>,
+    pub tints: Vec<f32>,
+    pub pulses: Vec<f32>,
+    /// If not empty the data only holds quads for these tile indices, which
+    /// will be written over the existing mesh data instead of replacing it.
+    pub dirty: Vec<usize>,
+    /// The number of tiles the mesh was last fully built with, if it can be
+    /// patched by dirty tracking. Zero otherwise.
+    pub patchable: usize,
 }
 
 impl TileData {
@@ -66,12 +87,19 @@ impl TileData {
         self.uvs.clear();
         self.fg.clear();
         self.bg.clear();
+        self.glyph_offsets.clear();
+        self.tints.clear();
+        self.pulses.clear();
+        self.dirty.clear();
     }
 
     pub fn reserve(&mut self, tile_count: usize) {
         self.uvs.reserve(tile_count * 4);
         self.fg.reserve(tile_count * 4);
         self.bg.reserve(tile_count * 4);
+        self.glyph_offsets.reserve(tile_count * 4);
+        self.tints.reserve(tile_count * 4);
+        self.pulses.reserve(tile_count * 4);
     }
 
     pub fn build_mesh_tiles(&mut self, mesh: &mut Mesh) {
@@ -82,8 +110,7 @@ impl TileData {
             VertexAttributeValues::Float32x2(uvs) => uvs,
             _ => panic!("Unexpected uv format"),
         };
-        uvs.clear();
-        uvs.append(&mut self.uvs);
+        write_attribute(uvs, &mut self.uvs, &self.dirty);
 
         let fg_cols = mesh
             .attribute_mut(ATTRIBUTE_COLOR_FG)
@@ -92,8 +119,7 @@ impl TileData {
             VertexAttributeValues::Float32x4(fg) => fg,
             _ => panic!("Unexpected fg colors format"),
         };
-        fg_cols.clear();
-        fg_cols.append(&mut self.fg);
+        write_attribute(fg_cols, &mut self.fg, &self.dirty);
 
         let bg_cols = mesh
             .attribute_mut(ATTRIBUTE_COLOR_BG)
@@ -102,11 +128,53 @@ impl TileData {
             VertexAttributeValues::Float32x4(bg) => bg,
             _ => panic!("Unexpected bg colors format"),
         };
-        bg_cols.clear();
-        bg_cols.append(&mut self.bg);
+        write_attribute(bg_cols, &mut self.bg, &self.dirty);
+
+        let offsets = mesh
+            .attribute_mut(ATTRIBUTE_GLYPH_OFFSET)
+            .expect("Error retrieving terminal mesh glyph offsets");
+        let offsets = match offsets {
+            VertexAttributeValues::Float32x4(offsets) => offsets,
+            _ => panic!("Unexpected glyph offsets format"),
+        };
+        write_attribute(offsets, &mut self.glyph_offsets, &self.dirty);
+
+        let tints = mesh
+            .attribute_mut(ATTRIBUTE_TINT)
+            .expect("Error retrieving terminal mesh tints");
+        let tints = match tints {
+            VertexAttributeValues::Float32(tints) => tints,
+            _ => panic!("Unexpected tints format"),
+        };
+        write_attribute(tints, &mut self.tints, &self.dirty);
+
+        let pulses = mesh
+            .attribute_mut(ATTRIBUTE_PULSE)
+            .expect("Error retrieving terminal mesh pulses");
+        let pulses = match pulses {
+            VertexAttributeValues::Float32(pulses) => pulses,
+            _ => panic!("Unexpected pulses format"),
+        };
+        write_attribute(pulses, &mut self.pulses, &self.dirty);
+        self.dirty.clear();
     }
 }
 
+/// Move per vertex data into a mesh attribute. If `dirty` is empty the
+/// attribute is replaced, otherwise only the quads of the dirty tiles are
+/// overwritten.
+fn write_attribute<T: Copy>(dst: &mut Vec<T>, src: &mut Vec<T>, dirty: &[usize]) {
+    if dirty.is_empty() {
+        dst.clear();
+        dst.append(src);
+        return;
+    }
+    for (quad, &i) in src.chunks_exact(4).zip(dirty) {
+        dst[i * 4..i * 4 + 4].copy_from_slice(quad);
+    }
+    src.clear();
+}
+
 /// Helper for building the terminal mesh's vertex data.
 pub struct VertMesher<'a> {
     pub tile_size: Vec2,
@@ -126,10 +194,16 @@ impl<'a> VertMesher<'a> {
 
     /// Generate vertex data for a tile at the given position.
     pub fn tile_verts_at(&mut self, xy: impl GridPoint) {
+        self.tile_verts_at_depth(xy, 0.0);
+    }
+
+    /// Generate vertex data for a tile at the given position, with the given
+    /// z position.
+    pub fn tile_verts_at_depth(&mut self, xy: impl GridPoint, depth: f32) {
         let right = Vec3::X * self.tile_size.x;
         let up = Vec3::Y * self.tile_size.y;
 
-        let p = (self.origin + xy.as_vec2() * self.tile_size).extend(0.0);
+        let p = (self.origin + xy.as_vec2() * self.tile_size).extend(depth);
 
         let vd = &mut self.vert_data;
 
@@ -152,6 +226,20 @@ fn color_to_arr(color: Color) -> [f32; 4] {
     [linear.red, linear.green, linear.blue, linear.alpha]
 }
 
+/// Per tile effects that change how a tile is rendered without changing the
+/// tile itself, see [`UvMesher::tile_uvs_ext`].
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct TileEffects {
+    /// Offset of the glyph from the center of the tile, in tiles.
+    pub offset: Vec2,
+    /// How far to tint the foreground towards the material's accent color.
+    pub tint: f32,
+    /// Whether the background pulses over time.
+    pub pulse: bool,
+    /// How the glyph is rotated or mirrored.
+    pub orientation: TileOrientation,
+}
+
 impl<'a> UvMesher<'a> {
     pub fn new(mapping: &'a UvMapping, tile_data: &'a mut TileData) -> Self {
         Self { mapping, tile_data }
@@ -160,13 +248,39 @@ impl<'a> UvMesher<'a> {
     /// Generate tile uvs for the next tile. Note these are not positional,
     /// they must be added in the same order as the vert data.
     pub fn tile_uvs(&mut self, glyph: char, fg: Color, bg: Color) {
+        self.tile_uvs_ext(glyph, fg, bg, TileEffects::default());
+    }
+
+    /// Generate tile uvs for the next tile with the given effects applied.
+    /// Glyph offsets, tints and orientations only affect the glyph, the
+    /// tile's background is only affected by pulses.
+    pub fn tile_uvs_ext(&mut self, glyph: char, fg: Color, bg: Color, effects: TileEffects) {
+        let TileEffects {
+            offset,
+            tint,
+            pulse,
+            orientation,
+        } = effects;
         let td = &mut self.tile_data;
-        let glyph_uv = self.mapping.uvs_from_glyph(glyph);
+        let glyph_uv = orientation.apply_to_uvs(*self.mapping.uvs_from_glyph(glyph));
         td.uvs.extend(glyph_uv);
         td.fg
             .extend(std::iter::repeat(color_to_arr(fg)).take(4));
         td.bg
             .extend(std::iter::repeat(color_to_arr(bg)).take(4));
+
+        // Uvs are in the same order as the tile verts: top left, bottom left,
+        // top right, bottom right
+        let [up, origin, _, right] = glyph_uv.map(Vec2::from);
+        let uv_offset = (right - origin) * offset.x + (up - origin) * offset.y;
+        let offset = [offset.x, offset.y, uv_offset.x, uv_offset.y];
+        td.glyph_offsets
+            .extend(std::iter::repeat(offset).take(4));
+        td.tints
+            .extend(std::iter::repeat(tint).take(4));
+        let pulse = if pulse { 1.0 } else { 0.0 };
+        td.pulses
+            .extend(std::iter::repeat(pulse).take(4));
     }
 }
 
@@ -196,5 +310,76 @@ mod test {
         assert_eq!(4, td.uvs.len());
         assert_eq!(4, td.fg.len());
         assert_eq!(4, td.bg.len());
+        assert_eq!(vec![[0.0; 4]; 4], td.glyph_offsets);
+        assert_eq!(vec![0.0; 4], td.tints);
+        assert_eq!(vec![0.0; 4], td.pulses);
+    }
+
+    #[test]
+    fn glyph_offset() {
+        let mapping = UvMapping::default();
+        let mut td = TileData::default();
+        let mut mesher = UvMesher::new(&mapping, &mut td);
+
+        let effects = TileEffects {
+            offset: Vec2::new(0.5, 0.0),
+            ..Default::default()
+        };
+        mesher.tile_uvs_ext('a', Color::WHITE, Color::BLACK, effects);
+
+        let uvs = mapping.uvs_from_glyph('a');
+        let half_tile = (uvs[3][0] - uvs[1][0]) * 0.5;
+        assert_eq!([0.5, 0.0, half_tile, 0.0], td.glyph_offsets[0]);
+    }
+
+    #[test]
+    fn tint() {
+        let mapping = UvMapping::default();
+        let mut td = TileData::default();
+        let mut mesher = UvMesher::new(&mapping, &mut td);
+
+        mesher.tile_uvs('a', Color::WHITE, Color::BLACK);
+        let effects = TileEffects {
+            tint: 0.75,
+            ..Default::default()
+        };
+        mesher.tile_uvs_ext('b', Color::WHITE, Color::BLACK, effects);
+
+        assert_eq!(8, td.tints.len());
+        assert_eq!([0.0; 4], td.tints[..4]);
+        assert_eq!([0.75; 4], td.tints[4..]);
+    }
+
+    #[test]
+    fn pulse() {
+        let mapping = UvMapping::default();
+        let mut td = TileData::default();
+        let mut mesher = UvMesher::new(&mapping, &mut td);
+
+        mesher.tile_uvs('a', Color::WHITE, Color::BLACK);
+        let effects = TileEffects {
+            pulse: true,
+            ..Default::default()
+        };
+        mesher.tile_uvs_ext('b', Color::WHITE, Color::BLACK, effects);
+
+        assert_eq!([0.0; 4], td.pulses[..4]);
+        assert_eq!([1.0; 4], td.pulses[4..]);
+    }
+
+    #[test]
+    fn orientation() {
+        let mapping = UvMapping::default();
+        let mut td = TileData::default();
+        let mut mesher = UvMesher::new(&mapping, &mut td);
+
+        let effects = TileEffects {
+            orientation: TileOrientation::FlipX,
+            ..Default::default()
+        };
+        mesher.tile_uvs_ext('a', Color::WHITE, Color::BLACK, effects);
+
+        let [tl, bl, tr, br] = *mapping.uvs_from_glyph('a');
+        assert_eq!(vec![tr, br, tl, bl], td.uvs);
     }
 }
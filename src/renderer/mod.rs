@@ -2,6 +2,7 @@
 
 mod border_mesh;
 mod entity;
+mod export;
 mod layout;
 mod material;
 mod mesh;
@@ -17,17 +18,20 @@ mod camera;
 pub mod code_page_437;
 
 use bevy::prelude::{App, IntoSystemConfigs, Last, Plugin, SystemSet};
-pub(crate) use font::BuiltInFontHandles;
+pub(crate) use font::{BuiltInFontHandles, DefaultTerminalFont};
 
+pub use border_mesh::MarchingAnts;
 pub use entity::*;
 
 pub use font::TerminalFont;
 
 pub use layout::TerminalLayout;
-pub use material::TerminalMaterial;
+pub use material::{TerminalMaterial, TerminalShader};
+pub use terminal_mesh::TerminalMeshData;
+pub use uv_mapping::UvMapping;
 
 #[cfg(feature = "camera")]
-pub use camera::{AutoCamera, TiledCamera, TiledCameraBundle};
+pub use camera::{AutoCamera, AutoCameraSettings, TiledCamera, TiledCameraBundle};
 
 /// System set for the terminal mesh initialization system.
 #[derive(SystemSet, Debug, Hash, PartialEq, Eq, Clone)]
@@ -68,12 +72,16 @@ impl Plugin for TerminalRendererPlugin {
             Last,
             (
                 mesh::init_mesh.in_set(MeshInit),
+                material::font_image_change.in_set(TerminalMaterialChange),
+                material::terminal_shader_change.in_set(TerminalMaterialChange),
+                material::terminal_depth_change.in_set(TerminalMaterialChange),
                 material::material_change.in_set(TerminalMaterialChange),
                 terminal_mesh::update_layout.in_set(TerminalLayoutChange),
                 terminal_mesh::update_vert_data.in_set(TerminalUpdateTiles),
                 terminal_mesh::update_tile_data.in_set(TerminalUpdateTiles),
                 mesh::update_mesh_verts.in_set(TerminalRender),
                 mesh::update_mesh_tiles.in_set(TerminalRender),
+                terminal_mesh::mark_rendered.in_set(TerminalRender),
             )
                 .chain(),
         );
@@ -1,15 +1,19 @@
-#import bevy_render::view::View;
+#import bevy_render::{globals::Globals, view::View};
 #import bevy_sprite::{mesh2d_functions as mesh_functions, mesh2d_types::Mesh2d};
 
 struct TerminalMaterial {
     clip_color: vec4<f32>,
     // 'flags' is a bit field indicating various options. u32 is 32 bits so we have up to 32 options.
     flags: u32,
+    // The color foregrounds are tinted towards by the per-tile tint
+    accent_color: vec4<f32>,
 };
 const TERMINAL_MATERIAL_FLAGS_TEXTURE_BIT: u32 = 1u;
 
 @group(0) @binding(0)
 var<uniform> view: View;
+@group(0) @binding(1)
+var<uniform> globals: Globals;
 
 @group(2) @binding(0)
 var<uniform> material: TerminalMaterial;
@@ -23,10 +27,16 @@ var<uniform> mesh: Mesh2d;
 
 struct Vertex {
     @builtin(instance_index) instance_index: u32,
+    @builtin(vertex_index) vertex_index: u32,
     @location(0) position: vec3<f32>,
     @location(1) uv: vec2<f32>,
     @location(2) bg_color: vec4<f32>,
     @location(3) fg_color: vec4<f32>,
+    // xy is the glyph offset in tiles, zw is the same offset in uv space
+    @location(4) glyph_offset: vec4<f32>,
+    @location(5) tint: f32,
+    // 1.0 if the background should pulse over time
+    @location(6) pulse: f32,
 };
 
 struct VertexOutput {
@@ -35,6 +45,11 @@ struct VertexOutput {
     @location(1) uv: vec2<f32>,
     @location(2) bg_color: vec4<f32>,
     @location(3) fg_color: vec4<f32>,
+    @location(4) glyph_offset: vec4<f32>,
+    // Position of the vertex within it's tile, [0..1]
+    @location(5) tile_position: vec2<f32>,
+    @location(6) tint: f32,
+    @location(7) pulse: f32,
 };
 
 /// Entry point for the vertex shader
@@ -49,6 +64,12 @@ fn vertex(vertex: Vertex) -> VertexOutput {
     out.uv = vertex.uv;
     out.fg_color = vertex.fg_color;
     out.bg_color = vertex.bg_color;
+    out.glyph_offset = vertex.glyph_offset;
+    // Tile verts are built in the order: top left, bottom left, top right, bottom right
+    let corner = vertex.vertex_index % 4u;
+    out.tile_position = vec2<f32>(f32(corner / 2u), f32(1u - corner % 2u));
+    out.tint = vertex.tint;
+    out.pulse = vertex.pulse;
     return out;
 }
 
@@ -58,29 +79,48 @@ struct FragmentInput {
     @location(1) uv: vec2<f32>,
     @location(2) bg_color: vec4<f32>,
     @location(3) fg_color: vec4<f32>,
+    @location(4) glyph_offset: vec4<f32>,
+    @location(5) tile_position: vec2<f32>,
+    @location(6) tint: f32,
+    @location(7) pulse: f32,
 };
 
+// Pulses per second for pulsing backgrounds
+const PULSE_RATE: f32 = 1.0;
+
 @fragment
 fn fragment(in: FragmentInput) -> @location(0) vec4<f32> {
     
     var clip_color: vec4<f32> = material.clip_color;
-    var fg_color = in.fg_color;
+    var fg_color = vec4<f32>(mix(in.fg_color.rgb, material.accent_color.rgb, in.tint), in.fg_color.a);
     var bg_color = in.bg_color;
+    // Pulsing backgrounds fade between full and quarter brightness
+    let wave = 0.5 + 0.5 * sin(globals.time * PULSE_RATE * 6.2831853);
+    bg_color = vec4<f32>(bg_color.rgb * mix(1.0, 0.25 + 0.75 * wave, in.pulse), bg_color.a);
 
     var out_color = fg_color;
 
+    // Offset glyphs sample from the shifted uv, anything shifted outside the
+    // tile shows the background
+    let glyph_position = in.tile_position - in.glyph_offset.xy;
+    let outside = any(glyph_position < vec2<f32>(0.0)) || any(glyph_position > vec2<f32>(1.0));
+
     if ((material.flags & TERMINAL_MATERIAL_FLAGS_TEXTURE_BIT) != 0u) {
-        var tex_color = textureSample(texture, texture_sampler, in.uv);
+        var tex_color = textureSample(texture, texture_sampler, in.uv - in.glyph_offset.zw);
 
         let tex_rgb = vec3<f32>(tex_color.rgb);
         let clip_rgb = vec3<f32>(clip_color.rgb);
         
-        if( all(tex_rgb - clip_rgb < vec3<f32>(0.001, 0.001, 0.001)) ) {
+        if( outside || all(tex_rgb - clip_rgb < vec3<f32>(0.001, 0.001, 0.001)) ) {
             out_color = bg_color;
         } else {
             out_color = vec4<f32>(tex_color.rgb * fg_color.rgb, fg_color.a);
         }
     }
+    // Don't let fully transparent pixels write depth and hide other terminals
+    if (out_color.a <= 0.0) {
+        discard;
+    }
     //return vec4<f32>(1.0, 0.0, 0.0, 1.0);
     return out_color;
 }
@@ -1,10 +1,10 @@
-use bevy::prelude::{Assets, Changed, GlobalTransform, Handle, Or, Query, Res};
-use sark_grids::Size2d;
+use bevy::prelude::{Assets, Changed, GlobalTransform, Handle, IVec2, Or, Query, Ref, Res, Vec2};
+use sark_grids::{geometry::GridRect, Size2d};
 
-use crate::{Terminal, TerminalLayout};
+use crate::{Terminal, TerminalLayout, Tile, TileOrientation};
 
 use super::{
-    mesh_data::{TileData, UvMesher, VertData, VertMesher},
+    mesh_data::{TileData, TileEffects, UvMesher, VertData, VertMesher},
     uv_mapping::UvMapping,
 };
 
@@ -23,52 +23,634 @@ pub(crate) fn update_layout(
 #[allow(clippy::type_complexity)]
 pub(crate) fn update_vert_data(
     mut q_term: Query<
-        (&TerminalLayout, &mut VertData, &Handle<UvMapping>),
-        Changed<TerminalLayout>,
+        (
+            &Terminal,
+            Ref<TerminalLayout>,
+            &mut VertData,
+            &Handle<UvMapping>,
+        ),
+        Or<(Changed<Terminal>, Changed<TerminalLayout>)>,
     >,
     mappings: Res<Assets<UvMapping>>,
 ) {
-    for (layout, mut verts, mapping) in &mut q_term {
+    for (term, layout, mut verts, mapping) in &mut q_term {
         if mappings.get(mapping).is_none() {
             continue;
         }
-
-        verts.clear();
-        verts.reserve(layout.term_size().len());
-
-        //let origin = layout.origin();
-        //println!("Origin {}", origin);
-        let mut mesher = VertMesher::new(layout.origin(), layout.tile_size, &mut verts);
-
-        // Note the order verts are added - uvs must be added in the same order!
-        for i in 0..layout.term_size().len() {
-            let x = i % layout.width();
-            let y = i / layout.width();
-            mesher.tile_verts_at([x, y]);
+        // Vertex positions only depend on terminal contents if invisible tiles
+        // are being skipped, overlays have been added or removed or tiles have
+        // depths
+        let has_overlays = term.iter_overlays().next().is_some();
+        let quads = layout.term_size().len() + layout.backdrop.is_some() as usize;
+        if !layout.is_changed()
+            && !layout.skip_invisible_tiles
+            && !has_overlays
+            && term.depths().is_empty()
+            && verts.verts.len() == quads * 4
+        {
+            continue;
         }
+
+        build_vert_data(term, &layout, &mut verts);
     }
 }
 
 #[allow(clippy::type_complexity)]
 pub(crate) fn update_tile_data(
     mut q_term: Query<
-        (&Terminal, &mut TileData, &Handle<UvMapping>),
+        (
+            &mut Terminal,
+            Ref<TerminalLayout>,
+            &mut TileData,
+            &Handle<UvMapping>,
+        ),
         Or<(Changed<Terminal>, Changed<TerminalLayout>)>,
     >,
     mappings: Res<Assets<UvMapping>>,
 ) {
-    for (term, mut tiles, mapping) in &mut q_term {
+    for (mut term, layout, mut tiles, mapping) in &mut q_term {
         if mappings.get(mapping).is_none() {
             continue;
         }
-        tiles.clear();
-        tiles.reserve(term.size().len());
         let mapping = mappings.get(mapping).unwrap();
-        let mut mesher = UvMesher::new(mapping, &mut tiles);
 
-        //println!("Updating tile data");
-        for tile in term.iter() {
-            mesher.tile_uvs(tile.glyph, tile.fg_color, tile.bg_color);
+        // Don't flag the terminal as changed again
+        let dirty = if layout.dirty_tracking {
+            term.bypass_change_detection().take_dirty_rect()
+        } else {
+            None
+        };
+        let term = &*term;
+
+        if !layout.is_changed() && can_patch(term, &layout) && tiles.patchable == term.slice().len()
+        {
+            // Don't flag the tile data as changed if nothing needs updating.
+            // An empty dirty list would replace the whole mesh
+            let dirty = dirty
+                .map(|rect| dirty_tiles(term, rect))
+                .unwrap_or_default();
+            if !dirty.is_empty() {
+                build_dirty_tile_data(term, &layout, mapping, dirty, &mut tiles);
+            }
+            continue;
+        }
+
+        build_tile_data(term, &layout, mapping, &mut tiles);
+        mark_patchable(term, &layout, &mut tiles);
+    }
+}
+
+/// Reset the dirty flag of every terminal that was rendered this frame, see
+/// [`Terminal::is_dirty`].
+pub(crate) fn mark_rendered(mut q_term: Query<&mut Terminal, Changed<Terminal>>) {
+    for mut term in &mut q_term {
+        // Don't flag the terminal as changed again
+        term.bypass_change_detection().mark_rendered();
+    }
+}
+
+/// Whether the mesh for this terminal can be updated one tile at a time. The
+/// quad for each tile must be at the same index as the tile.
+fn can_patch(term: &Terminal, layout: &TerminalLayout) -> bool {
+    layout.dirty_tracking
+        && !layout.skip_invisible_tiles
+        && layout.backdrop.is_none()
+        && term.iter_overlays().next().is_none()
+        && term.glyph_offsets().is_empty()
+        && term.tints().is_empty()
+        && term.pulses().is_empty()
+        && term.orientations().is_empty()
+}
+
+/// Record whether the fully built mesh can be updated one tile at a time
+/// next frame.
+fn mark_patchable(term: &Terminal, layout: &TerminalLayout, tiles: &mut TileData) {
+    tiles.patchable = if can_patch(term, layout) {
+        term.slice().len()
+    } else {
+        0
+    };
+}
+
+/// The indices of all tiles inside the terminal's dirty rect, see
+/// [`Terminal::take_dirty_rect`].
+fn dirty_tiles(term: &Terminal, rect: GridRect) -> Vec<usize> {
+    let min = rect.min_i().max(IVec2::ZERO);
+    let max = rect.max_i().min(term.size().as_ivec2() - 1);
+    (min.y..=max.y)
+        .flat_map(|y| (min.x..=max.x).map(move |x| term.transform_lti([x, y])))
+        .collect()
+}
+
+/// Build tile data for only the dirty tiles, to be written over the existing
+/// mesh data.
+fn build_dirty_tile_data(
+    term: &Terminal,
+    layout: &TerminalLayout,
+    mapping: &UvMapping,
+    dirty: Vec<usize>,
+    tiles: &mut TileData,
+) {
+    tiles.clear();
+    tiles.reserve(dirty.len());
+    let mut mesher = UvMesher::new(mapping, tiles);
+    for &i in &dirty {
+        let tile = term.slice()[i];
+        let effects = TileEffects {
+            offset: layout.glyph_offset,
+            ..Default::default()
+        };
+        mesher.tile_uvs_ext(tile.glyph, tile.fg_color, tile.bg_color, effects);
+    }
+    tiles.dirty = dirty;
+}
+
+/// The vertex data for a terminal mesh.
+///
+/// Each tile is a quad made of four vertices, every attribute has one entry per
+/// vertex. If the layout has a backdrop (see [`TerminalLayout::backdrop`]) its
+/// quad comes first. Quads for any overlay glyphs (see [`Terminal::put_overlay`])
+/// follow the tile quads. See [`Terminal::build_mesh_data`].
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct TerminalMeshData {
+    pub positions: Vec<[f32; 3]>,
+    pub indices: Vec<u32>,
+    pub uvs: Vec<[f32; 2]>,
+    /// Foreground colors, in linear color space.
+    pub fg_colors: Vec<[f32; 4]>,
+    /// Background colors, in linear color space.
+    pub bg_colors: Vec<[f32; 4]>,
+    /// Glyph offsets, see [`Terminal::set_glyph_offset`]. The first two
+    /// components are the offset in tiles, the last two are the offset in uv
+    /// space.
+    pub glyph_offsets: Vec<[f32; 4]>,
+    /// Accent tints, see [`Terminal::set_tint`].
+    pub tints: Vec<f32>,
+    /// Background pulses, see [`Terminal::set_pulse`]. Either 0.0 or 1.0.
+    pub pulses: Vec<f32>,
+}
+
+impl Terminal {
+    /// Build the vertex data that would be used to render this terminal's
+    /// mesh, without spawning anything.
+    ///
+    /// Useful for debugging custom materials and uv mappings, or for exporting
+    /// geometry.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let term = Terminal::new([2,1]);
+    /// let layout = TerminalLayout::from(&term);
+    /// let data = term.build_mesh_data(&layout, &UvMapping::default());
+    /// assert_eq!(8, data.positions.len());
+    /// ```
+    pub fn build_mesh_data(
+        &self,
+        layout: &TerminalLayout,
+        mapping: &UvMapping,
+    ) -> TerminalMeshData {
+        let mut verts = VertData::default();
+        let mut tiles = TileData::default();
+        build_vert_data(self, layout, &mut verts);
+        build_tile_data(self, layout, mapping, &mut tiles);
+        TerminalMeshData {
+            positions: verts.verts,
+            indices: verts.indices,
+            uvs: tiles.uvs,
+            fg_colors: tiles.fg,
+            bg_colors: tiles.bg,
+            glyph_offsets: tiles.glyph_offsets,
+            tints: tiles.tints,
+            pulses: tiles.pulses,
+        }
+    }
+}
+
+fn build_vert_data(term: &Terminal, layout: &TerminalLayout, verts: &mut VertData) {
+    verts.clear();
+    verts.reserve(layout.term_size().len());
+
+    // The backdrop is drawn first so it renders behind everything else
+    if layout.backdrop.is_some() {
+        let (origin, size) = layout.backdrop_rect();
+        VertMesher::new(origin, size, verts).tile_verts_at([0, 0]);
+    }
+
+    let mut mesher = VertMesher::new(layout.origin(), layout.tile_size, verts);
+    let depth = |i: usize| term.depths().get(i).copied().unwrap_or(0.0);
+
+    // Note the order verts are added - uvs must be added in the same order!
+    for i in 0..layout.term_size().len() {
+        if layout.skip_invisible_tiles && term.slice().get(i).is_some_and(Tile::is_invisible) {
+            continue;
+        }
+        let x = i % layout.width();
+        let y = i / layout.width();
+        mesher.tile_verts_at_depth([x, y], depth(i));
+    }
+
+    // Overlays are drawn after all tiles so they render on top
+    for (i, _, _) in term.iter_overlays() {
+        let x = i % layout.width();
+        let y = i / layout.width();
+        mesher.tile_verts_at_depth([x, y], depth(i));
+    }
+}
+
+fn build_tile_data(
+    term: &Terminal,
+    layout: &TerminalLayout,
+    mapping: &UvMapping,
+    tiles: &mut TileData,
+) {
+    tiles.clear();
+    tiles.reserve(term.size().len());
+    let mut mesher = UvMesher::new(mapping, tiles);
+
+    if let Some(color) = layout.backdrop {
+        mesher.tile_uvs(' ', color, color);
+    }
+
+    let offsets = term.glyph_offsets();
+    let tints = term.tints();
+    let pulses = term.pulses();
+    let orientations = term.orientations();
+    let effects = |i: usize| TileEffects {
+        offset: offsets.get(i).copied().unwrap_or(Vec2::ZERO) + layout.glyph_offset,
+        tint: tints.get(i).copied().unwrap_or(0.0),
+        pulse: pulses.get(i).copied().unwrap_or(false),
+        orientation: orientations.get(i).copied().unwrap_or_default(),
+    };
+    for (i, tile) in term.iter().enumerate() {
+        if layout.skip_invisible_tiles && tile.is_invisible() {
+            continue;
         }
+        mesher.tile_uvs_ext(tile.glyph, tile.fg_color, tile.bg_color, effects(i));
+    }
+
+    let transparent = Tile::transparent().bg_color;
+    for (i, glyph, color) in term.iter_overlays() {
+        // Overlays have a transparent background, there's nothing to pulse,
+        // and their glyphs are drawn upright
+        let effects = TileEffects {
+            pulse: false,
+            orientation: TileOrientation::Normal,
+            ..effects(i)
+        };
+        mesher.tile_uvs_ext(glyph, color, transparent, effects);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Instant;
+
+    use bevy::{
+        prelude::{App, Color, GlobalTransform, Last, Mesh, Update, Vec2},
+        render::mesh::VertexAttributeValues,
+    };
+
+    use crate::{
+        renderer::uv_mapping::UvMapping, Border, StringFormatter, Terminal, TerminalLayout, Tile,
+        TileFormatter, TileOrientation,
+    };
+
+    use super::{
+        build_dirty_tile_data, build_tile_data, build_vert_data, dirty_tiles, mark_patchable,
+        mark_rendered, update_layout, TerminalMeshData, TileData, VertData,
+    };
+    use crate::renderer::{
+        mesh::new_terminal_mesh,
+        mesh_data::{ATTRIBUTE_COLOR_FG, ATTRIBUTE_UV},
+    };
+
+    fn mesh_uvs(mesh: &Mesh) -> Vec<[f32; 2]> {
+        match mesh.attribute(ATTRIBUTE_UV) {
+            Some(VertexAttributeValues::Float32x2(uvs)) => uvs.clone(),
+            _ => panic!("Missing uvs"),
+        }
+    }
+
+    fn mesh_fg(mesh: &Mesh) -> Vec<[f32; 4]> {
+        match mesh.attribute(ATTRIBUTE_COLOR_FG) {
+            Some(VertexAttributeValues::Float32x4(fg)) => fg.clone(),
+            _ => panic!("Missing fg colors"),
+        }
+    }
+
+    /// Build the full mesh for a terminal with dirty tracking enabled.
+    fn full_mesh(term: &Terminal, layout: &TerminalLayout, tiles: &mut TileData) -> Mesh {
+        let mut mesh = new_terminal_mesh();
+        build_tile_data(term, layout, &UvMapping::default(), tiles);
+        mark_patchable(term, layout, tiles);
+        tiles.build_mesh_tiles(&mut mesh);
+        mesh
+    }
+
+    #[test]
+    fn skip_invisible_tiles() {
+        let mut term = Terminal::new([10, 10]).with_clear_tile(Tile::transparent());
+        term.put_string([0, 0], "Hi".fg(Color::WHITE));
+        let mut layout = TerminalLayout::from(&term);
+        let mapping = UvMapping::default();
+
+        let mut verts = VertData::default();
+        let mut tiles = TileData::default();
+        build_vert_data(&term, &layout, &mut verts);
+        build_tile_data(&term, &layout, &mapping, &mut tiles);
+        assert_eq!(400, verts.verts.len());
+        assert_eq!(400, tiles.uvs.len());
+
+        layout.skip_invisible_tiles = true;
+        build_vert_data(&term, &layout, &mut verts);
+        build_tile_data(&term, &layout, &mapping, &mut tiles);
+        assert_eq!(8, verts.verts.len());
+        assert_eq!(12, verts.indices.len());
+        assert_eq!(8, tiles.uvs.len());
+    }
+
+    #[test]
+    fn build_mesh_data() {
+        let mut term = Terminal::new([1, 1]);
+        term.put_char([0, 0], 'a'.fg(Color::WHITE).bg(Color::BLACK));
+        let layout = TerminalLayout::from(&term);
+        let mapping = UvMapping::default();
+
+        let data = term.build_mesh_data(&layout, &mapping);
+
+        assert_eq!(4, data.positions.len());
+        assert_eq!(vec![0, 1, 2, 3, 2, 1], data.indices);
+        let origin = layout.origin().extend(0.0).to_array();
+        assert!(data.positions.contains(&origin));
+        assert_eq!(mapping.uvs_from_glyph('a').to_vec(), data.uvs);
+        assert_eq!(vec![[1.0; 4]; 4], data.fg_colors);
+        assert_eq!(vec![[0.0, 0.0, 0.0, 1.0]; 4], data.bg_colors);
+    }
+
+    #[test]
+    fn overlay_quads() {
+        let mut term = Terminal::new([1, 1]);
+        term.put_char([0, 0], 'a');
+        term.put_overlay([0, 0], '`', Color::WHITE);
+        let layout = TerminalLayout::from(&term);
+        let mapping = UvMapping::default();
+
+        let data = term.build_mesh_data(&layout, &mapping);
+
+        assert_eq!(8, data.positions.len());
+        assert_eq!(12, data.indices.len());
+        // Both quads cover the same cell
+        assert_eq!(data.positions[..4], data.positions[4..]);
+        assert_eq!(mapping.uvs_from_glyph('a')[..], data.uvs[..4]);
+        assert_eq!(mapping.uvs_from_glyph('`')[..], data.uvs[4..]);
+        assert_eq!([0.0; 4], data.bg_colors[4]);
+    }
+
+    #[test]
+    fn tints() {
+        let mut term = Terminal::new([2, 1]);
+        term.set_tint([1, 0], 0.25);
+        let layout = TerminalLayout::from(&term);
+
+        let data = term.build_mesh_data(&layout, &UvMapping::default());
+
+        assert_eq!(vec![0.0, 0.0, 0.0, 0.0, 0.25, 0.25, 0.25, 0.25], data.tints);
+    }
+
+    #[test]
+    fn orientations() {
+        let mut term = Terminal::new([2, 1]);
+        term.put_string([0, 0], "ab");
+        term.set_orientation([1, 0], TileOrientation::Rotate180);
+        let layout = TerminalLayout::from(&term);
+        let mapping = UvMapping::default();
+
+        let data = term.build_mesh_data(&layout, &mapping);
+
+        let [tl, bl, tr, br] = *mapping.uvs_from_glyph('b');
+        assert_eq!(mapping.uvs_from_glyph('a')[..], data.uvs[..4]);
+        assert_eq!([br, tr, bl, tl], data.uvs[4..]);
+    }
+
+    #[test]
+    fn pulses() {
+        let mut term = Terminal::new([2, 1]);
+        term.set_pulse([0, 0], true);
+        let layout = TerminalLayout::from(&term);
+
+        let data = term.build_mesh_data(&layout, &UvMapping::default());
+
+        assert_eq!(vec![1.0, 1.0, 1.0, 1.0, 0.0, 0.0, 0.0, 0.0], data.pulses);
+    }
+
+    #[test]
+    fn is_dirty() {
+        let mut app = App::new();
+        app.add_systems(Last, mark_rendered);
+        let term = app.world_mut().spawn(Terminal::new([5, 5])).id();
+        let is_dirty = |app: &App| app.world().get::<Terminal>(term).unwrap().is_dirty();
+        assert!(is_dirty(&app));
+
+        app.update();
+        assert!(!is_dirty(&app));
+        // A clean frame
+        app.update();
+        assert!(!is_dirty(&app));
+
+        let mut t = app.world_mut().get_mut::<Terminal>(term).unwrap();
+        t.put_char([1, 1], 'a');
+        assert!(is_dirty(&app));
+        assert!(is_dirty(&app));
+
+        app.update();
+        assert!(!is_dirty(&app));
+
+        // Writing the same tile again still counts
+        let mut t = app.world_mut().get_mut::<Terminal>(term).unwrap();
+        t.put_char([1, 1], 'a');
+        assert!(is_dirty(&app));
+    }
+
+    #[test]
+    fn resize_keeps_border() {
+        let mut app = App::new();
+        app.add_systems(Update, update_layout);
+        let term = Terminal::new([5, 5]).with_border(Border::single_line());
+        let layout = TerminalLayout::from(&term);
+        let entity = app
+            .world_mut()
+            .spawn((term, layout, GlobalTransform::default()))
+            .id();
+        app.update();
+
+        let mut term = app.world_mut().get_mut::<Terminal>(entity).unwrap();
+        term.resize([8, 3]);
+        assert!(term.has_border());
+        assert_eq!([10, 5], term.size_with_border().to_array());
+        app.update();
+
+        let world = app.world();
+        let term = world.get::<Terminal>(entity).unwrap();
+        let layout = world.get::<TerminalLayout>(entity).unwrap();
+        assert_eq!(term.border(), layout.border());
+        assert_eq!([8, 3], layout.term_size().to_array());
+    }
+
+    #[test]
+    fn depth_occludes() {
+        let mapping = UvMapping::default();
+        let mut wall = Terminal::new([2, 1]);
+        wall.put_string([0, 0], "##");
+        wall.set_depth([0, 0], 1.0);
+        wall.set_depth([1, 0], 3.0);
+        let mut player = Terminal::new([2, 1]).with_clear_tile(Tile::transparent());
+        player.put_char([0, 0], '@');
+        player.put_char([1, 0], '@');
+        player.set_depth([0, 0], 2.0);
+        player.set_depth([1, 0], 2.0);
+
+        let layout = TerminalLayout::from(&wall);
+        let wall = wall.build_mesh_data(&layout, &mapping);
+        let player = player.build_mesh_data(&layout, &mapping);
+
+        // Every vert of a tile shares the tile's depth, the higher depth is
+        // in front of the 2d camera
+        let z = |data: &TerminalMeshData, i: usize| data.positions[i * 4][2];
+        assert!(data_depths_match(&wall) && data_depths_match(&player));
+        assert!(z(&player, 0) > z(&wall, 0));
+        assert!(z(&wall, 1) > z(&player, 1));
+    }
+
+    fn data_depths_match(data: &TerminalMeshData) -> bool {
+        data.positions
+            .chunks(4)
+            .all(|quad| quad.iter().all(|p| p[2] == quad[0][2]))
+    }
+
+    #[test]
+    fn backdrop() {
+        let term = Terminal::new([3, 2]).with_border(Border::single_line());
+        let mut layout = TerminalLayout::from(&term);
+        layout.backdrop = Some(Color::WHITE);
+
+        let data = term.build_mesh_data(&layout, &UvMapping::default());
+
+        // The backdrop quad is first, so tiles and their glyphs draw over it
+        assert_eq!(7 * 4, data.positions.len());
+        assert_eq!(vec![[1.0; 4]; 4], data.bg_colors[..4]);
+        assert_eq!(vec![[0.0, 0.0, 0.0, 1.0]; 24], data.bg_colors[4..]);
+
+        // Covers the terminal and its border
+        let (min, max) =
+            data.positions[..4]
+                .iter()
+                .fold((Vec2::MAX, Vec2::MIN), |(min, max), p| {
+                    let p = Vec2::new(p[0], p[1]);
+                    (min.min(p), max.max(p))
+                });
+        assert_eq!(layout.origin() - Vec2::ONE, min);
+        assert_eq!(Vec2::new(5.0, 4.0), max - min);
+    }
+
+    #[test]
+    fn layout_glyph_offset() {
+        let term = Terminal::new([2, 1]);
+        let mut layout = TerminalLayout::from(&term);
+        let mapping = UvMapping::default();
+        let before = term.build_mesh_data(&layout, &mapping);
+
+        layout.glyph_offset = Vec2::new(0.0, -0.125);
+        let after = term.build_mesh_data(&layout, &mapping);
+
+        // Tile quads and their backgrounds don't move
+        assert_eq!(before.positions, after.positions);
+        assert_eq!(before.bg_colors, after.bg_colors);
+        assert!(after
+            .glyph_offsets
+            .iter()
+            .all(|o| o[0] == 0.0 && o[1] == -0.125));
+        assert!(after.glyph_offsets.iter().all(|o| o[3] != 0.0));
+    }
+
+    #[test]
+    fn dirty_tracking_patches_changed_tiles() {
+        let mut term = Terminal::new([10, 5]);
+        term.put_string([0, 0], "Hello");
+        let mut layout = TerminalLayout::from(&term);
+        layout.dirty_tracking = true;
+        let mapping = UvMapping::default();
+
+        let mut tiles = TileData::default();
+        let mut mesh = full_mesh(&term, &layout, &mut tiles);
+        assert_eq!(term.slice().len(), tiles.patchable);
+        term.take_dirty_rect();
+
+        term.put_char([3, 2], 'x'.fg(Color::BLACK));
+        let rect = term.take_dirty_rect().unwrap();
+        let dirty = dirty_tiles(&term, rect);
+        assert_eq!(vec![term.transform_lti([3, 2])], dirty);
+
+        build_dirty_tile_data(&term, &layout, &mapping, dirty, &mut tiles);
+        assert_eq!(4, tiles.uvs.len());
+        tiles.build_mesh_tiles(&mut mesh);
+
+        let expected = full_mesh(&term, &layout, &mut TileData::default());
+        assert_eq!(mesh_uvs(&expected), mesh_uvs(&mesh));
+        assert_eq!(mesh_fg(&expected), mesh_fg(&mesh));
+        assert!(term.take_dirty_rect().is_none());
+    }
+
+    #[test]
+    fn dirty_tracking_disabled() {
+        let term = Terminal::new([2, 2]);
+        let layout = TerminalLayout::from(&term);
+        let mut tiles = TileData::default();
+        full_mesh(&term, &layout, &mut tiles);
+        assert_eq!(0, tiles.patchable);
+    }
+
+    /// Compare single tile updates against a full rebuild on a large terminal.
+    ///
+    /// Run with `cargo test --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_single_tile_update() {
+        const FRAMES: usize = 100;
+        let mut term = Terminal::new([200, 120]);
+        let mut layout = TerminalLayout::from(&term);
+        let mapping = UvMapping::default();
+
+        let mut tiles = TileData::default();
+        let mut mesh = full_mesh(&term, &layout, &mut tiles);
+        let start = Instant::now();
+        for i in 0..FRAMES {
+            term.put_char([i as i32, 0], 'a');
+            build_tile_data(&term, &layout, &mapping, &mut tiles);
+            tiles.build_mesh_tiles(&mut mesh);
+        }
+        let full = start.elapsed();
+
+        layout.dirty_tracking = true;
+        let mut tiles = TileData::default();
+        let mut mesh = full_mesh(&term, &layout, &mut tiles);
+        term.take_dirty_rect();
+        let start = Instant::now();
+        for i in 0..FRAMES {
+            term.put_char([i as i32, 1], 'b');
+            let rect = term.take_dirty_rect().unwrap();
+            let dirty = dirty_tiles(&term, rect);
+            build_dirty_tile_data(&term, &layout, &mapping, dirty, &mut tiles);
+            tiles.build_mesh_tiles(&mut mesh);
+        }
+        let patched = start.elapsed();
+
+        println!(
+            "200x120 single tile update, {} frames: full rebuild {:?}, dirty tracking {:?}",
+            FRAMES, full, patched
+        );
+        assert!(patched < full);
     }
 }
@@ -18,11 +18,19 @@ use super::code_page_437::CP_437_CHARS;
 #[derive(Debug, Clone, Asset, TypePath)]
 pub struct UvMapping {
     uv_map: HashMap<char, [[f32; 2]; 4]>,
+    /// Named icons and the glyph they're written as, see
+    /// [`UvMapping::insert_icon`].
+    icons: HashMap<String, char>,
 }
 
 impl UvMapping {
     pub fn code_page_437() -> Self {
-        UvMapping::from_grid([16, 16], CP_437_CHARS.iter().cloned())
+        // The last tile is a non-breaking space, see `wrap_words`
+        UvMapping::from_grid([16, 16], CP_437_CHARS.iter().cloned()).with_glyph(
+            '\u{A0}',
+            [15, 15],
+            [16, 16],
+        )
     }
 
     /// Create a uv mapping where the keys from the iterator are mapped to their corresponding
@@ -37,7 +45,10 @@ impl UvMapping {
             uv_map.insert(ch, uvs);
         }
 
-        Self { uv_map }
+        Self {
+            uv_map,
+            icons: HashMap::default(),
+        }
     }
 
     pub fn get_grid_uvs(xy: [u32; 2], tile_count: [u32; 2]) -> [[f32; 2]; 4] {
@@ -54,6 +65,61 @@ impl UvMapping {
         ]
     }
 
+    /// Map a glyph to the tile at the given position on a 2d tile sheet.
+    ///
+    /// This can be used to add icon tiles to the mapping, see
+    /// [`UvMapping::insert_icon`].
+    pub fn insert_glyph(&mut self, glyph: char, xy: [u32; 2], tile_count: [u32; 2]) {
+        self.uv_map
+            .insert(glyph, Self::get_grid_uvs(xy, tile_count));
+    }
+
+    /// Map a glyph to the tile at the given position on a 2d tile sheet.
+    pub fn with_glyph(mut self, glyph: char, xy: [u32; 2], tile_count: [u32; 2]) -> Self {
+        self.insert_glyph(glyph, xy, tile_count);
+        self
+    }
+
+    /// Register a named icon, such as `":skull:"`, which is written to a
+    /// terminal as the given glyph. See
+    /// [`Terminal::put_icon`](crate::Terminal::put_icon).
+    ///
+    /// The glyph must also be mapped to a tile. A character from the unicode
+    /// private use area (`'\u{E000}'` to `'\u{F8FF}'`) is a good choice for
+    /// icons that don't correspond to any real character.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut mapping = UvMapping::code_page_437();
+    /// mapping.insert_glyph('\u{E000}', [4, 15], [16, 16]);
+    /// mapping.insert_icon(":skull:", '\u{E000}');
+    /// assert_eq!(Some('\u{E000}'), mapping.icon_glyph(":skull:"));
+    /// ```
+    pub fn insert_icon(&mut self, name: impl Into<String>, glyph: char) {
+        self.icons.insert(name.into(), glyph);
+    }
+
+    /// Register a named icon. See [`UvMapping::insert_icon`].
+    pub fn with_icon(mut self, name: impl Into<String>, glyph: char) -> Self {
+        self.insert_icon(name, glyph);
+        self
+    }
+
+    /// Retrieve the glyph for a named icon, if it's been registered.
+    pub fn icon_glyph(&self, name: &str) -> Option<char> {
+        self.icons.get(name).copied()
+    }
+
+    /// Retrieve the uvs for a named icon, if it's been registered and its
+    /// glyph is mapped to a tile.
+    pub fn uvs_from_icon(&self, name: &str) -> Option<&[[f32; 2]; 4]> {
+        self.icon_glyph(name)
+            .and_then(|glyph| self.uv_map.get(&glyph))
+    }
+
     pub fn uvs_from_glyph(&self, ch: char) -> &[[f32; 2]; 4] {
         self.uv_map.get(&ch).unwrap_or_else(|| {
             panic!(
@@ -109,3 +175,34 @@ pub(crate) fn uv_mapping_loaded(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use crate::Terminal;
+
+    use super::UvMapping;
+
+    #[test]
+    fn icon_glyph() {
+        let skull = '\u{E000}';
+        let mapping = UvMapping::code_page_437()
+            .with_glyph(skull, [4, 15], [16, 16])
+            .with_icon(":skull:", skull);
+        let mut term = Terminal::new([5, 5]);
+        term.put_icon([0, 0], ":skull:", &mapping);
+
+        let expected = UvMapping::get_grid_uvs([4, 15], [16, 16]);
+        assert_eq!(&expected, mapping.uvs_from_glyph(term.get_char([0, 0])));
+        assert_eq!(Some(&expected), mapping.uvs_from_icon(":skull:"));
+        assert_eq!(None, mapping.uvs_from_icon(":missing:"));
+    }
+
+    #[test]
+    fn non_breaking_space() {
+        let mapping = UvMapping::code_page_437();
+        assert_eq!(
+            &UvMapping::get_grid_uvs([15, 15], [16, 16]),
+            mapping.uvs_from_glyph('\u{A0}')
+        );
+    }
+}
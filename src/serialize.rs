@@ -0,0 +1,226 @@
+//! Compact serialization for [`Terminal`], enabled by the `serde` feature.
+//!
+//! Only the size, tiles, clear tile and border are saved. Colors are stored as
+//! srgba `u8` arrays, so they are rounded to 8 bits per channel.
+
+use bevy::prelude::Color;
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use crate::{border::Bevel, AlignedString, Border, Edge, Terminal, Tile};
+
+type Rgba = [u8; 4];
+
+fn to_rgba(color: Color) -> Rgba {
+    let c = color.to_srgba();
+    [c.red, c.green, c.blue, c.alpha].map(|v| (v.clamp(0.0, 1.0) * 255.0).round() as u8)
+}
+
+fn from_rgba([r, g, b, a]: Rgba) -> Color {
+    Color::srgba_u8(r, g, b, a)
+}
+
+#[derive(Serialize, Deserialize)]
+struct TileData(char, Rgba, Rgba);
+
+impl From<&Tile> for TileData {
+    fn from(tile: &Tile) -> Self {
+        TileData(tile.glyph, to_rgba(tile.fg_color), to_rgba(tile.bg_color))
+    }
+}
+
+impl From<TileData> for Tile {
+    fn from(TileData(glyph, fg, bg): TileData) -> Self {
+        Tile {
+            glyph,
+            fg_color: from_rgba(fg),
+            bg_color: from_rgba(bg),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct AlignedStringData {
+    align: f32,
+    string: String,
+    fg: Option<Rgba>,
+    bg: Option<Rgba>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct BorderData {
+    /// Glyphs in the same order as [`Border::from_string`].
+    glyphs: [char; 8],
+    title_connectors: bool,
+    title_brackets: Option<[char; 2]>,
+    /// Light color, dark color and whether the bevel is sunken.
+    bevel: Option<(Rgba, Rgba, bool)>,
+    edge_strings: Vec<(Edge, AlignedStringData)>,
+}
+
+impl From<&Border> for BorderData {
+    fn from(b: &Border) -> Self {
+        let mut edge_strings: Vec<_> = b
+            .edge_strings
+            .iter()
+            .map(|(edge, s)| {
+                let string = AlignedStringData {
+                    align: s.align,
+                    string: s.string.clone(),
+                    fg: s.fg_col.map(to_rgba),
+                    bg: s.bg_col.map(to_rgba),
+                };
+                (*edge, string)
+            })
+            .collect();
+        // Keep the output stable, the strings are stored in a hash map
+        edge_strings.sort_by_key(|(edge, _)| *edge as u8);
+        BorderData {
+            glyphs: [
+                b.top_left,
+                b.top,
+                b.top_right,
+                b.left,
+                b.right,
+                b.bottom_left,
+                b.bottom,
+                b.bottom_right,
+            ],
+            title_connectors: b.title_connectors,
+            title_brackets: b.title_brackets,
+            bevel: b
+                .bevel
+                .map(|bevel| (to_rgba(bevel.light), to_rgba(bevel.dark), bevel.sunken)),
+            edge_strings,
+        }
+    }
+}
+
+impl From<BorderData> for Border {
+    fn from(data: BorderData) -> Self {
+        let [top_left, top, top_right, left, right, bottom_left, bottom, bottom_right] =
+            data.glyphs;
+        let mut border = Border {
+            top,
+            left,
+            right,
+            bottom,
+            top_left,
+            top_right,
+            bottom_left,
+            bottom_right,
+            title_connectors: data.title_connectors,
+            title_brackets: data.title_brackets,
+            bevel: data.bevel.map(|(light, dark, sunken)| Bevel {
+                light: from_rgba(light),
+                dark: from_rgba(dark),
+                sunken,
+            }),
+            ..Default::default()
+        };
+        for (edge, s) in data.edge_strings {
+            let string = AlignedString {
+                align: s.align,
+                string: s.string,
+                fg_col: s.fg.map(from_rgba),
+                bg_col: s.bg.map(from_rgba),
+            };
+            border.set_edge_string(edge, string);
+        }
+        border
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct TerminalData {
+    size: [u32; 2],
+    clear_tile: TileData,
+    border: Option<BorderData>,
+    /// Tiles in row-major order, starting from the bottom left.
+    tiles: Vec<TileData>,
+}
+
+impl Serialize for Terminal {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        TerminalData {
+            size: self.size().to_array(),
+            clear_tile: TileData::from(&self.clear_tile),
+            border: self.border().map(BorderData::from),
+            tiles: self.iter().map(TileData::from).collect(),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Terminal {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let data = TerminalData::deserialize(deserializer)?;
+        let [w, h] = data.size;
+        if w == 0 || h == 0 {
+            return Err(D::Error::custom(format!(
+                "invalid terminal size {w}x{h}, terminal dimensions must be at least 1"
+            )));
+        }
+        let expected = w as usize * h as usize;
+        if data.tiles.len() != expected {
+            return Err(D::Error::custom(format!(
+                "terminal of size {w}x{h} should have {expected} tiles, found {}",
+                data.tiles.len()
+            )));
+        }
+        let mut term = Terminal::new([w, h]).with_clear_tile(Tile::from(data.clear_tile));
+        if let Some(border) = data.border {
+            term.set_border(border.into());
+        }
+        for (t, tile) in term.iter_mut().zip(data.tiles) {
+            *t = tile.into();
+        }
+        Ok(term)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::prelude::Color;
+
+    use crate::{
+        AlignedStringFormatter, Border, Edge, StringFormatter, Terminal, Tile, TileFormatter,
+    };
+
+    #[test]
+    fn round_trip() {
+        let mut term = Terminal::new([6, 3])
+            .with_clear_tile(Tile {
+                glyph: '.',
+                fg_color: Color::srgb_u8(100, 100, 100),
+                bg_color: Color::BLACK,
+            })
+            .with_border(
+                Border::double_line()
+                    .with_title("Log".aligned(0.5))
+                    .with_edge_string(Edge::Bottom, "1/2".fg_col(Color::srgb_u8(255, 0, 0)))
+                    .with_bevel(Color::WHITE, Color::srgb_u8(50, 50, 50)),
+            );
+        term.clear();
+        term.put_string([0, 1], "Hello".fg(Color::srgb_u8(10, 200, 30)));
+        term.put_char([5, 2], 'é'.bg(Color::srgba_u8(0, 0, 255, 128)));
+
+        let json = serde_json::to_string(&term).unwrap();
+        let loaded: Terminal = serde_json::from_str(&json).unwrap();
+
+        assert_eq!(term.size(), loaded.size());
+        assert_eq!(term.clear_tile, loaded.clear_tile);
+        assert_eq!(term.border(), loaded.border());
+        for y in 0..3 {
+            assert_eq!(term.get_string([0, y], 6), loaded.get_string([0, y], 6));
+        }
+        assert!(term.iter().eq(loaded.iter()));
+    }
+
+    #[test]
+    fn size_mismatch() {
+        let json = r#"{"size":[2,2],"clear_tile":[" ",[255,255,255,255],[0,0,0,255]],
+            "border":null,"tiles":[[" ",[255,255,255,255],[0,0,0,255]]]}"#;
+        let result = serde_json::from_str::<Terminal>(json);
+        assert!(result.is_err());
+    }
+}
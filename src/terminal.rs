@@ -1,22 +1,39 @@
+use std::borrow::Cow;
 use std::ops::Div;
 use std::ops::RangeBounds;
-use std::ops::Sub;
+#[cfg(feature = "debug_writes")]
+use std::panic::Location;
 
+use bevy::log::warn;
 use bevy::math::IVec2;
 use bevy::math::UVec2;
 use bevy::prelude::Color;
 use bevy::prelude::Component;
+use bevy::prelude::Image;
 use bevy::prelude::Vec2;
+use bevy::render::render_asset::RenderAssetUsages;
+use bevy::render::render_resource::{Extent3d, TextureDimension, TextureFormat};
+use bevy::utils::HashMap;
 
 use sark_grids::geometry::GridRect;
 use sark_grids::grid::Side;
 use sark_grids::Grid;
 use sark_grids::GridPoint;
+use sark_grids::Pivot;
 use sark_grids::Size2d;
 
+use crate::border::AlignedString;
 use crate::border::Border;
+use crate::border::BoxGlyphs;
+use crate::border::Edge;
 use crate::fmt_tile::ColorFormat;
+use crate::formatting::wrap::{is_break, wrap_words};
+use crate::formatting::FormattedString;
+use crate::formatting::FormattedTile;
 use crate::formatting::StringFormatter;
+use crate::line::BresenhamLine;
+use crate::renderer::UvMapping;
+use crate::tile_layer::TileLayer;
 use crate::TileFormatter;
 
 /// A simple terminal for writing text in a readable grid.
@@ -24,6 +41,14 @@ use crate::TileFormatter;
 /// Contains various functions for drawing colorful text to a
 /// terminal.
 ///
+/// With the `serde` feature enabled terminals can be serialized. Only the
+/// size, tiles, `clear_tile` and border are saved, with colors rounded to
+/// 8 bits per channel.
+///
+/// With the `debug_writes` feature enabled, writing two different glyphs to
+/// the same tile in one frame logs a warning, see
+/// [`Terminal::take_write_conflicts`].
+///
 /// # Example
 /// ```rust
 /// use bevy_ascii_terminal::*;
@@ -50,12 +75,59 @@ pub struct Terminal {
     /// terminal positions and sizes do not include the border unless otherwise
     /// specified.
     border: Option<Border>,
+    /// Glyphs replaced when written, see [`Terminal::with_glyph_substitution`].
+    glyph_substitutions: HashMap<char, char>,
+    /// Clickable regions registered with [`Terminal::put_link`].
+    links: Vec<(String, GridRect)>,
+    /// Tiles which are not cleared by [`Terminal::clear_non_sticky`].
+    sticky: TileLayer<bool>,
+    /// Per tile glyph offsets set with [`Terminal::set_glyph_offset`].
+    glyph_offsets: TileLayer<Vec2>,
+    /// Overlay glyphs and their colors set with [`Terminal::put_overlay`].
+    overlays: TileLayer<Option<(char, Color)>>,
+    /// Per tile accent tints set with [`Terminal::set_tint`].
+    tints: TileLayer<f32>,
+    /// Per tile pulse flags set with [`Terminal::set_pulse`].
+    pulses: TileLayer<bool>,
+    /// Per tile glyph orientations set with [`Terminal::set_orientation`].
+    orientations: TileLayer<TileOrientation>,
+    /// Per tile depths set with [`Terminal::set_depth`].
+    depths: TileLayer<f32>,
+    /// Column and line, counted from the top left, where the next character
+    /// written by [`Terminal::print`] will go.
+    print_cursor: UVec2,
+    /// The number of lines ended by [`Terminal::print`] since the print
+    /// cursor was last reset, used to pick the zebra stripe color.
+    print_line: usize,
+    /// Alternating background colors for lines written with
+    /// [`Terminal::print`], see [`Terminal::with_zebra_stripes`].
+    zebra_stripes: Option<[Color; 2]>,
+    /// The pivot applied to positions without one, see
+    /// [`Terminal::with_default_pivot`].
+    default_pivot: Option<Pivot>,
+    /// The area affected by [`Terminal::scroll_up`] and [`Terminal::print`],
+    /// see [`Terminal::with_scroll_region`].
+    scroll_region: Option<GridRect>,
+    /// Whether any tiles were written since the terminal was last rendered,
+    /// see [`Terminal::is_dirty`].
+    dirty: bool,
+    /// The area changed by tracked writes since the last call to
+    /// [`Terminal::take_dirty_rect`].
+    dirty_rect: Option<GridRect>,
+    /// The glyph written to each tile since the terminal was last rendered
+    /// and where it was written from.
+    #[cfg(feature = "debug_writes")]
+    frame_writes: HashMap<usize, (char, &'static Location<'static>)>,
+    /// See [`Terminal::take_write_conflicts`].
+    #[cfg(feature = "debug_writes")]
+    write_conflicts: Vec<WriteConflict>,
 }
 
 /// A single tile of the terminal.
 ///
 /// Defaults to a blank glyph with a black background and a white foreground.
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Tile {
     /// The glyph for the tile. Glyphs are mapped to sprites via the
     /// terminal's `UvMapping`
@@ -70,6 +142,10 @@ impl Tile {
     pub const DEFAULT_FGCOL: Color = Color::WHITE;
     pub const DEFAULT_BGCOL: Color = Color::BLACK;
 
+    /// The maximum difference between color channels for two tiles to be
+    /// considered equal by [`Tile::approx_eq`].
+    pub const COLOR_EPSILON: f32 = 1.0 / 512.0;
+
     /// Create an invisible tile.
     pub fn transparent() -> Tile {
         Tile {
@@ -78,6 +154,234 @@ impl Tile {
             bg_color: Color::srgba_u8(0, 0, 0, 0),
         }
     }
+
+    /// Returns true if the tile would render nothing - it has a fully
+    /// transparent background and either a blank glyph or a fully transparent
+    /// foreground.
+    pub fn is_invisible(&self) -> bool {
+        let transparent = |c: Color| c.to_linear().alpha <= 0.0;
+        transparent(self.bg_color) && (self.glyph == ' ' || transparent(self.fg_color))
+    }
+
+    /// Compare two tiles, treating colors as equal if they are within
+    /// [`Tile::COLOR_EPSILON`] of each other or are both fully transparent.
+    ///
+    /// Useful for comparing against tiles with colors that were calculated
+    /// rather than set directly, which rarely compare exactly.
+    pub fn approx_eq(&self, other: &Tile) -> bool {
+        self.glyph == other.glyph
+            && color_approx_eq(self.fg_color, other.fg_color)
+            && color_approx_eq(self.bg_color, other.bg_color)
+    }
+}
+
+/// How a tile's glyph is rotated or mirrored when it's rendered, see
+/// [`Terminal::set_orientation`].
+///
+/// Rotations are clockwise. Only the glyph is affected, the tile's background
+/// is unchanged.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TileOrientation {
+    #[default]
+    Normal,
+    Rotate90,
+    Rotate180,
+    Rotate270,
+    /// Mirror the glyph horizontally.
+    FlipX,
+    /// Mirror the glyph vertically.
+    FlipY,
+}
+
+impl TileOrientation {
+    /// Reorder the uvs for the corners of a tile, given in the order top
+    /// left, bottom left, top right, bottom right, so the glyph is drawn with
+    /// this orientation.
+    pub fn apply_to_uvs(self, uvs: [[f32; 2]; 4]) -> [[f32; 2]; 4] {
+        let order = match self {
+            TileOrientation::Normal => [0, 1, 2, 3],
+            TileOrientation::Rotate90 => [1, 3, 0, 2],
+            TileOrientation::Rotate180 => [3, 2, 1, 0],
+            TileOrientation::Rotate270 => [2, 0, 3, 1],
+            TileOrientation::FlipX => [2, 3, 0, 1],
+            TileOrientation::FlipY => [1, 0, 3, 2],
+        };
+        order.map(|i| uvs[i])
+    }
+}
+
+/// A tile that was written with two different glyphs in the same frame, see
+/// [`Terminal::take_write_conflicts`].
+#[cfg(feature = "debug_writes")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WriteConflict {
+    /// The tile position in terminal local space.
+    pub xy: IVec2,
+    /// The glyph written first.
+    pub first: char,
+    /// The glyph that replaced it.
+    pub second: char,
+}
+
+/// Error returned when trying to resize a terminal to a size with a zero
+/// dimension. See [`Terminal::try_resize`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct InvalidSizeError {
+    /// The size that was rejected.
+    pub size: UVec2,
+}
+
+impl std::fmt::Display for InvalidSizeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid terminal size {}x{}, terminal dimensions must be at least 1",
+            self.size.x, self.size.y
+        )
+    }
+}
+
+impl std::error::Error for InvalidSizeError {}
+
+/// How blocks of tiles are collapsed into a single tile by
+/// [`Terminal::downscale_into`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum DownscaleMode {
+    /// Use the most common glyph in the block, with the foreground and
+    /// background colors averaged across the whole block.
+    #[default]
+    AverageColor,
+    /// Copy the first tile in the block with the most common glyph, colors
+    /// included.
+    DominantGlyph,
+}
+
+/// Which tile color becomes the pixel color in [`Terminal::to_rgba_image`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ColorSource {
+    #[default]
+    Background,
+    Foreground,
+}
+
+/// Vertical alignment of text within a box, see
+/// [`Terminal::draw_text_box_aligned`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum VerticalAlign {
+    #[default]
+    Top,
+    /// Centered, with any odd row of padding below the text.
+    Center,
+    Bottom,
+}
+
+/// The cardinal arrow glyph closest to the given direction.
+fn arrow_glyph(dir: IVec2) -> char {
+    if dir.x.abs() >= dir.y.abs() {
+        if dir.x < 0 {
+            '←'
+        } else {
+            '→'
+        }
+    } else if dir.y < 0 {
+        '↓'
+    } else {
+        '↑'
+    }
+}
+
+/// Replace all icon names from the mapping in square brackets with their
+/// glyph.
+fn replace_icons(string: &str, mapping: &UvMapping) -> String {
+    let mut out = String::with_capacity(string.len());
+    let mut rest = string;
+    while let Some(start) = rest.find('[') {
+        out.push_str(&rest[..start]);
+        let tail = &rest[start + 1..];
+        let icon = tail
+            .find(']')
+            .and_then(|end| mapping.icon_glyph(&tail[..end]).map(|glyph| (end, glyph)));
+        match icon {
+            Some((end, glyph)) => {
+                out.push(glyph);
+                rest = &tail[end + 1..];
+            }
+            None => {
+                out.push('[');
+                rest = tail;
+            }
+        }
+    }
+    out.push_str(rest);
+    out
+}
+
+/// Black or white, whichever is more readable over the given background.
+///
+/// Uses the relative luminance of the color. The threshold is the luminance
+/// where black and white text have equal contrast ratios.
+fn contrast_color(bg: Color) -> Color {
+    let c = bg.to_linear();
+    let luminance = 0.2126 * c.red + 0.7152 * c.green + 0.0722 * c.blue;
+    if luminance > 0.179 {
+        Color::BLACK
+    } else {
+        Color::WHITE
+    }
+}
+
+/// For each character in `string` (not counting line returns), whether it's
+/// part of an occurrence of `query` on the same line.
+fn match_indices(string: &str, query: &str) -> Vec<bool> {
+    let query: Vec<char> = query.chars().collect();
+    let mut matches = Vec::new();
+    for line in string.lines() {
+        let line: Vec<char> = line.chars().collect();
+        let start = matches.len();
+        matches.resize(start + line.len(), false);
+        if query.is_empty() || query.len() > line.len() {
+            continue;
+        }
+        for i in 0..=line.len() - query.len() {
+            if line[i..i + query.len()] == query[..] {
+                matches[start + i..start + i + query.len()].fill(true);
+            }
+        }
+    }
+    matches
+}
+
+fn average_color(colors: impl Iterator<Item = Color>) -> Color {
+    let mut sum = [0.0; 4];
+    let mut count = 0;
+    for c in colors {
+        let c = c.to_linear();
+        for (s, ch) in sum.iter_mut().zip([c.red, c.green, c.blue, c.alpha]) {
+            *s += ch;
+        }
+        count += 1;
+    }
+    let [r, g, b, a] = sum.map(|s| s / count.max(1) as f32);
+    Color::linear_rgba(r, g, b, a)
+}
+
+fn shade_color(color: Color, factor: f32) -> Color {
+    let c = color.to_srgba();
+    let [r, g, b] = [c.red, c.green, c.blue].map(|ch| (ch * factor).clamp(0.0, 1.0));
+    Color::srgba(r, g, b, c.alpha)
+}
+
+fn color_approx_eq(a: Color, b: Color) -> bool {
+    let [a, b] = [a, b].map(|c| {
+        let c = c.to_linear();
+        [c.red, c.green, c.blue, c.alpha]
+    });
+    if a[3] <= Tile::COLOR_EPSILON && b[3] <= Tile::COLOR_EPSILON {
+        return true;
+    }
+    a.iter()
+        .zip(b.iter())
+        .all(|(a, b)| (a - b).abs() <= Tile::COLOR_EPSILON)
 }
 
 impl Default for Tile {
@@ -100,13 +404,29 @@ impl From<char> for Tile {
 }
 
 impl Terminal {
-    /// Construct a terminal with the given size
+    /// The glyph written in place of missing icons.
+    pub const FALLBACK_GLYPH: char = '?';
+
+    /// Construct a terminal with the given size.
+    ///
+    /// Any zero dimensions will be clamped to a minimum size of 1.
     pub fn new(size: impl Size2d) -> Terminal {
         let clear_tile = Tile::default();
+        let size = size.as_uvec2().max(UVec2::ONE);
+        let len = (size.x * size.y) as usize;
         Terminal {
-            tiles: Grid::new(size),
-            size: size.as_uvec2(),
+            tiles: Grid::new(size.to_array()),
+            size,
             clear_tile,
+            sticky: TileLayer::new(len),
+            glyph_offsets: TileLayer::new(len),
+            overlays: TileLayer::new(len),
+            tints: TileLayer::new(len),
+            pulses: TileLayer::new(len),
+            orientations: TileLayer::new(len),
+            depths: TileLayer::new(len),
+            dirty_rect: Some(GridRect::from_bl([0, 0], size)),
+            dirty: true,
             ..Default::default()
         }
     }
@@ -121,6 +441,178 @@ impl Terminal {
         self
     }
 
+    /// Set a pivot which is applied to any position without a pivot of it's
+    /// own when writing to the terminal.
+    ///
+    /// For example with a [`Pivot::Center`] default pivot `[0,0]` refers to the
+    /// center tile and `[-1,0]` to the tile to the left of it. Strings are
+    /// aligned to the pivot the same as if it was set on the position with
+    /// `xy.pivot(..)`.
+    ///
+    /// The default pivot is used by [`Terminal::put_char`],
+    /// [`Terminal::put_formatted`], [`Terminal::put_tile`] and the functions
+    /// which write a string from a single position, like
+    /// [`Terminal::put_string`]. Other functions always use terminal local
+    /// positions, use [`Terminal::local_point`] to convert positions for them.
+    ///
+    /// This is unrelated to the pivot set with
+    /// [`TerminalBundle::with_pivot`](crate::TerminalBundle::with_pivot),
+    /// which determines where the terminal is rendered relative to it's
+    /// transform.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([11,11]).with_default_pivot(Pivot::Center);
+    /// term.put_char([0,0], '@');
+    /// assert_eq!('@', term.get_char([5,5]));
+    /// ```
+    pub fn with_default_pivot(mut self, pivot: Pivot) -> Self {
+        self.default_pivot = Some(pivot);
+        self
+    }
+
+    /// Set or remove the default pivot, see [`Terminal::with_default_pivot`].
+    pub fn set_default_pivot(&mut self, pivot: Option<Pivot>) {
+        self.default_pivot = pivot;
+    }
+
+    /// The default pivot, see [`Terminal::with_default_pivot`].
+    pub fn default_pivot(&self) -> Option<Pivot> {
+        self.default_pivot
+    }
+
+    /// Limit scrolling and printing to a region of the terminal, like the
+    /// scroll margins of a real terminal. The rect is in terminal local space.
+    ///
+    /// [`Terminal::scroll_up`] only moves the tiles inside the region and the
+    /// print cursor wraps at the edges of the region and scrolls it instead
+    /// of the whole terminal, so anything drawn outside of it, like a header
+    /// or footer, stays in place. The region is clipped to the terminal, if
+    /// it doesn't overlap the terminal at all the whole terminal is used.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// // A log panel with a fixed header
+    /// let mut term = Terminal::new([20,10])
+    ///     .with_scroll_region(GridRect::from_bl([0,0], [20,9]));
+    /// term.put_string([0,9], "== Combat Log ==");
+    /// for i in 0..20 {
+    ///     term.println(format!("The goblin hits you {i}"));
+    /// }
+    /// assert_eq!("== Combat Log ==", term.get_string([0,9], 16));
+    /// ```
+    pub fn with_scroll_region(mut self, rect: GridRect) -> Self {
+        self.set_scroll_region(Some(rect));
+        self
+    }
+
+    /// Set or remove the scroll region, see [`Terminal::with_scroll_region`].
+    ///
+    /// This moves the print cursor to the top left of the region.
+    pub fn set_scroll_region(&mut self, rect: Option<GridRect>) {
+        self.scroll_region = rect;
+        self.reset_print_cursor();
+    }
+
+    /// The scroll region, see [`Terminal::with_scroll_region`].
+    pub fn scroll_region(&self) -> Option<GridRect> {
+        self.scroll_region
+    }
+
+    /// Give lines written with [`Terminal::print`] alternating background
+    /// colors, making dense logs easier to read.
+    ///
+    /// Each line's background is set across the full width of the terminal,
+    /// or the scroll region if one is set, as soon as the line is started.
+    /// Lines that wrap keep the same color on every row. Strings with their
+    /// own background color will still override it.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let mut term = Terminal::new([30,10])
+    ///     .with_zebra_stripes(Color::BLACK, Color::srgb(0.1, 0.1, 0.15));
+    /// for i in 0..20 {
+    ///     term.println(format!("[{i}] The goblin misses"));
+    /// }
+    /// ```
+    pub fn with_zebra_stripes(mut self, even: Color, odd: Color) -> Self {
+        self.set_zebra_stripes(Some([even, odd]));
+        self
+    }
+
+    /// Set or remove the alternating line colors, see
+    /// [`Terminal::with_zebra_stripes`].
+    pub fn set_zebra_stripes(&mut self, colors: Option<[Color; 2]>) {
+        self.zebra_stripes = colors;
+    }
+
+    /// The alternating line colors, see [`Terminal::with_zebra_stripes`].
+    pub fn zebra_stripes(&self) -> Option<[Color; 2]> {
+        self.zebra_stripes
+    }
+
+    /// The bottom left and top right tiles of the scroll region, clipped to
+    /// the terminal.
+    fn scroll_bounds(&self) -> [IVec2; 2] {
+        let max = self.size.as_ivec2() - 1;
+        if let Some(rect) = self.scroll_region {
+            let [min, max] = [rect.min_i().max(IVec2::ZERO), rect.max_i().min(max)];
+            if min.cmple(max).all() {
+                return [min, max];
+            }
+        }
+        [IVec2::ZERO, max]
+    }
+
+    /// Convert a position to terminal local space (origin bottom left),
+    /// applying it's pivot or the terminal's default pivot.
+    pub fn local_point(&self, xy: impl GridPoint) -> IVec2 {
+        match self.pivot_of(xy) {
+            Some(pivot) => self.tiles.pivoted_point(xy.as_ivec2().pivot(pivot)),
+            None => xy.as_ivec2(),
+        }
+    }
+
+    /// The pivot of a position, or the terminal's default pivot if it has
+    /// none.
+    fn pivot_of(&self, xy: impl GridPoint) -> Option<Pivot> {
+        xy.get_pivot().or(self.default_pivot)
+    }
+
+    /// Convert a position relative to one pivot of the terminal into the same
+    /// tile relative to another pivot.
+    ///
+    /// Any pivot already on `xy` is ignored in favor of `from`. Converting to
+    /// [`Pivot::BottomLeft`] gives the terminal local position used by
+    /// functions like [`Terminal::get_tile`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let term = Terminal::new([11,11]);
+    /// let xy = term.convert_pivot([1,-1], Pivot::Center, Pivot::BottomLeft);
+    /// assert_eq!([6,4], xy.to_array());
+    /// ```
+    pub fn convert_pivot(&self, xy: impl GridPoint, from: Pivot, to: Pivot) -> IVec2 {
+        let local = self.tiles.pivoted_point(xy.as_ivec2().pivot(from));
+        let origin = self.tiles.pivoted_point(IVec2::ZERO.pivot(to));
+        // Each axis either points away from the origin or back towards it
+        let axis = self.tiles.pivoted_point(IVec2::ONE.pivot(to)) - origin;
+        (local - origin) * axis
+    }
+
     pub fn with_clear_tile(mut self, clear_tile: impl Into<Tile>) -> Self {
         self.clear_tile = clear_tile.into();
         self.clear();
@@ -143,12 +635,111 @@ impl Terminal {
         self.border.as_mut()
     }
 
+    /// Write a string along one edge of the terminal's border, for example a
+    /// status string on the bottom edge.
+    ///
+    /// The string's alignment determines where it's placed along the edge.
+    /// Does nothing if the terminal has no border.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut term = Terminal::new([10,5]).with_border(Border::single_line());
+    /// // Write right aligned text on the bottom border
+    /// term.put_border_string(Edge::Bottom, "3/7".aligned(1.0));
+    /// ```
+    pub fn put_border_string(&mut self, edge: Edge, string: impl Into<AlignedString>) {
+        if let Some(border) = self.border.as_mut() {
+            border.set_edge_string(edge, string);
+        }
+    }
+
     /// Resize the terminal.
     ///
-    /// This will clear the terminal.
+    /// This will clear the terminal's contents, including any per-tile
+    /// effects, and reset the print cursor. Every tile of the resized terminal
+    /// is set to the clear tile. The border and clear tile are kept, the
+    /// border will be rebuilt around the new size.
+    ///
+    /// Any zero dimensions will be clamped to a minimum size of 1 and a
+    /// warning logged, see [`Terminal::try_resize`] to reject them instead.
     pub fn resize(&mut self, size: impl Size2d) {
-        self.tiles = Grid::new(size);
-        self.size = size.as_uvec2();
+        let requested = size.as_uvec2();
+        if requested.cmpeq(UVec2::ZERO).any() {
+            warn!("Terminal resized to {requested}, zero dimensions will be clamped to 1");
+        }
+        let size = requested.max(UVec2::ONE);
+        self.tiles = Grid::new(size.to_array());
+        let clear_tile = self.clear_tile;
+        self.tiles.iter_mut().for_each(|t| *t = clear_tile);
+        self.size = size;
+        let len = self.tiles.slice().len();
+        self.sticky.resize(len);
+        self.glyph_offsets.resize(len);
+        self.overlays.resize(len);
+        self.tints.resize(len);
+        self.pulses.resize(len);
+        self.orientations.resize(len);
+        self.depths.resize(len);
+        self.links.clear();
+        self.reset_print_cursor();
+        self.mark_all_dirty();
+    }
+
+    /// Resize the terminal, returning an error if either dimension is zero.
+    ///
+    /// This will clear the terminal. If the size is invalid the terminal is
+    /// left unchanged.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// assert!(term.try_resize([0,5]).is_err());
+    /// assert!(term.try_resize([5,5]).is_ok());
+    /// ```
+    pub fn try_resize(&mut self, size: impl Size2d) -> Result<(), InvalidSizeError> {
+        let size = size.as_uvec2();
+        if size.cmpeq(UVec2::ZERO).any() {
+            return Err(InvalidSizeError { size });
+        }
+        self.resize(size.to_array());
+        Ok(())
+    }
+
+    /// Swap the tiles of this terminal with another terminal of the same size.
+    ///
+    /// This is a cheap way to "present" a back buffer without cloning. When
+    /// called through a `Mut<Terminal>` both terminals will be flagged as
+    /// changed.
+    ///
+    /// Per tile state goes with the tiles: sticky flags, overlays, glyph
+    /// offsets, tints, pulses, orientations and depths are swapped too. Links,
+    /// the print cursor, the border and other settings stay with each
+    /// terminal.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the two terminals are not the same size.
+    pub fn swap_with(&mut self, other: &mut Terminal) {
+        assert_eq!(
+            self.size, other.size,
+            "Error swapping terminal tiles, terminal sizes must match"
+        );
+        std::mem::swap(&mut self.tiles, &mut other.tiles);
+        std::mem::swap(&mut self.sticky, &mut other.sticky);
+        std::mem::swap(&mut self.overlays, &mut other.overlays);
+        std::mem::swap(&mut self.glyph_offsets, &mut other.glyph_offsets);
+        std::mem::swap(&mut self.tints, &mut other.tints);
+        std::mem::swap(&mut self.pulses, &mut other.pulses);
+        std::mem::swap(&mut self.orientations, &mut other.orientations);
+        std::mem::swap(&mut self.depths, &mut other.depths);
+        self.mark_all_dirty();
+        other.mark_all_dirty();
     }
 
     /// The width of the terminal, excluding the border.
@@ -231,305 +822,4081 @@ impl Terminal {
     /// // unaffected
     /// term.put_char([2,3], 'q');
     /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
     pub fn put_char(&mut self, xy: impl GridPoint, writer: impl TileFormatter) {
         let fmt = writer.format();
+        let xy = self.local_point(xy);
         fmt.draw(xy, self);
+        self.mark_dirty(xy, xy);
+        self.record_write(xy);
     }
 
-    /// Change the foreground or background color for a single tile in the terminal.
+    /// Insert a formatted character into the terminal if the position is in
+    /// bounds, otherwise do nothing.
+    ///
+    /// Returns true if the character was written. See [`Terminal::put_char`].
     ///
     /// # Example
     ///
     /// ```rust
-    /// use bevy::prelude::*;
     /// use bevy_ascii_terminal::prelude::*;
-    /// use bevy::color::palettes::basic::BLUE;
-    /// let mut term = Terminal::new([10,10]);
     ///
-    /// // Set the background color for the given tile to blue.
-    /// term.put_color([3,3], Color::Srgba(BLUE).bg());
+    /// let mut term = Terminal::new([10,10]);
+    /// assert!(term.try_put_char([2,3], 'a'));
+    /// assert!(!term.try_put_char([12,3], 'a'));
     /// ```
-    pub fn put_color(&mut self, xy: impl GridPoint, color: ColorFormat) {
-        let tile = self.get_tile_mut(xy);
-        match color {
-            ColorFormat::FgColor(col) => tile.fg_color = col,
-            ColorFormat::BgColor(col) => tile.bg_color = col,
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn try_put_char(&mut self, xy: impl GridPoint, writer: impl TileFormatter) -> bool {
+        let xy = self.local_point(xy);
+        if !self.in_bounds(xy) {
+            return false;
         }
+        writer.format().draw(xy, self);
+        self.mark_dirty(xy, xy);
+        self.record_write(xy);
+        true
     }
 
-    /// Insert a [Tile].
-    pub fn put_tile(&mut self, xy: impl GridPoint, tile: Tile) {
-        let t = self.get_tile_mut(xy);
-        *t = tile;
-    }
-
-    /// Write a formatted string to the terminal.
+    /// Apply a pre-built [`FormattedTile`] to a tile in the terminal.
     ///
-    /// The [`StringFormatter`] trait allows you to optionally specify a foreground
-    /// and/or background color for the string using the `fg` and `bg` functions.
-    /// If you don't specify a color then the existing colors in the terminal
-    /// will be unaffected.
+    /// Unlike [`Terminal::put_char`] the format is taken by reference, so a
+    /// single format can be stored and reused as a style for many writes.
     ///
     /// # Example
     ///
     /// ```rust
     /// use bevy_ascii_terminal::prelude::*;
     /// use bevy::prelude::Color;
-    /// use bevy::color::palettes::basic::{GREEN, BLUE};
+    /// use bevy::color::palettes::basic::{YELLOW, MAROON};
     ///
     /// let mut term = Terminal::new([10,10]);
-    /// // Write a blue "Hello" to the terminal
-    /// term.put_string([1,2], "Hello".fg(Color::Srgba(BLUE)));
-    /// // Write "Hello" with a green background
-    /// term.put_string([2,1], "Hello".bg(Color::Srgba(GREEN)));
+    /// let warning = '!'.fg(Color::Srgba(YELLOW)).bg(Color::Srgba(MAROON));
+    ///
+    /// term.put_formatted([1,1], &warning);
+    /// term.put_formatted([5,1], &warning);
     /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_formatted(&mut self, xy: impl GridPoint, fmt: &FormattedTile) {
+        let xy = self.local_point(xy);
+        fmt.draw(xy, self);
+        self.record_write(xy);
+    }
+
+    /// Replace a glyph with another whenever it's written to the terminal.
     ///
-    /// You can also specify a `Pivot` for the string via the `pivot` function.
-    /// This will align the string to the given pivot point. If the string
-    /// is multiple lines, it will be adjusted appropriately to fit the given
-    /// alignment.
+    /// Substitutions are applied to the glyphs of formatted characters and
+    /// strings as they're written, so text can be themed without changing the
+    /// code that writes it. Glyphs already in the terminal are unaffected.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use bevy_ascii_terminal::*;
-    /// use bevy::prelude::Color;
+    /// use bevy_ascii_terminal::prelude::*;
     ///
-    /// let mut term = Terminal::new([10,10]);
-    /// // Write a mutli-line string to the center of the terminal
-    /// term.put_string([0,0].pivot(Pivot::Center), "Hello\nHow are you?");
+    /// let mut term = Terminal::new([10,10]).with_glyph_substitution('#', '█');
+    /// term.put_string([0,0], "##.##");
+    /// assert_eq!("██.██", term.get_string([0,0], 5));
     /// ```
-    pub fn put_string<'a>(&mut self, xy: impl GridPoint, writer: impl StringFormatter<'a> + 'a) {
-        let pivot = if let Some(pivot) = xy.get_pivot() {
-            Vec2::from(pivot)
-        } else {
-            Vec2::ZERO
-        };
-        let origin = self.tiles.pivoted_point(xy);
-        let fmt = writer.formatted();
-        let string = &fmt.string;
-
-        let h = string.lines().count() as i32;
-        let y = (origin.y as f32 + (h - 1) as f32 * (1.0 - pivot.y)) as i32;
-
-        let bounds = self.tiles.bounds();
-
-        //println!("Origin {}, y {}", origin, y);
-
-        for (i, line) in string.lines().enumerate() {
-            let y = y - i as i32;
-            //println!("Origin {}, Line {}. Bounds {}", origin, y, bounds);
-            if y < bounds.min_i().y || y > bounds.max_i().y {
-                break;
-            }
-
-            let len = line.chars().count().min(self.width());
-            let x = origin.x - ((len - 1) as f32 * pivot.x) as i32;
-            //println!("Getting index for {}, {}", x, y);
-            let i = self.transform_lti([x, y]);
-            //println!("X {}, I {}", x, i);
-            let tiles = self.tiles.slice_mut()[i..].iter_mut().take(len);
-
-            //println!("Writing string at {:?}", [x,y]);
-
-            for (char, t) in line.chars().zip(tiles) {
-                t.glyph = char;
-                fmt.apply(t);
-            }
-        }
+    pub fn with_glyph_substitution(mut self, from: char, to: char) -> Self {
+        self.add_glyph_substitution(from, to);
+        self
     }
 
-    /// Clear a range of characters to the terminal's `clear_tile`.
-    pub fn clear_string(&mut self, xy: impl GridPoint, len: usize) {
-        let i = self.transform_lti(xy);
-        for t in self.tiles.slice_mut()[i..].iter_mut().take(len) {
-            *t = self.clear_tile;
-        }
+    /// Replace a glyph with another whenever it's written to the terminal.
+    /// See [`Terminal::with_glyph_substitution`].
+    pub fn add_glyph_substitution(&mut self, from: char, to: char) {
+        self.glyph_substitutions.insert(from, to);
     }
 
-    /// Retrieve the char from a tile.
-    pub fn get_char(&self, xy: impl GridPoint) -> char {
-        self.get_tile(xy).glyph
+    /// Remove a glyph substitution, see [`Terminal::with_glyph_substitution`].
+    pub fn remove_glyph_substitution(&mut self, from: char) {
+        self.glyph_substitutions.remove(&from);
     }
 
-    /// Retrieve a string from the terminal.
-    pub fn get_string(&self, xy: impl GridPoint, len: usize) -> String {
-        let i = self.transform_lti(xy);
-        let iter = self.tiles.slice()[i..].iter().take(len).map(|t| t.glyph);
+    /// Remove all glyph substitutions.
+    pub fn clear_glyph_substitutions(&mut self) {
+        self.glyph_substitutions.clear();
+    }
 
-        String::from_iter(iter)
+    /// The glyph written in place of the given glyph, see
+    /// [`Terminal::with_glyph_substitution`].
+    pub fn substitute_glyph(&self, glyph: char) -> char {
+        self.glyph_substitutions
+            .get(&glyph)
+            .copied()
+            .unwrap_or(glyph)
     }
 
-    #[inline]
-    /// Retrieve an immutable reference to a tile in the terminal.
-    pub fn get_tile(&self, xy: impl GridPoint) -> &Tile {
-        &self.tiles[self.transform_lti(xy)]
+    /// Write a named icon to the terminal. Icons are registered on the
+    /// terminal's uv mapping with [`UvMapping::insert_icon`].
+    ///
+    /// If no icon with the given name exists then [`Terminal::FALLBACK_GLYPH`]
+    /// will be written instead. Tile colors are unaffected. Otherwise this
+    /// behaves the same as [`Terminal::put_char`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy_ascii_terminal::UvMapping;
+    ///
+    /// let mapping = UvMapping::default()
+    ///     .with_glyph('\u{E000}', [4, 15], [16, 16])
+    ///     .with_icon(":skull:", '\u{E000}');
+    /// let mut term = Terminal::new([10,10]);
+    /// term.put_icon([1,1], ":skull:", &mapping);
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_icon(&mut self, xy: impl GridPoint, name: &str, mapping: &UvMapping) {
+        let glyph = mapping.icon_glyph(name).unwrap_or(Self::FALLBACK_GLYPH);
+        self.put_char(xy, glyph);
     }
 
-    #[inline]
-    /// Retrieve a mutable reference to a tile in the terminal.
+    /// Write a formatted string to the terminal, replacing any icon names from
+    /// the uv mapping in square brackets with their icon glyph.
+    ///
+    /// Each icon takes up a single tile and uses the same formatting as the
+    /// rest of the string. Bracketed names that haven't been registered with
+    /// [`UvMapping::insert_icon`] are written as is. Otherwise this behaves
+    /// the same as [`Terminal::put_string`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy_ascii_terminal::UvMapping;
+    ///
+    /// let mapping = UvMapping::default().with_icon("heart", '♥');
+    /// let mut term = Terminal::new([20,5]);
+    /// term.put_rich([0,0], "HP [heart] 10", &mapping);
+    /// assert_eq!("HP ♥ 10", term.get_string([0,0], 7));
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_rich<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+        mapping: &UvMapping,
+    ) {
+        let mut fmt = writer.formatted();
+        fmt.string = replace_icons(&fmt.string, mapping).into();
+        self.put_string(xy, fmt);
+    }
+
+    /// Change the foreground or background color for a single tile in the terminal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::color::palettes::basic::BLUE;
+    /// let mut term = Terminal::new([10,10]);
+    ///
+    /// // Set the background color for the given tile to blue.
+    /// term.put_color([3,3], Color::Srgba(BLUE).bg());
+    /// ```
+    pub fn put_color(&mut self, xy: impl GridPoint, color: ColorFormat) {
+        let xy = self.local_point(xy);
+        let tile = self.get_tile_mut(xy);
+        match color {
+            ColorFormat::FgColor(col) => tile.fg_color = col,
+            ColorFormat::BgColor(col) => tile.bg_color = col,
+        }
+    }
+
+    /// Set the background color of every tile in the terminal. Glyphs and
+    /// foreground colors are unaffected.
+    pub fn fill_bg(&mut self, color: Color) {
+        self.tiles.iter_mut().for_each(|t| t.bg_color = color);
+        self.mark_all_dirty();
+    }
+
+    /// Set the foreground color of every tile in the terminal. Glyphs and
+    /// background colors are unaffected.
+    pub fn fill_fg(&mut self, color: Color) {
+        self.tiles.iter_mut().for_each(|t| t.fg_color = color);
+        self.mark_all_dirty();
+    }
+
+    /// Set the background color of every tile that doesn't match the
+    /// terminal's `clear_tile`, leaving empty tiles untouched.
+    ///
+    /// Tiles are compared the same way as [`Terminal::is_clear`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.put_string([1,1], "Menu");
+    /// // Only the text gets the new background
+    /// term.recolor_content_bg(Color::srgb(0.1, 0.1, 0.4));
+    /// ```
+    pub fn recolor_content_bg(&mut self, color: Color) {
+        let clear = self.clear_tile;
+        self.tiles
+            .iter_mut()
+            .filter(|t| !t.approx_eq(&clear))
+            .for_each(|t| t.bg_color = color);
+        self.mark_all_dirty();
+    }
+
+    /// Insert a [Tile].
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_tile(&mut self, xy: impl GridPoint, tile: Tile) {
+        let xy = self.local_point(xy);
+        *self.get_tile_mut(xy) = tile;
+        self.mark_dirty(xy, xy);
+        self.record_write(xy);
+    }
+
+    /// Insert a [Tile] if the position is in bounds, otherwise do nothing.
+    ///
+    /// Returns true if the tile was written. See [`Terminal::put_tile`].
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn try_put_tile(&mut self, xy: impl GridPoint, tile: Tile) -> bool {
+        let xy = self.local_point(xy);
+        match self.try_get_tile_mut(xy) {
+            Some(t) => {
+                *t = tile;
+                self.mark_dirty(xy, xy);
+                self.record_write(xy);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Insert a tile with the given glyph and colors.
+    ///
+    /// The same as [`Terminal::put_tile`], without building the [`Tile`]
+    /// first. Useful in tight loops where the formatting traits are overkill.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// for x in 0..10 {
+    ///     term.put_tile_parts([x,0], '=', Color::WHITE, Color::BLACK);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_tile_parts(&mut self, xy: impl GridPoint, glyph: char, fg: Color, bg: Color) {
+        self.put_tile(
+            xy,
+            Tile {
+                glyph,
+                fg_color: fg,
+                bg_color: bg,
+            },
+        );
+    }
+
+    /// Write a formatted string to the terminal.
+    ///
+    /// The [`StringFormatter`] trait allows you to optionally specify a foreground
+    /// and/or background color for the string using the `fg` and `bg` functions.
+    /// If you don't specify a color then the existing colors in the terminal
+    /// will be unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    /// use bevy::color::palettes::basic::{GREEN, BLUE};
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// // Write a blue "Hello" to the terminal
+    /// term.put_string([1,2], "Hello".fg(Color::Srgba(BLUE)));
+    /// // Write "Hello" with a green background
+    /// term.put_string([2,1], "Hello".bg(Color::Srgba(GREEN)));
+    /// ```
+    ///
+    /// You can also specify a `Pivot` for the string via the `pivot` function.
+    /// This will align the string to the given pivot point. If the string
+    /// is multiple lines, it will be adjusted appropriately to fit the given
+    /// alignment.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// // Write a mutli-line string to the center of the terminal
+    /// term.put_string([0,0].pivot(Pivot::Center), "Hello\nHow are you?");
+    /// ```
+    ///
+    /// Lines that run past the edge of the terminal are cut off unless the
+    /// string is [`wrapped`](StringFormatter::wrapped), in which case it's
+    /// broken into lines at whitespace, or set to
+    /// [`continue_on_overflow`](StringFormatter::continue_on_overflow), in
+    /// which case the rest of the line continues on the next row. Returns the
+    /// number of rows the string takes up, including any added by wrapping.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// let rows = term.put_string([0,0].pivot(Pivot::TopLeft), "A long line of text".wrapped());
+    /// term.put_string([0, rows as i32].pivot(Pivot::TopLeft), "Next");
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+    ) -> usize {
+        self.write_string(xy, writer, |_| true, |_, _, _| {}).1
+    }
+
+    /// Write a formatted string to the terminal, aligned to the given pivot.
+    ///
+    /// This is the same as calling [`Terminal::put_string`] with `xy.pivot(pivot)`.
+    /// Any pivot already attached to `xy` is replaced.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::math::IVec2;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// let xy = IVec2::new(0, 0);
+    /// term.put_string_pivoted(xy, Pivot::TopRight, "Hello");
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_pivoted<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        pivot: Pivot,
+        writer: impl StringFormatter<'a> + 'a,
+    ) -> usize {
+        self.put_string(xy.as_ivec2().pivot(pivot), writer)
+    }
+
+    /// Write a formatted right to left string to the terminal.
+    ///
+    /// Each line is written starting from it's right edge and advancing
+    /// leftward, so the first character of each line is the rightmost.
+    /// Multiple lines stack vertically the same as with
+    /// [`Terminal::put_string`].
+    ///
+    /// If `xy` has no pivot it's the position of the first character of the
+    /// bottom line, with every line's right edge aligned to it. Otherwise
+    /// lines are aligned to the pivot the same as [`Terminal::put_string`],
+    /// so a right pivot aligns the right edge of every line. Lines are
+    /// clamped to the terminal width and any characters outside the terminal
+    /// are clipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([20,10]);
+    /// term.put_string_rtl([0,0].pivot(Pivot::TopRight), "שלום\nעולם");
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_rtl<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+    ) {
+        let pivot = self
+            .pivot_of(xy)
+            .map(Vec2::from)
+            .unwrap_or(Vec2::new(1.0, 0.0));
+        let origin = self.local_point(xy);
+        let fmt = writer.formatted();
+
+        let h = fmt.string.lines().count() as i32;
+        let top = (origin.y as f32 + (h - 1) as f32 * (1.0 - pivot.y)) as i32;
+        for (i, line) in fmt.string.lines().enumerate() {
+            let y = top - i as i32;
+            let len = line.chars().count().min(self.width());
+            let left = origin.x - (len.saturating_sub(1) as f32 * pivot.x) as i32;
+            for (j, ch) in line.chars().take(len).enumerate() {
+                let xy = IVec2::new(left + (len - 1 - j) as i32, y);
+                if !self.in_bounds(xy) {
+                    continue;
+                }
+                self.put_string_char(xy, ch, &fmt);
+            }
+        }
+    }
+
+    /// Write a formatted string along a path, placing each character at the
+    /// next position in `path`.
+    ///
+    /// Writing stops when either the path or the string ends. Line returns
+    /// are skipped and positions outside the terminal are clipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::math::IVec2;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// // Write diagonally up and to the right
+    /// let path: Vec<_> = (0..5).map(|i| IVec2::new(i, i)).collect();
+    /// term.put_string_path(&path, "River");
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_path<'a>(&mut self, path: &[IVec2], writer: impl StringFormatter<'a> + 'a) {
+        let fmt = writer.formatted();
+        let chars = fmt.string.chars().filter(|c| *c != '\n');
+        for (&xy, ch) in path.iter().zip(chars) {
+            if !self.in_bounds(xy) {
+                continue;
+            }
+            self.put_string_char(xy, ch, &fmt);
+        }
+    }
+
+    /// Write a single character of a formatted string, applying glyph
+    /// substitution and the string's line decoration. The position must be in
+    /// bounds.
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    fn put_string_char(&mut self, xy: IVec2, ch: char, fmt: &FormattedString) {
+        let glyph = self.substitute_glyph(ch);
+        let t = self.get_tile_mut(xy);
+        t.glyph = glyph;
+        fmt.apply(t);
+        if let Some(line) = fmt.decoration_glyph() {
+            let fg = t.fg_color;
+            self.put_overlay(xy, line, fg);
+        }
+        self.record_write(xy);
+    }
+
+    /// Write a formatted string to the terminal, skipping any positions for
+    /// which `mask` returns false.
+    ///
+    /// The mask receives positions in terminal local space. This can be used
+    /// to clip text to arbitrary shapes, such as a circle or a set of visible
+    /// tiles.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::math::IVec2;
+    ///
+    /// let mut term = Terminal::new([20,10]);
+    /// let center = IVec2::new(10, 5);
+    /// // Only write inside a circle with a radius of 3
+    /// term.put_string_masked([0,5], "Spotlight on this text", |p| {
+    ///     (p - center).length_squared() <= 3 * 3
+    /// });
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_masked<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+        mask: impl Fn(IVec2) -> bool,
+    ) {
+        self.write_string(xy, writer, mask, |_, _, _| {});
+    }
+
+    /// Write a formatted string to the terminal and return the position just
+    /// past the last written character.
+    ///
+    /// This is where the next character would go if the string were one
+    /// character longer, for example to draw a cursor after text that's being
+    /// revealed over time. The returned position may be outside the terminal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::math::IVec2;
+    ///
+    /// let mut term = Terminal::new([20,10]);
+    /// let cursor = term.put_string_cursor([1,5], "Hello\nWorld!");
+    /// assert_eq!(IVec2::new(7, 4), cursor);
+    /// term.put_char(cursor, '_');
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_cursor<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+    ) -> IVec2 {
+        self.write_string(xy, writer, |_| true, |_, _, _| {}).0
+    }
+
+    /// Write a formatted string to the terminal, cutting off any part of a
+    /// line that would run past the left or right edge of the terminal.
+    ///
+    /// Returns true if any characters were cut off, so the caller can switch
+    /// to a shorter string. Lines that fall outside the top or bottom of the
+    /// terminal are not counted.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([12,1]);
+    /// let hp = 1250;
+    /// if term.put_string_truncated([0,0], format!("Health: {}", hp)) {
+    ///     term.clear();
+    ///     term.put_string([0,0], format!("HP: {}", hp));
+    /// }
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_truncated<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+    ) -> bool {
+        let width = self.width() as i32;
+        let pivot_x = self.pivot_of(xy).map(|p| Vec2::from(p).x).unwrap_or(0.0);
+        let origin = self.local_point(xy);
+        let overflow = writer.string().lines().any(|line| {
+            let count = line.chars().count();
+            let len = count.min(self.width());
+            let x = origin.x - (len.saturating_sub(1) as f32 * pivot_x) as i32;
+            count > 0 && (count > len || x < 0 || x + len as i32 > width)
+        });
+        self.write_string(xy, writer, |p| p.x >= 0 && p.x < width, |_, _, _| {});
+        overflow
+    }
+
+    /// Write a formatted string to the terminal, word wrapped to fit between
+    /// `xy` and the right edge of the terminal, returning any text that
+    /// didn't fit.
+    ///
+    /// `xy` is the start of the first line and following lines are written
+    /// below it until the bottom of the terminal is reached. The remaining
+    /// text keeps the formatting of the original string, so it can be written
+    /// to the next page with the same colors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let mut page = Terminal::new([10,3]);
+    /// let text = "It was a dark and stormy night, the rain fell in torrents";
+    /// let mut rest = page.put_string_paged([0,2], text.fg(Color::srgb(0.6, 0.6, 1.0)));
+    /// while let Some(text) = rest {
+    ///     // Wait for the player to turn the page...
+    ///     page.clear();
+    ///     rest = page.put_string_paged([0,2], text);
+    /// }
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_paged<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+    ) -> Option<FormattedString<'a>> {
+        let origin = self.local_point(xy);
+        let fmt = writer.formatted();
+        let width = (self.width() as i32 - origin.x).max(0) as usize;
+        if width == 0 || origin.y < 0 {
+            return (!fmt.string.is_empty()).then_some(fmt);
+        }
+
+        let lines = wrap_words(&fmt.string, width);
+        let fit = lines.len().min(origin.y as usize + 1);
+        for (i, line) in lines[..fit].iter().enumerate() {
+            let line = fmt.with_string(*line);
+            // The origin is already in local space, so ignore any default pivot
+            let xy = IVec2::new(origin.x, origin.y - i as i32).pivot(Pivot::BottomLeft);
+            self.put_string(xy, line);
+        }
+
+        // Lines are slices of the original string
+        let start = lines.get(fit)?.as_ptr() as usize - fmt.string.as_ptr() as usize;
+        let string = match &fmt.string {
+            Cow::Borrowed(s) => {
+                let s: &'a str = *s;
+                Cow::Borrowed(&s[start..])
+            }
+            Cow::Owned(s) => Cow::Owned(s[start..].to_string()),
+        };
+        Some(fmt.with_string(string))
+    }
+
+    /// Write a formatted string to the terminal, choosing the foreground color
+    /// of each character with the given callback.
+    ///
+    /// The callback receives the index of the character within the string
+    /// (line returns are not counted) and the character itself. Returning
+    /// `None` leaves the existing color for that tile unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    /// use bevy::color::palettes::basic::YELLOW;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// // Draw all digits in yellow
+    /// term.put_string_with([0,0], "HP: 25", |_, c| {
+    ///     c.is_ascii_digit().then_some(Color::Srgba(YELLOW))
+    /// });
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_with<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+        fg_color: impl Fn(usize, char) -> Option<Color>,
+    ) {
+        self.write_string(
+            xy,
+            writer,
+            |_| true,
+            |i, ch, t| {
+                if let Some(col) = fg_color(i, ch) {
+                    t.fg_color = col;
+                }
+            },
+        );
+    }
+
+    /// Write a formatted string to the terminal, choosing a black or white
+    /// foreground color for each character based on the luminance of the
+    /// background it's written over.
+    ///
+    /// Any background color set on the string is applied before the foreground
+    /// is chosen. Any foreground color set on the string is ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    /// use bevy::color::palettes::basic::YELLOW;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.fill_bg(Color::Srgba(YELLOW));
+    /// // Text will be black over the yellow background
+    /// term.put_string_auto_contrast([1,1], "Hello");
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_auto_contrast<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+    ) {
+        self.write_string(
+            xy,
+            writer,
+            |_| true,
+            |_, _, t| t.fg_color = contrast_color(t.bg_color),
+        );
+    }
+
+    /// Write a formatted string to the terminal, setting the background color
+    /// of every occurrence of `query` within the string to `highlight`.
+    ///
+    /// Matches are case sensitive and don't span line returns. Overlapping
+    /// matches are all highlighted. An empty query highlights nothing.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    /// use bevy::color::palettes::basic::YELLOW;
+    ///
+    /// let mut term = Terminal::new([30,10]);
+    /// // Highlight both occurrences of "sword"
+    /// term.put_string_highlighted([1,1], "A sword. Another sword.", "sword", Color::Srgba(YELLOW));
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_highlighted<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+        query: &str,
+        highlight: Color,
+    ) {
+        let matches = match_indices(writer.string(), query);
+        self.write_string(
+            xy,
+            writer,
+            |_| true,
+            |i, _, t| {
+                if matches.get(i).copied().unwrap_or(false) {
+                    t.bg_color = highlight;
+                }
+            },
+        );
+    }
+
+    /// Write a formatted string to the terminal over a repeating background
+    /// pattern.
+    ///
+    /// The rectangle covered by the string (the width of its longest line by
+    /// the number of lines) is first filled with `pattern`, then the text is
+    /// written on top. Whitespace in the string leaves the pattern visible.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    /// use bevy::color::palettes::basic::GRAY;
+    ///
+    /// let mut term = Terminal::new([20,10]);
+    /// term.put_string_patterned([1,1], "Score\nHi", '░'.fg(Color::Srgba(GRAY)));
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_patterned<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+        pattern: impl TileFormatter,
+    ) {
+        let pattern = pattern.format();
+        let footprint = self.string_footprint(xy, writer.string());
+        let (min, max) = (footprint.min_i(), footprint.max_i());
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                if self.in_bounds([x, y]) {
+                    pattern.apply(self.get_tile_mut([x, y]));
+                }
+            }
+        }
+        self.write_string(
+            xy,
+            writer,
+            |_| true,
+            |_, ch, t| {
+                if ch.is_whitespace() {
+                    pattern.apply(t);
+                }
+            },
+        );
+    }
+
+    /// Write a formatted string to the terminal and register the area it covers
+    /// as a clickable link with the given id.
+    ///
+    /// Clicking inside the link on a terminal with a [`ToWorld`](crate::ToWorld)
+    /// component will send a [`TerminalLinkClicked`](crate::TerminalLinkClicked)
+    /// event. Writing a link with an id that's already registered will replace
+    /// the previous link's area.
+    ///
+    /// Links are not removed when the terminal is cleared, see
+    /// [`Terminal::clear_links`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([20,10]);
+    /// term.put_link([1,5], "New Game", "new_game");
+    /// term.put_link([1,4], "Quit", "quit");
+    /// assert_eq!(Some("quit"), term.link_at([3,4]));
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_link<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+        id: impl Into<String>,
+    ) {
+        let id = id.into();
+        let fmt = writer.formatted();
+        let rect = self.string_footprint(xy, &fmt);
+        self.put_string(xy, fmt);
+        match self.links.iter_mut().find(|(link, _)| *link == id) {
+            Some(link) => link.1 = rect,
+            None => self.links.push((id, rect)),
+        }
+    }
+
+    /// The id of the most recently registered link at the given position, if
+    /// any.
+    ///
+    /// See [`Terminal::put_link`].
+    pub fn link_at(&self, xy: impl GridPoint) -> Option<&str> {
+        let xy = xy.as_ivec2();
+        self.links
+            .iter()
+            .rev()
+            .find(|(_, rect)| xy.cmpge(rect.min_i()).all() && xy.cmple(rect.max_i()).all())
+            .map(|(id, _)| id.as_str())
+    }
+
+    /// Remove a registered link. Returns false if no link with the given id
+    /// was registered.
+    pub fn remove_link(&mut self, id: &str) -> bool {
+        let len = self.links.len();
+        self.links.retain(|(link, _)| link != id);
+        self.links.len() != len
+    }
+
+    /// Remove all registered links.
+    pub fn clear_links(&mut self) {
+        self.links.clear();
+    }
+
+    /// Measure the width and height a string would occupy if it were written
+    /// to the terminal, without writing it.
+    ///
+    /// If `wrap_width` is given the string will be measured as if it were word
+    /// wrapped to that width. The result is not clipped to the terminal's size.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::math::UVec2;
+    ///
+    /// let term = Terminal::new([5,5]);
+    /// assert_eq!(UVec2::new(12, 2), term.measure_string("Hello\nHow are you?", None));
+    /// ```
+    pub fn measure_string<'a>(
+        &self,
+        writer: impl StringFormatter<'a> + 'a,
+        wrap_width: Option<usize>,
+    ) -> UVec2 {
+        let string = writer.string();
+        let lines = match wrap_width {
+            Some(w) => wrap_words(string, w),
+            None => string.lines().collect(),
+        };
+        let w = lines.iter().map(|l| l.chars().count()).max().unwrap_or(0);
+        UVec2::new(w as u32, lines.len() as u32)
+    }
+
+    /// The rectangle covered by a string written with [`Terminal::put_string`]
+    /// at the given position, after it's been wrapped or split into rows.
+    /// Lines are clamped to the terminal width.
+    fn string_footprint(&self, xy: impl GridPoint, fmt: &FormattedString) -> GridRect {
+        let pivot = self.pivot_of(xy).map(Vec2::from).unwrap_or(Vec2::ZERO);
+        let origin = self.local_point(xy);
+
+        let string = self.layout_string(xy, fmt);
+        let size = self
+            .measure_string(string.as_ref(), None)
+            .max(UVec2::ONE)
+            .as_ivec2();
+        let h = size.y;
+        let w = size.x.min(self.width() as i32);
+        let top = (origin.y as f32 + (h - 1) as f32 * (1.0 - pivot.y)) as i32;
+        let left = origin.x - ((w - 1) as f32 * pivot.x) as i32;
+        GridRect::from_bl([left, top - (h - 1)], [w, h])
+    }
+
+    /// Write a formatted string to the terminal, calling `per_tile` on each
+    /// written tile with the character's index in the string.
+    ///
+    /// Tiles for which `mask` returns false will be skipped. Returns the
+    /// position just past the last character of the string, see
+    /// [`Terminal::put_string_cursor`], and the number of rows the string
+    /// takes up, see [`Terminal::put_string`].
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub(crate) fn write_string<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+        mask: impl Fn(IVec2) -> bool,
+        mut per_tile: impl FnMut(usize, char, &mut Tile),
+    ) -> (IVec2, usize) {
+        let pivot = if let Some(pivot) = self.pivot_of(xy) {
+            Vec2::from(pivot)
+        } else {
+            Vec2::ZERO
+        };
+        let origin = self.local_point(xy);
+        let fmt = writer.formatted();
+        let string = self.layout_string(xy, &fmt);
+        let decoration = fmt.decoration_glyph();
+        let pulse = fmt.is_pulsing();
+
+        let h = string.lines().count() as i32;
+        let y = (origin.y as f32 + (h - 1) as f32 * (1.0 - pivot.y)) as i32;
+
+        let bounds = self.tiles.bounds();
+        let tile_count = self.tiles.slice().len();
+
+        let mut next = IVec2::new(origin.x, origin.y);
+        let mut char_index = 0;
+        for (i, line) in string.lines().enumerate() {
+            let y = y - i as i32;
+            if y < bounds.min_i().y || y > bounds.max_i().y {
+                break;
+            }
+
+            let len = line.chars().count().min(self.width());
+            let x = origin.x - (len.saturating_sub(1) as f32 * pivot.x) as i32;
+            next = IVec2::new(x + len as i32, y);
+            let start = self.transform_lti([x, y]);
+
+            for (j, char) in line.chars().take(len).enumerate() {
+                let i = start + j;
+                if i >= tile_count {
+                    break;
+                }
+                if !mask(IVec2::new(x + j as i32, y)) {
+                    continue;
+                }
+                let glyph = self.substitute_glyph(char);
+                let xy = self.transform_itl(i);
+                self.mark_dirty(xy, xy);
+                let t = &mut self.tiles[i];
+                t.glyph = glyph;
+                fmt.apply(t);
+                per_tile(char_index + j, char, t);
+                if let Some(line) = decoration {
+                    let fg = t.fg_color;
+                    self.put_overlay([x + j as i32, y], line, fg);
+                }
+                if pulse {
+                    self.set_pulse([x + j as i32, y], true);
+                }
+                self.record_write(IVec2::new(x + j as i32, y));
+            }
+            char_index += line.chars().count();
+        }
+
+        // A trailing line return moves the cursor to the start of the next line
+        if string.ends_with('\n') {
+            next = IVec2::new(origin.x, next.y - 1);
+        }
+        (next, h as usize)
+    }
+
+    /// The text of a formatted string as it will be written at `xy`, word
+    /// wrapped if the string is [`wrapped`](StringFormatter::wrapped) or split
+    /// into rows if it [continues on overflow](StringFormatter::continue_on_overflow).
+    fn layout_string<'s>(&self, xy: impl GridPoint, fmt: &'s FormattedString) -> Cow<'s, str> {
+        if fmt.is_wrapped() {
+            return Cow::Owned(wrap_words(&fmt.string, self.wrap_width(xy)).join("\n"));
+        }
+        let width = self.wrap_width(xy);
+        if !fmt.continues_on_overflow() || fmt.string.lines().all(|l| l.chars().count() <= width) {
+            return Cow::Borrowed(&fmt.string);
+        }
+        let mut rows = Vec::new();
+        for line in fmt.string.lines() {
+            let chars: Vec<char> = line.chars().collect();
+            if chars.is_empty() {
+                rows.push(String::new());
+            }
+            rows.extend(chars.chunks(width).map(|c| c.iter().collect::<String>()));
+        }
+        let mut string = rows.join("\n");
+        if fmt.string.ends_with('\n') {
+            string.push('\n');
+        }
+        Cow::Owned(string)
+    }
+
+    /// The width a [`wrapped`](StringFormatter::wrapped) string written at
+    /// `xy` is wrapped to: the longest line that stays inside the terminal
+    /// once it's aligned to the pivot.
+    fn wrap_width(&self, xy: impl GridPoint) -> usize {
+        let pivot_x = self.pivot_of(xy).map(|p| Vec2::from(p).x).unwrap_or(0.0);
+        let x = self.local_point(xy).x;
+        let right = self.width() as i32 - 1;
+        let mut width = self.width() as i32;
+        if pivot_x > 0.0 {
+            width = width.min((x as f32 / pivot_x) as i32 + 1);
+        }
+        if pivot_x < 1.0 {
+            width = width.min(((right - x) as f32 / (1.0 - pivot_x)) as i32 + 1);
+        }
+        width.max(1) as usize
+    }
+
+    /// The character index in `string` where each line begins when it's
+    /// written at `xy` as a [`wrapped`](StringFormatter::wrapped) string.
+    ///
+    /// Indices count characters, not bytes, and the first line always starts
+    /// at 0. The nth index is the start of the nth row written by
+    /// [`Terminal::put_string`], which can be used to map a caret position in
+    /// the string to a tile.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let term = Terminal::new([10,5]);
+    /// let starts = term.wrapped_line_starts([0,0], "Hello there world");
+    /// assert_eq!(vec![0, 6, 12], starts);
+    /// ```
+    pub fn wrapped_line_starts(&self, xy: impl GridPoint, string: &str) -> Vec<usize> {
+        let base = string.as_ptr() as usize;
+        let starts: Vec<_> = wrap_words(string, self.wrap_width(xy))
+            .into_iter()
+            .map(|line| string[..line.as_ptr() as usize - base].chars().count())
+            .collect();
+        if starts.is_empty() {
+            return vec![0];
+        }
+        starts
+    }
+
+    /// Write a formatted string at the print cursor, moving the cursor to the
+    /// end of the string.
+    ///
+    /// The print cursor starts at the top left of the terminal and behaves
+    /// like a console: lines wrap when they reach the right edge, line returns
+    /// move the cursor to the start of the next line and the terminal scrolls
+    /// up when the cursor moves past the bottom line. The print cursor is
+    /// separate from any position passed to the other write functions and
+    /// is reset when the terminal is cleared or resized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([20,5]);
+    /// term.print("Loading... ");
+    /// term.println("done!");
+    /// term.println("Welcome to the dungeon");
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn print<'a>(&mut self, writer: impl StringFormatter<'a> + 'a) {
+        let fmt = writer.formatted();
+        if self.print_cursor.x as i32 == self.scroll_bounds()[0].x {
+            self.stripe_print_row();
+        }
+        for ch in fmt.string.chars() {
+            if ch == '\n' {
+                self.print_line += 1;
+                self.print_newline();
+                continue;
+            }
+            if self.print_cursor.x as i32 > self.scroll_bounds()[1].x {
+                self.print_newline();
+            }
+            let xy = self.print_cursor();
+            self.put_string_char(xy, ch, &fmt);
+            self.print_cursor.x += 1;
+        }
+    }
+
+    /// Write a formatted string at the print cursor followed by a line
+    /// return. See [`Terminal::print`].
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn println<'a>(&mut self, writer: impl StringFormatter<'a> + 'a) {
+        self.print(writer);
+        self.print_line += 1;
+        self.print_newline();
+    }
+
+    /// The position the next character written by [`Terminal::print`] will
+    /// be written to. The x position is equal to the terminal width if the
+    /// current line is full.
+    pub fn print_cursor(&self) -> IVec2 {
+        let y = self.height() as i32 - 1 - self.print_cursor.y as i32;
+        IVec2::new(self.print_cursor.x as i32, y)
+    }
+
+    /// Move the print cursor, see [`Terminal::print`]. The position is clamped
+    /// to the terminal bounds.
+    pub fn set_print_cursor(&mut self, xy: impl GridPoint) {
+        let xy = xy.as_ivec2().clamp(IVec2::ZERO, self.size.as_ivec2() - 1);
+        let line = self.height() as i32 - 1 - xy.y;
+        self.print_cursor = UVec2::new(xy.x as u32, line as u32);
+    }
+
+    /// Move the print cursor to the start of the next line, scrolling the
+    /// terminal if it's on the bottom line.
+    fn print_newline(&mut self) {
+        let [min, _] = self.scroll_bounds();
+        let y = self.print_cursor().y;
+        self.print_cursor.x = min.x as u32;
+        if y > min.y {
+            self.print_cursor.y += 1;
+        } else {
+            self.scroll_up(1);
+        }
+        self.stripe_print_row();
+    }
+
+    /// Set the background of the print cursor's row to the current line's
+    /// zebra stripe color, if any.
+    fn stripe_print_row(&mut self) {
+        let Some(colors) = self.zebra_stripes else {
+            return;
+        };
+        let color = colors[self.print_line % 2];
+        let [min, max] = self.scroll_bounds();
+        let y = self.print_cursor().y;
+        for x in min.x..=max.x {
+            self.get_tile_mut([x, y]).bg_color = color;
+        }
+        self.mark_dirty(IVec2::new(min.x, y), IVec2::new(max.x, y));
+    }
+
+    /// Where the print cursor starts: the top left of the scroll region.
+    fn print_home(&self) -> UVec2 {
+        let [min, max] = self.scroll_bounds();
+        UVec2::new(min.x as u32, (self.height() as i32 - 1 - max.y) as u32)
+    }
+
+    /// Move the print cursor back to where it starts, the top left of the
+    /// terminal or the scroll region if one is set. See [`Terminal::print`].
+    pub fn reset_print_cursor(&mut self) {
+        self.print_cursor = self.print_home();
+        self.print_line = 0;
+    }
+
+    /// Write a formatted string to the terminal, flowing the words around any
+    /// existing content.
+    ///
+    /// `xy` is the start of the first line. Each word is written to the first
+    /// run of clear tiles (see [`Terminal::is_clear`]) on the current line
+    /// that's long enough to hold it, otherwise it moves to the next line
+    /// down, which starts again from the x position of `xy`. Existing content
+    /// is never overwritten. Line returns in the string always start a new
+    /// line.
+    ///
+    /// Returns the number of words that didn't fit before the bottom of the
+    /// terminal was reached. A word too long to fit in any free space stops
+    /// the flow.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([30,10]);
+    /// // An image placeholder in the middle of the page
+    /// for y in 3..7 {
+    ///     term.put_string([10, y], "########");
+    /// }
+    /// term.put_string_flowed([0,9], "Text flows around the box like the columns of a magazine");
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn put_string_flowed<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        writer: impl StringFormatter<'a> + 'a,
+    ) -> usize {
+        let origin = self.local_point(xy);
+        let fmt = writer.formatted();
+
+        // Words, with `None` marking a line return
+        let mut tokens = Vec::new();
+        for (i, line) in fmt.string.lines().enumerate() {
+            if i > 0 {
+                tokens.push(None);
+            }
+            tokens.extend(line.split(is_break).filter(|w| !w.is_empty()).map(Some));
+        }
+
+        let left = origin.x.max(0);
+        let [mut x, mut y] = [left, origin.y];
+        let mut gap = 0;
+        for (i, token) in tokens.iter().enumerate() {
+            let Some(word) = token else {
+                [x, y, gap] = [left, y - 1, 0];
+                continue;
+            };
+            let len = word.chars().count() as i32;
+            let start = loop {
+                if y < 0 {
+                    return tokens[i..].iter().flatten().count();
+                }
+                if let Some(start) = self.find_free_run(x + gap, y, len) {
+                    break start;
+                }
+                [x, y, gap] = [left, y - 1, 0];
+            };
+            for (j, ch) in word.chars().enumerate() {
+                self.put_string_char(IVec2::new(start + j as i32, y), ch, &fmt);
+            }
+            [x, gap] = [start + len, 1];
+        }
+        0
+    }
+
+    /// The x position of the first run of `len` clear tiles on line `y`,
+    /// starting from `x`.
+    fn find_free_run(&self, x: i32, y: i32, len: i32) -> Option<i32> {
+        if y < 0 || y >= self.height() as i32 {
+            return None;
+        }
+        let mut run = 0;
+        for x in x.max(0)..self.width() as i32 {
+            if self.is_clear([x, y]) {
+                run += 1;
+                if run == len {
+                    return Some(x + 1 - len);
+                }
+            } else {
+                run = 0;
+            }
+        }
+        None
+    }
+
+    /// Draw a bordered box with word wrapped text inside it.
+    ///
+    /// `xy` is the bottom left corner of the box and `size` includes the
+    /// border. The box interior is cleared to the terminal's `clear_tile`
+    /// and the body text is wrapped to fit inside with one tile of padding
+    /// on the left and right. If the border has a title (see
+    /// [`Border::with_title`]) or other edge strings they will be drawn along
+    /// the border edges.
+    ///
+    /// Text that doesn't fit inside the box will be clipped. Returns the
+    /// number of wrapped lines that were clipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([30,10]);
+    /// let border = Border::single_line().with_title("Message");
+    /// term.draw_text_box([0,0], [20,6], &border, "You feel a strange sense of deja vu.");
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn draw_text_box<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        size: impl Size2d,
+        border: &Border,
+        body: impl StringFormatter<'a> + 'a,
+    ) -> usize {
+        self.draw_text_box_aligned(xy, size, border, body, VerticalAlign::Top)
+    }
+
+    /// Draw a bordered box with word wrapped text inside it, vertically
+    /// aligned within the box.
+    ///
+    /// Behaves the same as [`Terminal::draw_text_box`], but short text can be
+    /// centered or aligned to the bottom of the box. Text that doesn't fit is
+    /// always clipped from the bottom.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([30,10]);
+    /// let border = Border::double_line();
+    /// term.draw_text_box_aligned([0,0], [20,8], &border, "Are you sure?", VerticalAlign::Center);
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn draw_text_box_aligned<'a>(
+        &mut self,
+        xy: impl GridPoint,
+        size: impl Size2d,
+        border: &Border,
+        body: impl StringFormatter<'a> + 'a,
+        align: VerticalAlign,
+    ) -> usize {
+        let [x, y] = xy.as_array();
+        let [w, h] = size.as_ivec2().to_array();
+        if w < 2 || h < 2 {
+            return 0;
+        }
+        let [right, top] = [x + w - 1, y + h - 1];
+
+        for y in y + 1..top {
+            for x in x + 1..right {
+                if self.in_bounds([x, y]) {
+                    *self.get_tile_mut([x, y]) = self.clear_tile;
+                }
+            }
+        }
+        self.put_clipped([x, y], border.bottom_left);
+        self.put_clipped([x, top], border.top_left);
+        self.put_clipped([right, y], border.bottom_right);
+        self.put_clipped([right, top], border.top_right);
+        for x in x + 1..right {
+            self.put_clipped([x, top], border.top);
+            self.put_clipped([x, y], border.bottom);
+        }
+        for y in y + 1..top {
+            self.put_clipped([x, y], border.left);
+            self.put_clipped([right, y], border.right);
+        }
+
+        for (p, fmt) in border.edge_string_tiles(IVec2::new(w, h)) {
+            let xy = p + IVec2::new(x, y);
+            if self.in_bounds(xy) {
+                fmt.draw(xy, self);
+                self.record_write(xy);
+            }
+        }
+
+        let fmt = body.formatted();
+        let text_w = (w - 4).max(0) as usize;
+        let text_h = (h - 2) as usize;
+        let lines = wrap_words(&fmt.string, text_w);
+        let padding = text_h.saturating_sub(lines.len());
+        let offset = match align {
+            VerticalAlign::Top => 0,
+            VerticalAlign::Center => padding / 2,
+            VerticalAlign::Bottom => padding,
+        };
+        for (i, line) in lines.iter().take(text_h).enumerate() {
+            let ly = top - 1 - (i + offset) as i32;
+            for (j, ch) in line.chars().enumerate() {
+                let xy = [x + 2 + j as i32, ly];
+                if self.in_bounds(xy) {
+                    let glyph = self.substitute_glyph(ch);
+                    let t = self.get_tile_mut(xy);
+                    t.glyph = glyph;
+                    fmt.apply(t);
+                    self.record_write(xy.into());
+                }
+            }
+        }
+        lines.len().saturating_sub(text_h)
+    }
+
+    /// Draw the outline of a rectangle with the given glyphs.
+    ///
+    /// `xy` is the bottom left corner of the box and `size` includes the
+    /// outline. Only the glyphs of the outline tiles are changed, their colors
+    /// are left as is. If the glyphs have a `fill` tile the inside of the box
+    /// is set to it. Any part of the box outside the terminal is clipped and a
+    /// box with a zero dimension draws nothing. See
+    /// [`Terminal::draw_nine_slice`] to draw a box from arbitrary glyphs.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([20,10]);
+    /// term.draw_box([2,2], [8,5], BoxGlyphs::double_line().with_fill(' '));
+    /// assert_eq!('╔', term.get_char([2,6]));
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn draw_box(&mut self, xy: impl GridPoint, size: impl Size2d, glyphs: BoxGlyphs) {
+        let [x, y] = xy.as_array();
+        let [w, h] = size.as_ivec2().to_array();
+        if w <= 0 || h <= 0 {
+            return;
+        }
+        let [right, top] = [x + w - 1, y + h - 1];
+
+        if let Some(fill) = glyphs.fill {
+            for y in y + 1..top {
+                for x in x + 1..right {
+                    if self.in_bounds([x, y]) {
+                        *self.get_tile_mut([x, y]) = fill;
+                        self.record_write(IVec2::new(x, y));
+                    }
+                }
+            }
+        }
+        for x in x + 1..right {
+            self.put_clipped([x, top], glyphs.horizontal);
+            self.put_clipped([x, y], glyphs.horizontal);
+        }
+        for y in y + 1..top {
+            self.put_clipped([x, y], glyphs.vertical);
+            self.put_clipped([right, y], glyphs.vertical);
+        }
+        self.put_clipped([x, y], glyphs.bottom_left);
+        self.put_clipped([x, top], glyphs.top_left);
+        self.put_clipped([right, y], glyphs.bottom_right);
+        self.put_clipped([right, top], glyphs.top_right);
+    }
+
+    /// Fill a rect with nine glyphs, one for each corner, one for each edge
+    /// and one for the center, for drawing scalable panels from a graphical
+    /// tileset.
+    ///
+    /// `slices` are in rows from the top left: top left, top, top right, left,
+    /// center, right, bottom left, bottom, bottom right. Edge and center glyphs
+    /// are repeated to fill the rect. Like [`Terminal::draw_box`] only glyphs
+    /// are changed and anything outside the terminal is clipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.draw_nine_slice(GridRect::from_bl([0,0], [5,3]), ['╭','─','╮','│',' ','│','╰','─','╯']);
+    /// assert_eq!('╭', term.get_char([0,2]));
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn draw_nine_slice(&mut self, rect: GridRect, slices: [char; 9]) {
+        if rect.size().cmple(IVec2::ZERO).any() {
+            return;
+        }
+        let [min, max] = [rect.min_i(), rect.max_i()];
+        for y in min.y..=max.y {
+            let row = if y == max.y {
+                0
+            } else if y == min.y {
+                2
+            } else {
+                1
+            };
+            for x in min.x..=max.x {
+                let col = if x == min.x {
+                    0
+                } else if x == max.x {
+                    2
+                } else {
+                    1
+                };
+                self.put_clipped([x, y], slices[row * 3 + col]);
+            }
+        }
+    }
+
+    /// Change the glyph of a tile, ignoring positions outside the terminal.
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    fn put_clipped(&mut self, xy: impl GridPoint, glyph: char) {
+        if self.in_bounds(xy) {
+            self.get_tile_mut(xy).glyph = glyph;
+            self.record_write(xy.as_ivec2());
+        }
+    }
+
+    /// Clear a range of characters to the terminal's `clear_tile`.
+    pub fn clear_string(&mut self, xy: impl GridPoint, len: usize) {
+        let i = self.transform_lti(xy);
+        let end = (i + len).min(self.tiles.slice().len());
+        for t in self.tiles.slice_mut()[i..end].iter_mut() {
+            *t = self.clear_tile;
+        }
+        if end > i {
+            let [min, max] = [self.transform_itl(i), self.transform_itl(end - 1)];
+            if min.y == max.y {
+                self.mark_dirty(min, max);
+            } else {
+                // The span wraps, mark every row it touches
+                self.mark_dirty(
+                    IVec2::new(0, min.y),
+                    IVec2::new(self.width() as i32 - 1, max.y),
+                );
+            }
+        }
+    }
+
+    /// Retrieve the char from a tile.
+    pub fn get_char(&self, xy: impl GridPoint) -> char {
+        self.get_tile(xy).glyph
+    }
+
+    /// Retrieve a string from the terminal.
+    pub fn get_string(&self, xy: impl GridPoint, len: usize) -> String {
+        let i = self.transform_lti(xy);
+        let iter = self.tiles.slice()[i..].iter().take(len).map(|t| t.glyph);
+
+        String::from_iter(iter)
+    }
+
+    /// Iterate over a range of tiles along a row, along with their terminal
+    /// local positions.
+    ///
+    /// Unlike [`Terminal::get_string`] the iterator stops at the right edge
+    /// of the terminal rather than wrapping onto the next row.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut term = Terminal::new([10,2]);
+    /// term.put_string([0,1], "Open Quit");
+    /// // Find the position of each word
+    /// let words: Vec<_> = term
+    ///     .iter_string([0,1], 10)
+    ///     .filter(|(xy, _)| xy.x == 0 || term.get_char([xy.x - 1, xy.y]) == ' ')
+    ///     .filter(|(_, t)| t.glyph != ' ')
+    ///     .map(|(xy, _)| xy.x)
+    ///     .collect();
+    /// assert_eq!(vec![0, 5], words);
+    /// ```
+    pub fn iter_string(
+        &self,
+        xy: impl GridPoint,
+        len: usize,
+    ) -> impl Iterator<Item = (IVec2, &Tile)> {
+        let i = self.transform_lti(xy);
+        let start = self.transform_itl(i);
+        let len = len.min(self.width() - start.x as usize);
+        self.tiles.slice()[i..i + len]
+            .iter()
+            .enumerate()
+            .map(move |(j, t)| (start + IVec2::new(j as i32, 0), t))
+    }
+
+    #[inline]
+    /// Retrieve an immutable reference to a tile in the terminal.
+    pub fn get_tile(&self, xy: impl GridPoint) -> &Tile {
+        &self.tiles[self.transform_lti(xy)]
+    }
+
+    #[inline]
+    /// Retrieve a mutable reference to a tile in the terminal.
+    ///
+    /// The tile is marked dirty, see [`Terminal::take_dirty_rect`].
     pub fn get_tile_mut(&mut self, xy: impl GridPoint) -> &mut Tile {
         let i = self.transform_lti(xy);
-        &mut self.tiles[i]
+        let xy = self.transform_itl(i);
+        self.mark_dirty(xy, xy);
+        &mut self.tiles[i]
+    }
+
+    /// Retrieve a reference to a tile in the terminal, or `None` if the
+    /// position is out of bounds.
+    pub fn try_get_tile(&self, xy: impl GridPoint) -> Option<&Tile> {
+        self.in_bounds(xy).then(|| self.get_tile(xy))
+    }
+
+    /// Retrieve a mutable reference to a tile in the terminal, or `None` if
+    /// the position is out of bounds.
+    pub fn try_get_tile_mut(&mut self, xy: impl GridPoint) -> Option<&mut Tile> {
+        if !self.in_bounds(xy) {
+            return None;
+        }
+        Some(self.get_tile_mut(xy))
+    }
+
+    /// Returns true if the tile at the given position matches the terminal's
+    /// `clear_tile`.
+    ///
+    /// Colors are compared approximately, see [`Tile::approx_eq`].
+    pub fn is_clear(&self, xy: impl GridPoint) -> bool {
+        self.get_tile(xy).approx_eq(&self.clear_tile)
+    }
+
+    /// Clear an area of the terminal to the terminal's `clear_tile`.
+    pub fn clear_box(&mut self, xy: impl GridPoint, size: impl Size2d) {
+        let [width, height] = size.as_array();
+        let [x, y] = xy.as_array();
+        for y in y..y + height as i32 {
+            for x in x..x + width as i32 {
+                *self.get_tile_mut([x, y]) = self.clear_tile;
+            }
+        }
+        if width > 0 && height > 0 {
+            let max = IVec2::new(x + width as i32 - 1, y + height as i32 - 1);
+            self.mark_dirty(IVec2::new(x, y), max);
+        }
+    }
+
+    /// Apply a formatted tile to every tile in the terminal.
+    ///
+    /// Unlike [`Terminal::clear`] this doesn't use or change the terminal's
+    /// `clear_tile`. Like [`Terminal::put_char`] only the parts of the tile
+    /// that are specified are changed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.fill('.'.fg(Color::srgb(0.5, 0.5, 0.5)));
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn fill(&mut self, tile: impl TileFormatter) {
+        let size = self.size;
+        self.fill_box([0, 0], size, tile);
+    }
+
+    /// Apply a formatted tile to every tile in an area of the terminal. Any
+    /// part of the area outside the terminal is ignored.
+    ///
+    /// See [`Terminal::fill`].
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn fill_box(&mut self, xy: impl GridPoint, size: impl Size2d, tile: impl TileFormatter) {
+        let fmt = tile.format();
+        let min = xy.as_ivec2().max(IVec2::ZERO);
+        let max = (xy.as_ivec2() + size.as_ivec2() - 1).min(self.size.as_ivec2() - 1);
+        if min.cmpgt(max).any() {
+            return;
+        }
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                fmt.draw([x, y], self);
+                self.record_write(IVec2::new(x, y));
+            }
+        }
+        self.mark_dirty(min, max);
+    }
+
+    /// Swap the foreground and background colors of every tile in the given
+    /// rect. The rect is in terminal local space and is clipped to the
+    /// terminal bounds.
+    ///
+    /// Inverting the same rect twice restores the original colors.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.put_string([0,2], "Selected");
+    /// // Highlight the selected row
+    /// term.invert_colors(GridRect::from_bl([0,2], [10,1]));
+    /// ```
+    pub fn invert_colors(&mut self, rect: GridRect) {
+        let [min, max] = [rect.min_i(), rect.max_i()];
+        let min = min.max(IVec2::ZERO);
+        let max = max.min(self.size.as_ivec2() - 1);
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let t = self.get_tile_mut([x, y]);
+                std::mem::swap(&mut t.fg_color, &mut t.bg_color);
+            }
+        }
+    }
+
+    /// Copy a region of the terminal into a new terminal the same size as the
+    /// region. The rect is in terminal local space.
+    ///
+    /// The new terminal has the same `clear_tile` and no border. Any part of
+    /// the rect outside this terminal is filled with the `clear_tile`.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut term = Terminal::new([20,10]);
+    /// term.put_string([2,2], "/\\");
+    /// term.put_string([2,1], "[]");
+    /// // Save the house as a prefab
+    /// let house = term.clone_region(GridRect::from_bl([2,1], [2,2]));
+    /// assert_eq!('/', house.get_char([0,1]));
+    /// ```
+    pub fn clone_region(&self, rect: GridRect) -> Terminal {
+        let size = rect.size().max(IVec2::ONE);
+        let mut region = Terminal::new(size.to_array()).with_clear_tile(self.clear_tile);
+        let min = rect.min_i();
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let xy = min + IVec2::new(x, y);
+                if self.in_bounds(xy) {
+                    region.put_tile([x, y], *self.get_tile(xy));
+                }
+            }
+        }
+        region
+    }
+
+    /// Copy all the tiles from another terminal into this one, with the
+    /// source's bottom left tile placed at `xy`. Any part of the source outside
+    /// this terminal is ignored.
+    ///
+    /// Only tiles are copied, the source's border, overlays and other per tile
+    /// effects are not. See [`Terminal::blit_with`] to skip some tiles.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut root = Terminal::new([40,20]);
+    /// let mut panel = Terminal::new([10,5]);
+    /// panel.put_string([1,1], "Panel");
+    /// root.blit([2,2], &panel);
+    /// assert_eq!("Panel", root.get_string([3,3], 5));
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn blit(&mut self, xy: impl GridPoint, source: &Terminal) {
+        self.blit_with(xy, source, |_| false);
+    }
+
+    /// Copy tiles from another terminal into this one, with the source's bottom
+    /// left tile placed at `xy`. Source tiles for which `skip` returns true are
+    /// not copied.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let mut root = Terminal::new([40,20]);
+    /// // An overlay which only covers the tiles that are written to
+    /// let mut overlay = Terminal::new([10,5]).with_clear_tile(Tile::transparent());
+    /// overlay.clear();
+    /// overlay.put_string([0,0], "!".fg(Color::WHITE));
+    /// root.blit_with([2,2], &overlay, Tile::is_invisible);
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn blit_with(
+        &mut self,
+        xy: impl GridPoint,
+        source: &Terminal,
+        skip: impl Fn(&Tile) -> bool,
+    ) {
+        let xy = xy.as_ivec2();
+        let min = xy.max(IVec2::ZERO);
+        let max = (xy + source.size.as_ivec2() - 1).min(self.size.as_ivec2() - 1);
+        if min.cmpgt(max).any() {
+            return;
+        }
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let tile = source.get_tile(IVec2::new(x, y) - xy);
+                if !skip(tile) {
+                    *self.get_tile_mut([x, y]) = *tile;
+                    self.record_write(IVec2::new(x, y));
+                }
+            }
+        }
+        self.mark_dirty(min, max);
+    }
+
+    /// Multiply the foreground and background colors of each tile by the
+    /// corresponding factor, for example to apply a light map.
+    ///
+    /// Factors are given per tile in row-major order, starting from the bottom
+    /// left of the terminal. Resulting color channels are clamped to the range
+    /// `0.0..=1.0`. Alpha is unaffected.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of factors doesn't match the number of tiles in
+    /// the terminal.
+    pub fn shade(&mut self, factors: &[f32]) {
+        assert_eq!(
+            self.tiles.slice().len(),
+            factors.len(),
+            "Error shading terminal, expected one factor per tile"
+        );
+        for (t, factor) in self.tiles.iter_mut().zip(factors) {
+            t.fg_color = shade_color(t.fg_color, *factor);
+            t.bg_color = shade_color(t.bg_color, *factor);
+        }
+        self.mark_all_dirty();
+    }
+
+    /// Returns true if any tiles have been written since the terminal was last
+    /// rendered, or if it's never been rendered.
+    ///
+    /// Useful for skipping expensive work that depends on the terminal
+    /// contents, like regenerating a minimap or sending the terminal over the
+    /// network. Any write counts, even one that leaves a tile with the same
+    /// glyph and colors it already had. Writes are tracked the same way as
+    /// [`Terminal::take_dirty_rect`], but taking the dirty rect doesn't
+    /// affect this.
+    pub fn is_dirty(&self) -> bool {
+        self.dirty
+    }
+
+    /// The smallest rect containing every tile written since the last call, or
+    /// `None` if nothing has changed. The rect is in terminal local space.
+    ///
+    /// Every function that changes tiles marks them, including
+    /// [`Terminal::get_tile_mut`] and the mutable iterators. Writes that
+    /// don't change a tile still mark it. Functions that change every tile,
+    /// like clearing, filling, scrolling or resizing the terminal, mark the
+    /// whole terminal, as does creating it.
+    ///
+    /// If [`crate::TerminalLayout::dirty_tracking`] is enabled the renderer
+    /// takes the rect every frame to patch the terminal mesh.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut term = Terminal::new([20,10]);
+    /// term.take_dirty_rect();
+    /// term.put_char([2,3], '@');
+    /// term.put_char([5,1], 'g');
+    /// let dirty = term.take_dirty_rect().unwrap();
+    /// assert_eq!([2,1], dirty.min_i().to_array());
+    /// assert_eq!([5,3], dirty.max_i().to_array());
+    /// ```
+    pub fn take_dirty_rect(&mut self) -> Option<GridRect> {
+        self.dirty_rect.take()
+    }
+
+    /// Mark every tile in the terminal as dirty.
+    fn mark_all_dirty(&mut self) {
+        self.dirty_rect = Some(GridRect::from_bl([0, 0], self.size));
+        self.dirty = true;
+    }
+
+    /// Expand the dirty rect to include the tiles from `min` to `max`,
+    /// inclusive. See [`Terminal::take_dirty_rect`].
+    fn mark_dirty(&mut self, min: IVec2, max: IVec2) {
+        let [min, max] = match self.dirty_rect {
+            Some(rect) => [rect.min_i().min(min), rect.max_i().max(max)],
+            None => [min, max],
+        };
+        self.dirty_rect = Some(GridRect::from_bl(min, max - min + 1));
+        self.dirty = true;
+    }
+
+    /// Reset [`Terminal::is_dirty`] once the terminal has been rendered.
+    pub(crate) fn mark_rendered(&mut self) {
+        self.dirty = false;
+        #[cfg(feature = "debug_writes")]
+        self.frame_writes.clear();
+    }
+
+    /// Every tile written with two different glyphs in the same frame since
+    /// the last call. Requires the `debug_writes` feature.
+    ///
+    /// A frame ends when the terminal is rendered. Every function that writes
+    /// glyphs is checked, including the string, drawing, filling and blitting
+    /// functions. Clearing, scrolling, color changes,
+    /// [`Terminal::cellular_automata_step`] and writes through
+    /// [`Terminal::get_tile_mut`] or the mutable iterators are not. Each
+    /// conflict is also logged as a warning along with where both glyphs were
+    /// written from, which helps track down systems fighting over the same
+    /// tiles.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.put_string([0,0], "HP: 10");
+    /// term.put_string([0,0], "MP: 5");
+    /// let conflict = term.take_write_conflicts()[0];
+    /// assert_eq!(('H', 'M'), (conflict.first, conflict.second));
+    /// ```
+    #[cfg(feature = "debug_writes")]
+    pub fn take_write_conflicts(&mut self) -> Vec<WriteConflict> {
+        std::mem::take(&mut self.write_conflicts)
+    }
+
+    /// Check a write against any earlier write to the same tile this frame,
+    /// see [`Terminal::take_write_conflicts`].
+    #[cfg(feature = "debug_writes")]
+    #[track_caller]
+    fn record_write(&mut self, xy: IVec2) {
+        if !self.in_bounds(xy) {
+            return;
+        }
+        let i = self.transform_lti(xy);
+        let second = self.tiles[i].glyph;
+        let location = Location::caller();
+        if let Some((first, first_location)) = self.frame_writes.insert(i, (second, location)) {
+            if first != second {
+                warn!(
+                    "Tile {xy} was written with '{first}' at {first_location} and then \
+                    '{second}' at {location} in the same frame"
+                );
+                self.write_conflicts
+                    .push(WriteConflict { xy, first, second });
+            }
+        }
+    }
+
+    #[cfg(not(feature = "debug_writes"))]
+    #[inline]
+    fn record_write(&mut self, _xy: IVec2) {}
+
+    /// Clear the terminal tiles to the terminal's `clear_tile`.
+    ///
+    /// This also removes all overlays and moves the print cursor back to the
+    /// top left, see [`Terminal::print`]. Other per tile settings are kept
+    /// since they're usually set once rather than redrawn with the tiles:
+    /// sticky flags, tints, orientations, pulses, depths and glyph offsets
+    /// each have their own function to reset them, like
+    /// [`Terminal::clear_tints`].
+    pub fn clear(&mut self) {
+        for t in self.tiles.iter_mut() {
+            *t = self.clear_tile
+        }
+        self.overlays.clear();
+        self.reset_print_cursor();
+        self.mark_all_dirty();
+    }
+
+    /// Mark a tile as sticky or not. Sticky tiles are left untouched by
+    /// [`Terminal::clear_non_sticky`], which is used by
+    /// [`ClearAfterRender`](crate::ClearAfterRender).
+    ///
+    /// This lets persistent content, like a static frame, coexist with
+    /// content that's redrawn every frame on the same terminal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([10,3]);
+    /// term.put_string([0,2], "HP:");
+    /// for x in 0..3 {
+    ///     term.set_sticky([x,2], true);
+    /// }
+    /// term.put_string([4,2], "25");
+    /// term.clear_non_sticky();
+    /// assert_eq!("HP:  ", term.get_string([0,2], 5));
+    /// ```
+    pub fn set_sticky(&mut self, xy: impl GridPoint, sticky: bool) {
+        let i = self.transform_lti(xy);
+        self.sticky.set(i, sticky);
+    }
+
+    /// Returns true if the tile at the given position is sticky.
+    ///
+    /// See [`Terminal::set_sticky`].
+    pub fn is_sticky(&self, xy: impl GridPoint) -> bool {
+        let i = self.transform_lti(xy);
+        self.sticky.get(i)
+    }
+
+    /// Unmark all sticky tiles.
+    pub fn clear_sticky(&mut self) {
+        self.sticky.clear();
+    }
+
+    /// Clear all tiles which aren't sticky to the terminal's `clear_tile`.
+    ///
+    /// Like [`Terminal::clear`] this removes the overlays of cleared tiles and
+    /// moves the print cursor back to the top left. See
+    /// [`Terminal::set_sticky`].
+    pub fn clear_non_sticky(&mut self) {
+        let sticky = self.sticky.as_slice();
+        if sticky.is_empty() {
+            self.clear();
+            return;
+        }
+        let tile = self.clear_tile;
+        for (i, (t, sticky)) in self.tiles.iter_mut().zip(sticky).enumerate() {
+            if !sticky {
+                *t = tile;
+                self.overlays.set(i, None);
+            }
+        }
+        self.reset_print_cursor();
+        self.mark_all_dirty();
+    }
+
+    /// Draw a second glyph on top of a tile with it's own foreground color,
+    /// for example a combining accent over a base character.
+    ///
+    /// Each tile can have a single overlay, which is rendered as a separate
+    /// quad above the tile with a transparent background.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::prelude::Color;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// // An 'a' with a grave accent
+    /// term.put_char([1,1], 'a');
+    /// term.put_overlay([1,1], '`', Color::WHITE);
+    /// ```
+    pub fn put_overlay(&mut self, xy: impl GridPoint, glyph: char, color: Color) {
+        let i = self.transform_lti(xy);
+        self.overlays.set(i, Some((glyph, color)));
+    }
+
+    /// The overlay glyph and color of the tile at the given position, if any.
+    ///
+    /// See [`Terminal::put_overlay`].
+    pub fn overlay(&self, xy: impl GridPoint) -> Option<(char, Color)> {
+        let i = self.transform_lti(xy);
+        self.overlays.get(i)
+    }
+
+    /// Remove the overlay from the tile at the given position.
+    pub fn clear_overlay(&mut self, xy: impl GridPoint) {
+        let i = self.transform_lti(xy);
+        self.overlays.set(i, None);
+    }
+
+    /// Iterate over all overlays in tile index order, along with the index of
+    /// the tile they're drawn over.
+    pub(crate) fn iter_overlays(&self) -> impl Iterator<Item = (usize, char, Color)> + '_ {
+        self.overlays
+            .as_slice()
+            .iter()
+            .enumerate()
+            .filter_map(|(i, o)| o.map(|(glyph, color)| (i, glyph, color)))
+    }
+
+    /// Shrink this terminal into another, smaller terminal, collapsing each
+    /// block of tiles into a single tile of `dest`.
+    ///
+    /// The blocks are sized so the whole terminal fits into `dest`. If a
+    /// dimension of `dest` is larger than this terminal's tiles will be
+    /// repeated instead. Useful for drawing a minimap of a larger view.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let map = Terminal::new([80,40]);
+    /// let mut minimap = Terminal::new([20,10]);
+    /// map.downscale_into(&mut minimap, DownscaleMode::DominantGlyph);
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn downscale_into(&self, dest: &mut Terminal, mode: DownscaleMode) {
+        let src = self.size().as_ivec2();
+        let dst = dest.size().as_ivec2();
+        // Range of source tiles covered by a destination tile along one axis
+        let span = |i: i32, src: i32, dst: i32| {
+            let start = i * src / dst;
+            let end = ((i + 1) * src / dst).max(start + 1);
+            start..end
+        };
+
+        for y in 0..dst.y {
+            for x in 0..dst.x {
+                let (xs, ys) = (span(x, src.x, dst.x), span(y, src.y, dst.y));
+                let block: Vec<&Tile> = ys
+                    .flat_map(|y| xs.clone().map(move |x| IVec2::new(x, y)))
+                    .map(|p| self.get_tile(p))
+                    .collect();
+
+                let mut counts: Vec<(char, usize)> = Vec::new();
+                for t in &block {
+                    match counts.iter_mut().find(|(g, _)| *g == t.glyph) {
+                        Some((_, n)) => *n += 1,
+                        None => counts.push((t.glyph, 1)),
+                    }
+                }
+                // Ties go to the glyph that appears first in the block
+                let glyph = counts
+                    .iter()
+                    .fold((' ', 0), |best, &c| if c.1 > best.1 { c } else { best })
+                    .0;
+
+                let tile = match mode {
+                    DownscaleMode::AverageColor => Tile {
+                        glyph,
+                        fg_color: average_color(block.iter().map(|t| t.fg_color)),
+                        bg_color: average_color(block.iter().map(|t| t.bg_color)),
+                    },
+                    DownscaleMode::DominantGlyph => {
+                        **block.iter().find(|t| t.glyph == glyph).unwrap()
+                    }
+                };
+                *dest.get_tile_mut([x, y]) = tile;
+                dest.record_write(IVec2::new(x, y));
+            }
+        }
+    }
+
+    /// Create an image with one pixel per tile, colored by either the
+    /// foreground or background color of the tile.
+    ///
+    /// Glyphs are ignored, this is meant for cheap minimaps and thumbnails
+    /// rather than a rendering of the terminal, see [`Terminal::to_image`] for
+    /// that. The top row of the image is the top row of the terminal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// fn minimap(q_term: Query<&Terminal>, mut images: ResMut<Assets<Image>>) {
+    ///     let image = q_term.single().to_rgba_image(ColorSource::Background);
+    ///     let handle = images.add(image);
+    /// }
+    /// ```
+    pub fn to_rgba_image(&self, source: ColorSource) -> Image {
+        let [width, height] = self.size().to_array();
+        let mut data = Vec::with_capacity((width * height * 4) as usize);
+        for y in (0..height as i32).rev() {
+            for x in 0..width as i32 {
+                let t = self.get_tile([x, y]);
+                let color = match source {
+                    ColorSource::Background => t.bg_color,
+                    ColorSource::Foreground => t.fg_color,
+                }
+                .to_srgba();
+                data.extend(
+                    [color.red, color.green, color.blue, color.alpha]
+                        .map(|c| (c.clamp(0.0, 1.0) * 255.0).round() as u8),
+                );
+            }
+        }
+        Image::new(
+            Extent3d {
+                width,
+                height,
+                depth_or_array_layers: 1,
+            },
+            TextureDimension::D2,
+            data,
+            TextureFormat::Rgba8UnormSrgb,
+            RenderAssetUsages::default(),
+        )
+    }
+
+    /// Tint the foreground of a tile towards the accent color of the terminal's
+    /// material. See [`TerminalMaterial::accent_color`](crate::TerminalMaterial::accent_color).
+    ///
+    /// The tint is clamped to the range `[0..1]`, where 0 leaves the
+    /// foreground unaffected and 1 replaces it with the accent color. The
+    /// blending happens in the shader, so tints can be updated every frame
+    /// without recalculating colors, for example to show a heat map or damage
+    /// states.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.put_char([5,5], 'g');
+    /// // Halfway towards the accent color
+    /// term.set_tint([5,5], 0.5);
+    /// ```
+    pub fn set_tint(&mut self, xy: impl GridPoint, tint: f32) {
+        let i = self.transform_lti(xy);
+        self.tints.set(i, tint.clamp(0.0, 1.0));
+    }
+
+    /// The accent tint of the tile at the given position.
+    ///
+    /// See [`Terminal::set_tint`].
+    pub fn tint(&self, xy: impl GridPoint) -> f32 {
+        let i = self.transform_lti(xy);
+        self.tints.get(i)
+    }
+
+    /// Reset all accent tints to zero.
+    pub fn clear_tints(&mut self) {
+        self.tints.clear();
+    }
+
+    /// Accent tints for every tile in the terminal, or an empty slice if no
+    /// tints are set.
+    pub(crate) fn tints(&self) -> &[f32] {
+        self.tints.as_slice()
+    }
+
+    /// Rotate or mirror the glyph of a tile when it's rendered.
+    ///
+    /// Useful for graphical tilesets, where one sprite can be reused for each
+    /// direction of a wall or corner. The uvs are reordered when the mesh is
+    /// built, so the terminal's [`UvMapping`](crate::UvMapping) is unaffected.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.put_char([5,5], '└');
+    /// // Draw the glyph as '┌'
+    /// term.set_orientation([5,5], TileOrientation::Rotate90);
+    /// ```
+    pub fn set_orientation(&mut self, xy: impl GridPoint, orientation: TileOrientation) {
+        let i = self.transform_lti(xy);
+        self.orientations.set(i, orientation);
+    }
+
+    /// The glyph orientation of the tile at the given position.
+    ///
+    /// See [`Terminal::set_orientation`].
+    pub fn orientation(&self, xy: impl GridPoint) -> TileOrientation {
+        let i = self.transform_lti(xy);
+        self.orientations.get(i)
+    }
+
+    /// Reset all glyph orientations to [`TileOrientation::Normal`].
+    pub fn clear_orientations(&mut self) {
+        self.orientations.clear();
+    }
+
+    /// Glyph orientations for every tile in the terminal, or an empty slice
+    /// if no orientations are set.
+    pub(crate) fn orientations(&self) -> &[TileOrientation] {
+        self.orientations.as_slice()
+    }
+
+    /// Make the background of a tile pulse, smoothly dimming and brightening
+    /// over time to draw attention to it.
+    ///
+    /// The pulse is animated in the shader using the global time, so it
+    /// doesn't require updating the terminal each frame. Strings can be
+    /// written with pulsing tiles using [`StringFormatter::pulse`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.put_char([5,5], '!');
+    /// term.set_pulse([5,5], true);
+    /// ```
+    pub fn set_pulse(&mut self, xy: impl GridPoint, pulse: bool) {
+        let i = self.transform_lti(xy);
+        self.pulses.set(i, pulse);
+    }
+
+    /// Whether the tile at the given position pulses.
+    ///
+    /// See [`Terminal::set_pulse`].
+    pub fn pulse(&self, xy: impl GridPoint) -> bool {
+        let i = self.transform_lti(xy);
+        self.pulses.get(i)
+    }
+
+    /// Stop all tiles from pulsing.
+    pub fn clear_pulses(&mut self) {
+        self.pulses.clear();
+    }
+
+    /// Pulse flags for every tile in the terminal, or an empty slice if no
+    /// tiles pulse.
+    pub(crate) fn pulses(&self) -> &[bool] {
+        self.pulses.as_slice()
+    }
+
+    /// Set the depth of a tile, used to decide which tile is visible where
+    /// terminals overlap.
+    ///
+    /// The depth is added to the z position of the tile's quad, in world
+    /// units. Where two terminals overlap, the tile with the greater final
+    /// z position is drawn in front, regardless of the order the terminals
+    /// are drawn in. This allows effects like a character on one terminal
+    /// standing in front of some walls on another terminal but behind others.
+    /// Keep depths small enough that tiles stay within the camera's range.
+    ///
+    /// # Cost
+    ///
+    /// A terminal with depths switches to its own copy of its material which
+    /// writes to the depth buffer, so it can't be batched with other
+    /// terminals. Its vertex positions are rebuilt whenever the terminal
+    /// changes. Fully transparent pixels are discarded so they don't occlude
+    /// anything, custom [`TerminalShader`](crate::TerminalShader)s should do
+    /// the same.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([10,10]).with_clear_tile(Tile::transparent());
+    /// term.put_char([5,5], '@');
+    /// // Stand in front of any tiles with a lower depth on other terminals
+    /// term.set_depth([5,5], 2.0);
+    /// ```
+    pub fn set_depth(&mut self, xy: impl GridPoint, depth: f32) {
+        let i = self.transform_lti(xy);
+        self.depths.set(i, depth);
+    }
+
+    /// The depth of the tile at the given position.
+    ///
+    /// See [`Terminal::set_depth`].
+    pub fn depth(&self, xy: impl GridPoint) -> f32 {
+        let i = self.transform_lti(xy);
+        self.depths.get(i)
+    }
+
+    /// Reset all tile depths to zero.
+    pub fn clear_depths(&mut self) {
+        self.depths.clear();
+    }
+
+    /// Depths for every tile in the terminal, or an empty slice if no depths
+    /// are set.
+    pub(crate) fn depths(&self) -> &[f32] {
+        self.depths.as_slice()
+    }
+
+    /// Offset the glyph of a tile from the center of the tile, measured in
+    /// tiles rather than pixels, so `0.5` is half a tile for any font. The
+    /// tile's background is unaffected.
+    ///
+    /// The glyph is shifted inside its own tile, so any part of it moved past
+    /// the edge of the tile is clipped and an offset of a whole tile or more
+    /// hides it completely. It can't spill over into neighboring tiles.
+    ///
+    /// Useful for effects like shaking a glyph when it's hit, without moving
+    /// the rest of the terminal.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    /// use bevy::math::Vec2;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.put_char([5,5], '@');
+    /// // Nudge the glyph one eighth of a tile to the right
+    /// term.set_glyph_offset([5,5], Vec2::new(0.125, 0.0));
+    /// ```
+    pub fn set_glyph_offset(&mut self, xy: impl GridPoint, offset: Vec2) {
+        let i = self.transform_lti(xy);
+        self.glyph_offsets.set(i, offset);
+    }
+
+    /// The glyph offset of the tile at the given position.
+    ///
+    /// See [`Terminal::set_glyph_offset`].
+    pub fn glyph_offset(&self, xy: impl GridPoint) -> Vec2 {
+        let i = self.transform_lti(xy);
+        self.glyph_offsets.get(i)
+    }
+
+    /// Reset all glyph offsets to zero.
+    pub fn clear_glyph_offsets(&mut self) {
+        self.glyph_offsets.clear();
+    }
+
+    /// Glyph offsets for every tile in the terminal, or an empty slice if no
+    /// offsets are set.
+    pub(crate) fn glyph_offsets(&self) -> &[Vec2] {
+        self.glyph_offsets.as_slice()
+    }
+
+    /// Move every row of tiles up by the given number of lines.
+    ///
+    /// Rows moved past the top of the terminal are lost and the rows left
+    /// empty at the bottom are cleared to the terminal's `clear_tile`. Only
+    /// tiles are moved, overlays, tints and other per tile settings stay
+    /// where they are. If a scroll region is set only the tiles inside it are
+    /// moved, see [`Terminal::with_scroll_region`].
+    pub fn scroll_up(&mut self, lines: usize) {
+        if self.scroll_region.is_some() {
+            let [min, max] = self.scroll_bounds();
+            let n = lines.min(i32::MAX as usize) as i32;
+            for y in (min.y..=max.y).rev() {
+                for x in min.x..=max.x {
+                    let tile = if y - n >= min.y {
+                        *self.get_tile([x, y - n])
+                    } else {
+                        self.clear_tile
+                    };
+                    *self.get_tile_mut([x, y]) = tile;
+                }
+            }
+            return;
+        }
+        let n = lines.min(self.height()) * self.width();
+        let tiles = self.tiles.slice_mut();
+        let len = tiles.len();
+        // Tiles are stored from the bottom row up
+        tiles.copy_within(..len - n, n);
+        tiles[..n].fill(self.clear_tile);
+        self.mark_all_dirty();
+    }
+
+    pub fn clear_line(&mut self, line: usize) {
+        let tile = self.clear_tile;
+        self.iter_row_mut(line).for_each(|t| *t = tile);
+    }
+
+    /// Draw a line between two positions, inclusive.
+    ///
+    /// The line is drawn using Bresenham's line algorithm, writing the given
+    /// tile to each position along it. Positions outside the terminal are
+    /// ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.draw_line([1,1], [8,6], '*');
+    /// assert_eq!('*', term.get_char([8,6]));
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn draw_line(&mut self, start: impl GridPoint, end: impl GridPoint, tile: impl Into<Tile>) {
+        let tile = tile.into();
+        for p in BresenhamLine::new(start.as_ivec2(), end.as_ivec2()) {
+            if self.in_bounds(p) {
+                *self.get_tile_mut(p) = tile;
+                self.record_write(p);
+            }
+        }
+    }
+
+    /// Draw a line between two positions with an arrowhead at the `to` end.
+    ///
+    /// The line is drawn using Bresenham's line algorithm, with the given
+    /// formatting applied to each tile. The arrowhead glyph is chosen based on
+    /// the dominant direction of the line, diagonal lines use the nearest
+    /// cardinal arrow. Positions outside the terminal are ignored.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.draw_arrow([1,1], [8,1], '─');
+    /// assert_eq!('→', term.get_char([8,1]));
+    /// ```
+    #[cfg_attr(feature = "debug_writes", track_caller)]
+    pub fn draw_arrow(
+        &mut self,
+        from: impl GridPoint,
+        to: impl GridPoint,
+        tile: impl TileFormatter,
+    ) {
+        let [from, to] = [from.as_ivec2(), to.as_ivec2()];
+        let fmt = tile.format();
+        for p in BresenhamLine::new(from, to).filter(|p| *p != to) {
+            if self.in_bounds(p) {
+                fmt.draw(p, self);
+                self.record_write(p);
+            }
+        }
+        if self.in_bounds(to) {
+            let head = fmt.glyph(arrow_glyph(to - from));
+            head.draw(to, self);
+            self.record_write(to);
+        }
+    }
+
+    /// Returns true if there is an unobstructed line between two positions in
+    /// the terminal.
+    ///
+    /// The line is walked using Bresenham's line algorithm. If `is_opaque`
+    /// returns true for any position on the line between `from` and `to` then
+    /// the line is blocked. The end points themselves are not tested.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.put_char([5,5], '#');
+    ///
+    /// let visible = term.bresenham_visible([0,5], [9,5], |p| term.get_char(p) == '#');
+    /// assert!(!visible);
+    /// ```
+    pub fn bresenham_visible(
+        &self,
+        from: impl GridPoint,
+        to: impl GridPoint,
+        is_opaque: impl Fn(IVec2) -> bool,
+    ) -> bool {
+        let [from, to] = [from.as_ivec2(), to.as_ivec2()];
+        !BresenhamLine::new(from, to)
+            .filter(|p| *p != from && *p != to)
+            .any(is_opaque)
+    }
+
+    /// Returns true if the given position is inside the bounds of the terminal.
+    #[inline]
+    pub fn in_bounds(&self, xy: impl GridPoint) -> bool {
+        self.tiles.in_bounds(xy)
+    }
+
+    /// An iterator over the in-bounds neighbors of a tile, with their positions.
+    ///
+    /// Yields the 4 orthogonal neighbors, or all 8 surrounding tiles if
+    /// `include_diagonals` is true. Positions outside the terminal are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([10,10]);
+    /// term.put_char([4,5], '#');
+    /// let walls = term
+    ///     .iter_neighbors([5,5], true)
+    ///     .filter(|(_, t)| t.glyph == '#')
+    ///     .count();
+    /// assert_eq!(1, walls);
+    /// ```
+    pub fn iter_neighbors(
+        &self,
+        xy: impl GridPoint,
+        include_diagonals: bool,
+    ) -> impl Iterator<Item = (IVec2, &Tile)> {
+        const ORTHOGONAL: [[i32; 2]; 4] = [[0, 1], [1, 0], [0, -1], [-1, 0]];
+        const DIAGONAL: [[i32; 2]; 4] = [[1, 1], [1, -1], [-1, -1], [-1, 1]];
+        let xy = xy.as_ivec2();
+        let diagonals = if include_diagonals { DIAGONAL.len() } else { 0 };
+        ORTHOGONAL
+            .into_iter()
+            .chain(DIAGONAL.into_iter().take(diagonals))
+            .map(move |d| xy + IVec2::from(d))
+            .filter(move |p| self.in_bounds(*p))
+            .map(move |p| (p, self.get_tile(p)))
+    }
+
+    /// Run one step of a cellular automaton over the terminal's glyphs, the
+    /// classic way to turn random noise into cave-like maps.
+    ///
+    /// Tiles with the `wall` glyph are walls, all other tiles are treated as
+    /// floors. A floor becomes a wall if it has at least `birth` wall
+    /// neighbors, and a wall stays a wall if it has at least `survival` wall
+    /// neighbors. Neighbors outside the terminal count as walls. The classic
+    /// "4-5 rule" uses a `birth` of 5 and a `survival` of 4.
+    ///
+    /// Every tile's glyph is set to either `wall` or `floor`, colors are left
+    /// unchanged. All tiles are updated at once from the previous state.
+    /// Returns the number of tiles that changed, which will approach zero
+    /// as the map settles.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy_ascii_terminal::prelude::*;
+    ///
+    /// let mut term = Terminal::new([40,20]);
+    /// // Fill the terminal with random walls
+    /// for (i, t) in term.iter_mut().enumerate() {
+    ///     t.glyph = if (i * 7919) % 100 < 45 { '#' } else { '.' };
+    /// }
+    /// for _ in 0..4 {
+    ///     term.cellular_automata_step('#', '.', 5, 4);
+    /// }
+    /// ```
+    pub fn cellular_automata_step(
+        &mut self,
+        wall: char,
+        floor: char,
+        birth: usize,
+        survival: usize,
+    ) -> usize {
+        let next: Vec<char> = self
+            .iter_bounds_positions()
+            .map(|p| {
+                let (count, walls) = self
+                    .iter_neighbors(p, true)
+                    .fold((0, 0), |(count, walls), (_, t)| {
+                        (count + 1, walls + (t.glyph == wall) as usize)
+                    });
+                let walls = walls + 8 - count;
+                let threshold = if self.get_char(p) == wall {
+                    survival
+                } else {
+                    birth
+                };
+                if walls >= threshold {
+                    wall
+                } else {
+                    floor
+                }
+            })
+            .collect();
+
+        let mut changed = 0;
+        for (t, glyph) in self.tiles.iter_mut().zip(next) {
+            if t.glyph != glyph {
+                t.glyph = glyph;
+                changed += 1;
+            }
+        }
+        if changed > 0 {
+            self.mark_all_dirty();
+        }
+        changed
+    }
+
+    /// Count the number of tiles in the terminal with the given glyph.
+    pub fn count_glyph(&self, ch: char) -> usize {
+        self.tiles.iter().filter(|t| t.glyph == ch).count()
+    }
+
+    /// Count the number of tiles in the terminal for each glyph.
+    pub fn glyph_histogram(&self) -> HashMap<char, usize> {
+        let mut counts = HashMap::default();
+        for t in self.tiles.iter() {
+            *counts.entry(t.glyph).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// An immutable iterator over the tiles of the terminal.
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Tile> {
+        self.tiles.iter()
+    }
+
+    /// A mutable iterator over the tiles of the terminal.
+    ///
+    /// Every tile is marked dirty, see [`Terminal::take_dirty_rect`].
+    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Tile> {
+        self.mark_all_dirty();
+        self.tiles.iter_mut()
+    }
+
+    /// An immutable iterator over an entire row of tiles in the terminal.
+    pub fn iter_row(&self, y: usize) -> impl DoubleEndedIterator<Item = &Tile> {
+        self.tiles.iter_row(y)
+    }
+
+    /// An immutable iterator over an entire row of tiles in the terminal.
+    pub fn iter_row_mut(&mut self, y: usize) -> impl DoubleEndedIterator<Item = &mut Tile> {
+        let y = y as i32;
+        self.mark_dirty(IVec2::new(0, y), IVec2::new(self.width() as i32 - 1, y));
+        self.tiles.iter_row_mut(y as usize)
+    }
+
+    /// An immutable iterator over a range of rows in the terminal.
+    ///
+    /// The iterator moves along each row from left to right, where 0 is the
+    /// bottom row and `height - 1` is the top row.
+    pub fn iter_rows(
+        &self,
+        range: impl RangeBounds<usize>,
+    ) -> impl DoubleEndedIterator<Item = &[Tile]> {
+        self.tiles.iter_rows(range)
+    }
+
+    /// A mutable iterator over a range of rows in the terminal.
+    ///
+    /// The iterator moves along each row from left to right, where 0 is the
+    /// bottom row and `height - 1` is the top row.
+    pub fn iter_rows_mut(
+        &mut self,
+        range: impl RangeBounds<usize>,
+    ) -> impl DoubleEndedIterator<Item = &mut [Tile]> {
+        self.mark_all_dirty();
+        self.tiles.iter_rows_mut(range)
+    }
+
+    /// An immutable iterator over an entire column of tiles in the terminal.
+    ///
+    /// The iterator moves from bottom to top.
+    pub fn iter_column(&self, x: usize) -> impl DoubleEndedIterator<Item = &Tile> {
+        self.tiles.iter_column(x)
+    }
+
+    /// A mutable iterator over an entire column of tiles in the terminal.
+    ///
+    /// The iterator moves from bottom to top.
+    pub fn iter_column_mut(&mut self, x: usize) -> impl DoubleEndedIterator<Item = &mut Tile> {
+        let x = x as i32;
+        self.mark_dirty(IVec2::new(x, 0), IVec2::new(x, self.height() as i32 - 1));
+        self.tiles.iter_column_mut(x as usize)
+    }
+
+    /// An iterator over every tile position in the terminal, in local space.
+    ///
+    /// The iterator moves along each row from left to right, starting from
+    /// the bottom row.
+    pub fn iter_bounds_positions(&self) -> impl Iterator<Item = IVec2> {
+        let [w, h] = self.size.as_ivec2().to_array();
+        (0..h).flat_map(move |y| (0..w).map(move |x| IVec2::new(x, y)))
+    }
+
+    /// An iterator over the tile positions along the edges of the terminal,
+    /// in local space. Each position is yielded exactly once.
+    pub fn iter_perimeter_positions(&self) -> impl Iterator<Item = IVec2> {
+        Self::iter_box_perimeter([0, 0], self.size)
+    }
+
+    /// An iterator over the tile positions along the edges of a box with
+    /// it's bottom left corner at `xy`. Each position is yielded exactly once.
+    pub fn iter_box_perimeter(
+        xy: impl GridPoint,
+        size: impl Size2d,
+    ) -> impl Iterator<Item = IVec2> {
+        let min = xy.as_ivec2();
+        let [w, h] = size.as_ivec2().to_array();
+        let (w, h) = if w > 0 && h > 0 { (w, h) } else { (0, 0) };
+        let top_w = if h > 1 { w } else { 0 };
+        let right_h = if w > 1 { h - 1 } else { 1 };
+        let bottom = (0..w).map(move |x| [x, 0]);
+        let top = (0..top_w).map(move |x| [x, h - 1]);
+        let left = (1..h - 1).map(|y| [0, y]);
+        let right = (1..right_h).map(move |y| [w - 1, y]);
+        bottom
+            .chain(top)
+            .chain(left)
+            .chain(right)
+            .map(move |p| min + IVec2::from(p))
+    }
+
+    /// Get the index for a given side on the terminal.
+    pub fn side_index(&self, side: Side) -> usize {
+        self.tiles.side_index(side)
+    }
+
+    /// Transform a position from terminal local space (origin bottom left) to
+    /// world space (origin center).
+    ///
+    /// The center tile is `size / 2`, rounded down. For an even size this is
+    /// the tile just above and to the right of the terminal's true center,
+    /// which is the tile covering the world origin when the terminal mesh is
+    /// centered. [`Terminal::transform_wtl`] uses the same convention, so the
+    /// two transforms are always inverses of each other.
+    #[inline]
+    pub fn transform_ltw(&self, pos: impl GridPoint) -> IVec2 {
+        pos.as_ivec2() - self.center_tile()
+    }
+
+    /// Transform a position from world space (origin center) to terminal local
+    /// space (origin bottom left).
+    ///
+    /// See [`Terminal::transform_ltw`] for how the center tile is chosen.
+    #[inline]
+    pub fn transform_wtl(&self, pos: impl GridPoint) -> IVec2 {
+        pos.as_ivec2() + self.center_tile()
+    }
+
+    /// The local position of the tile at the world origin.
+    #[inline]
+    fn center_tile(&self) -> IVec2 {
+        self.size.as_ivec2().div(2)
+    }
+
+    pub fn slice(&self) -> &[Tile] {
+        self.tiles.slice()
+    }
+
+    pub fn slice_mut(&mut self) -> &mut [Tile] {
+        self.mark_all_dirty();
+        self.tiles.slice_mut()
+    }
+
+    pub fn bounds_with_border(&self) -> GridRect {
+        let bounds = self.bounds();
+        if self.has_border() {
+            bounds.resized([1, 1])
+        } else {
+            bounds
+        }
+    }
+
+    pub fn bounds(&self) -> GridRect {
+        let mut bounds = self.tiles.bounds();
+        bounds.center -= self.size.as_ivec2() / 2;
+        //println!("TERM BOUNDS {}", bounds);
+        bounds
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AlignedStringFormatter;
+    use bevy::color::palettes::basic::RED;
+
+    #[test]
+    fn put_char() {
+        let mut term = Terminal::new([20, 20]);
+
+        term.put_char([5, 5], 'h');
+
+        assert_eq!('h', term.get_char([5, 5]));
+
+        term.put_char([1, 2], 'q'.fg(Color::Srgba(RED)));
+
+        let t = term.get_tile([1, 2]);
+        assert_eq!('q', t.glyph);
+        assert_eq!(Color::Srgba(RED), t.fg_color);
+    }
+
+    #[test]
+    fn put_string() {
+        let mut term = Terminal::new([20, 20]);
+        // term.put_string([0, 0], "Hello");
+        // assert_eq!("Hello", term.get_string([0, 0], 5));
+
+        term.put_string([1, 1], "Hello");
+        assert_eq!("He", term.get_string([1, 1], 2));
+    }
+
+    #[test]
+    fn put_string_with() {
+        let mut term = Terminal::new([20, 20]);
+        term.put_string_with([0, 0], "Hello", |_, c| {
+            "aeiou".contains(c).then_some(Color::Srgba(RED))
+        });
+
+        for (x, c) in "Hello".chars().enumerate() {
+            let t = term.get_tile([x as i32, 0]);
+            if "aeiou".contains(c) {
+                assert_eq!(Color::Srgba(RED), t.fg_color);
+            } else {
+                assert_eq!(Tile::DEFAULT_FGCOL, t.fg_color);
+            }
+        }
+    }
+
+    #[test]
+    fn put_string_patterned() {
+        let mut term = Terminal::new([20, 20]);
+        term.put_string_patterned([1, 1], "Hello\nA b", '░'.fg(Color::Srgba(RED)));
+
+        assert_eq!("Hello", term.get_string([1, 2], 5));
+        assert_eq!("A░b░░", term.get_string([1, 1], 5));
+        assert_eq!(Color::Srgba(RED), term.get_tile([5, 1]).fg_color);
+        assert_eq!(' ', term.get_char([6, 1]));
+        assert_eq!(' ', term.get_char([1, 3]));
+    }
+
+    #[test]
+    fn measure_string() {
+        let term = Terminal::new([5, 5]);
+        let text = "Hello\nHow are you?";
+        assert_eq!(UVec2::new(12, 2), term.measure_string(text, None));
+        assert_eq!(UVec2::new(7, 3), term.measure_string(text, Some(8)));
+        assert_eq!(UVec2::ZERO, term.measure_string("", None));
+        assert_eq!(
+            UVec2::new(12, 2),
+            term.measure_string(text.fg(Color::Srgba(RED)), None)
+        );
+    }
+
+    #[test]
+    fn sticky_tiles() {
+        let mut term = Terminal::new([10, 10]);
+        term.put_char([1, 1], 'a');
+        term.put_char([2, 2], 'b');
+        term.set_sticky([1, 1], true);
+        assert!(term.is_sticky([1, 1]));
+        assert!(!term.is_sticky([2, 2]));
+
+        term.clear_non_sticky();
+        assert_eq!('a', term.get_char([1, 1]));
+        assert!(term.is_clear([2, 2]));
+
+        term.set_sticky([1, 1], false);
+        term.clear_non_sticky();
+        assert!(term.is_clear([1, 1]));
+    }
+
+    #[test]
+    fn clear_non_sticky_overlays() {
+        let mut term = Terminal::new([10, 10]);
+        term.put_char([1, 1], 'a');
+        term.put_overlay([1, 1], '_', Color::WHITE);
+        term.put_overlay([2, 2], '_', Color::WHITE);
+        term.set_sticky([1, 1], true);
+        term.print("Hi");
+        term.take_dirty_rect();
+
+        term.clear_non_sticky();
+        assert!(term.overlay([1, 1]).is_some());
+        assert!(term.overlay([2, 2]).is_none());
+        assert_eq!(IVec2::new(0, 9), term.print_cursor());
+        assert!(term.take_dirty_rect().is_some());
+    }
+
+    #[test]
+    fn links() {
+        let mut term = Terminal::new([20, 10]);
+        term.put_link([1, 5], "New Game", "new_game");
+        term.put_link([1, 4], "Quit", "quit");
+
+        assert_eq!("New Game", term.get_string([1, 5], 8));
+        assert_eq!(Some("new_game"), term.link_at([8, 5]));
+        assert_eq!(Some("quit"), term.link_at([1, 4]));
+        assert_eq!(None, term.link_at([5, 4]));
+        assert_eq!(None, term.link_at([0, 5]));
+
+        term.put_link([10, 0], "Quit", "quit");
+        assert_eq!(None, term.link_at([1, 4]));
+        assert_eq!(Some("quit"), term.link_at([13, 0]));
+
+        assert!(term.remove_link("quit"));
+        assert!(!term.remove_link("quit"));
+        assert_eq!(None, term.link_at([13, 0]));
+    }
+
+    #[test]
+    fn links_wrapped() {
+        let mut term = Terminal::new([10, 5]);
+        term.put_link([2, 1], "Start a new game".wrapped(), "new");
+        assert_eq!("Start a", term.get_string([2, 2], 7));
+        assert_eq!(Some("new"), term.link_at([2, 2]));
+        assert_eq!(Some("new"), term.link_at([9, 1]));
+        assert_eq!(None, term.link_at([2, 3]));
+
+        term.put_link([0, 3], "0123456789ab".continue_on_overflow(), "long");
+        assert_eq!(Some("long"), term.link_at([9, 4]));
+        assert_eq!(Some("long"), term.link_at([0, 3]));
+    }
+
+    #[test]
+    fn glyph_offsets() {
+        let mut term = Terminal::new([10, 10]);
+        term.set_glyph_offset([1, 1], Vec2::ZERO);
+        assert!(term.glyph_offsets().is_empty());
+
+        term.set_glyph_offset([1, 1], Vec2::new(0.25, 0.0));
+        assert_eq!(Vec2::new(0.25, 0.0), term.glyph_offset([1, 1]));
+        assert_eq!(Vec2::ZERO, term.glyph_offset([2, 1]));
+        assert_eq!(100, term.glyph_offsets().len());
+
+        term.clear_glyph_offsets();
+        assert_eq!(Vec2::ZERO, term.glyph_offset([1, 1]));
+    }
+
+    #[test]
+    fn put_string_auto_contrast() {
+        let mut term = Terminal::new([20, 20]);
+        term.fill_bg(Color::srgb(0.9, 0.9, 0.8));
+        term.put_string_auto_contrast([0, 0], "Light");
+        term.put_string_auto_contrast([0, 1], "Dark".bg(Color::srgb(0.1, 0.1, 0.3)));
+
+        assert_eq!(Color::BLACK, term.get_tile([0, 0]).fg_color);
+        assert_eq!(Color::WHITE, term.get_tile([0, 1]).fg_color);
+    }
+
+    #[test]
+    fn overlays() {
+        let mut term = Terminal::new([10, 10]);
+        term.put_char([1, 1], 'a');
+        term.put_overlay([1, 1], '`', Color::Srgba(RED));
+        term.put_overlay([3, 1], '^', Color::WHITE);
+
+        assert_eq!(Some(('`', Color::Srgba(RED))), term.overlay([1, 1]));
+        assert_eq!(None, term.overlay([2, 1]));
+        assert_eq!('a', term.get_char([1, 1]));
+
+        term.clear_overlay([3, 1]);
+        assert_eq!(1, term.iter_overlays().count());
+
+        term.clear();
+        assert_eq!(None, term.overlay([1, 1]));
+    }
+
+    #[test]
+    fn resize_fills_clear_tile() {
+        let mut term = Terminal::new([2, 2]).with_clear_tile(Tile::transparent());
+        term.put_char([0, 0], 'a');
+        term.resize([3, 4]);
+
+        assert_eq!(12, term.iter().count());
+        assert!(term.iter().all(|t| *t == Tile::transparent()));
+    }
+
+    #[test]
+    fn zero_size() {
+        let term = Terminal::new([0, 0]);
+        assert_eq!(UVec2::ONE, term.size());
+
+        let mut term = Terminal::new([10, 10]);
+        term.resize([0, 5]);
+        assert_eq!(UVec2::new(1, 5), term.size());
+        term.put_char([0, 4], 'a');
+
+        let err = term.try_resize([0, 5]).unwrap_err();
+        assert_eq!(UVec2::new(0, 5), err.size);
+        assert_eq!(UVec2::new(1, 5), term.size());
+        assert_eq!('a', term.get_char([0, 4]));
+
+        term.try_resize([3, 3]).unwrap();
+        assert_eq!(UVec2::new(3, 3), term.size());
+    }
+
+    #[test]
+    fn depths() {
+        let mut term = Terminal::new([10, 10]);
+        term.set_depth([1, 1], 0.0);
+        assert!(term.depths().is_empty());
+
+        term.set_depth([1, 1], 2.5);
+        assert_eq!(2.5, term.depth([1, 1]));
+        assert_eq!(0.0, term.depth([2, 1]));
+
+        term.clear_depths();
+        assert!(term.depths().is_empty());
+    }
+
+    #[test]
+    fn tints() {
+        let mut term = Terminal::new([10, 10]);
+        term.set_tint([1, 1], 0.0);
+        assert!(term.tints().is_empty());
+
+        term.set_tint([1, 1], 0.5);
+        term.set_tint([2, 1], 3.0);
+        assert_eq!(0.5, term.tint([1, 1]));
+        assert_eq!(1.0, term.tint([2, 1]));
+        assert_eq!(0.0, term.tint([3, 1]));
+
+        term.clear_tints();
+        assert_eq!(0.0, term.tint([1, 1]));
+    }
+
+    #[test]
+    fn clear_keeps_tile_settings() {
+        let mut term = Terminal::new([4, 4]);
+        term.set_tint([1, 1], 0.5);
+        term.set_depth([1, 1], 2.0);
+        term.put_overlay([1, 1], '_', Color::WHITE);
+
+        term.clear();
+        assert_eq!(0.5, term.tint([1, 1]));
+        assert_eq!(2.0, term.depth([1, 1]));
+        assert_eq!(None, term.overlay([1, 1]));
+
+        term.resize([6, 6]);
+        assert_eq!(0.0, term.tint([1, 1]));
+        term.set_tint([5, 5], 1.0);
+        assert_eq!(36, term.tints().len());
+    }
+
+    #[test]
+    fn downscale_average() {
+        let mut src = Terminal::new([8, 8]);
+        // Bottom left 4x4 block: half red, half blue backgrounds
+        for y in 0..4 {
+            for x in 0..4 {
+                let color = if x < 2 {
+                    Color::srgb(1.0, 0.0, 0.0)
+                } else {
+                    Color::srgb(0.0, 0.0, 1.0)
+                };
+                src.put_char([x, y], '#'.bg(color));
+            }
+        }
+        src.put_char([0, 0], '.');
+        let mut dest = Terminal::new([2, 2]);
+
+        src.downscale_into(&mut dest, DownscaleMode::AverageColor);
+
+        let t = dest.get_tile([0, 0]);
+        assert_eq!('#', t.glyph);
+        assert!(color_approx_eq(
+            Color::linear_rgb(0.5, 0.0, 0.5),
+            t.bg_color
+        ));
+        assert!(dest.is_clear([1, 1]));
+    }
+
+    #[test]
+    fn downscale_dominant() {
+        let mut src = Terminal::new([4, 4]);
+        src.put_char([0, 0], 'a'.fg(Color::Srgba(RED)));
+        src.put_char([1, 0], 'b');
+        src.put_char([0, 1], 'a');
+        let mut dest = Terminal::new([2, 2]);
+
+        src.downscale_into(&mut dest, DownscaleMode::DominantGlyph);
+
+        assert_eq!('a', dest.get_char([0, 0]));
+        assert_eq!(Color::Srgba(RED), dest.get_tile([0, 0]).fg_color);
+        assert_eq!(' ', dest.get_char([1, 0]));
+    }
+
+    #[test]
+    fn to_rgba_image() {
+        let mut term = Terminal::new([3, 2]);
+        term.clear_tile.bg_color = Color::BLACK;
+        term.clear();
+        term.put_char([0, 1], 'a'.bg(Color::Srgba(RED)).fg(Color::WHITE));
+        term.put_char([2, 0], 'b'.bg(Color::WHITE));
+
+        let image = term.to_rgba_image(ColorSource::Background);
+        assert_eq!(UVec2::new(3, 2), image.size());
+        // Top left
+        assert_eq!([255, 0, 0, 255], px_at(&image, 0));
+        assert_eq!([0, 0, 0, 255], px_at(&image, 1));
+        // Bottom right
+        assert_eq!([255, 255, 255, 255], px_at(&image, 5));
+
+        let image = term.to_rgba_image(ColorSource::Foreground);
+        assert_eq!([255, 255, 255, 255], px_at(&image, 0));
+    }
+
+    fn px_at(image: &Image, i: usize) -> [u8; 4] {
+        image.data[i * 4..i * 4 + 4].try_into().unwrap()
+    }
+
+    #[test]
+    fn put_string_cursor() {
+        let mut term = Terminal::new([20, 10]);
+        assert_eq!(IVec2::new(6, 5), term.put_string_cursor([1, 5], "Hello"));
+        assert_eq!(
+            IVec2::new(7, 4),
+            term.put_string_cursor([1, 5], "Hello\nWorld!")
+        );
+        assert_eq!(IVec2::new(1, 4), term.put_string_cursor([1, 5], "Hello\n"));
+        assert_eq!(IVec2::new(1, 5), term.put_string_cursor([1, 5], ""));
+        assert_eq!(
+            IVec2::new(20, 9),
+            term.put_string_cursor([0, 0].pivot(Pivot::TopRight), "Hi")
+        );
+    }
+
+    #[test]
+    fn put_string_masked() {
+        let mut term = Terminal::new([20, 10]);
+        let center = IVec2::new(10, 5);
+        let radius = 3;
+        let text = "abcdefghijklmnopqrst";
+        for y in 0..10 {
+            term.put_string_masked([0, y], text, |p| {
+                (p - center).length_squared() <= radius * radius
+            });
+        }
+
+        for p in term.iter_bounds_positions() {
+            let inside = (p - center).length_squared() <= radius * radius;
+            assert_eq!(inside, !term.is_clear(p), "{}", p);
+        }
+        assert_eq!("hijklmn", term.get_string([7, 5], 7));
+    }
+
+    #[test]
+    fn put_string_pivoted() {
+        for pivot in [Pivot::TopLeft, Pivot::Center, Pivot::BottomRight] {
+            let mut a = Terminal::new([12, 6]);
+            let mut b = Terminal::new([12, 6]);
+            a.put_string_pivoted([1, 1], pivot, "Hello\nWorld!");
+            b.put_string([1, 1].pivot(pivot), "Hello\nWorld!");
+            assert_eq!(a.slice(), b.slice());
+        }
+    }
+
+    #[test]
+    fn println_scrolls() {
+        let mut term = Terminal::new([4, 3]);
+        for line in ["a", "b", "c", "d"] {
+            term.println(line);
+        }
+        assert_eq!("c   ", term.get_string([0, 2], 4));
+        assert_eq!("d   ", term.get_string([0, 1], 4));
+        assert_eq!("    ", term.get_string([0, 0], 4));
+        assert_eq!(IVec2::new(0, 0), term.print_cursor());
+
+        // Long lines wrap
+        term.print("wxyz12");
+        assert_eq!("d   ", term.get_string([0, 2], 4));
+        assert_eq!("wxyz", term.get_string([0, 1], 4));
+        assert_eq!("12  ", term.get_string([0, 0], 4));
+        assert_eq!(IVec2::new(2, 0), term.print_cursor());
+
+        term.clear();
+        assert_eq!(IVec2::new(0, 2), term.print_cursor());
+    }
+
+    #[test]
+    fn zebra_stripes() {
+        let [even, odd] = [Color::srgb(0.1, 0.1, 0.1), Color::srgb(0.2, 0.2, 0.2)];
+        let mut term = Terminal::new([4, 6]).with_zebra_stripes(even, odd);
+        term.println("a");
+        term.print("b\nc\n");
+        // Wrapped rows belong to the same line
+        term.println("wxyz12");
+
+        let row_bg = |term: &Terminal, y: i32| {
+            let bg = term.get_tile([0, y]).bg_color;
+            assert!((0..4).all(|x| term.get_tile([x, y]).bg_color == bg));
+            bg
+        };
+        assert_eq!(even, row_bg(&term, 5));
+        assert_eq!(odd, row_bg(&term, 4));
+        assert_eq!(even, row_bg(&term, 3));
+        assert_eq!(odd, row_bg(&term, 2));
+        assert_eq!(odd, row_bg(&term, 1));
+        // The next line is striped as soon as it's started
+        assert_eq!(even, row_bg(&term, 0));
+
+        // New lines scrolling in continue to alternate
+        term.println("d");
+        assert_eq!("d   ", term.get_string([0, 1], 4));
+        assert_eq!(even, row_bg(&term, 1));
+        assert_eq!(odd, row_bg(&term, 0));
+    }
+
+    #[test]
+    fn scroll_region() {
+        let mut term = Terminal::new([6, 5]).with_scroll_region(GridRect::from_bl([0, 1], [6, 3]));
+        term.put_string([0, 4], "HEADER");
+        term.put_string([0, 0], "FOOTER");
+        assert_eq!(IVec2::new(0, 3), term.print_cursor());
+        for line in ["a", "b", "c", "d"] {
+            term.println(line);
+        }
+        assert_eq!("HEADER", term.get_string([0, 4], 6));
+        assert_eq!("c     ", term.get_string([0, 3], 6));
+        assert_eq!("d     ", term.get_string([0, 2], 6));
+        assert_eq!("      ", term.get_string([0, 1], 6));
+        assert_eq!("FOOTER", term.get_string([0, 0], 6));
+
+        // Partial width regions leave the columns around them untouched
+        term.set_scroll_region(Some(GridRect::from_bl([1, 1], [3, 3])));
+        term.put_string([0, 3], "xyzwvu");
+        term.scroll_up(1);
+        assert_eq!("HEADER", term.get_string([0, 4], 6));
+        assert_eq!("x   vu", term.get_string([0, 3], 6));
+        assert_eq!("d     ", term.get_string([0, 2], 6));
+        assert_eq!("      ", term.get_string([0, 1], 6));
+        assert_eq!("FOOTER", term.get_string([0, 0], 6));
+
+        // Printing wraps at the edge of the region
+        term.print("12345");
+        assert_eq!("x123vu", term.get_string([0, 3], 6));
+        assert_eq!("d45   ", term.get_string([0, 2], 6));
+    }
+
+    #[test]
+    fn put_rich() {
+        let mapping = UvMapping::default().with_icon("heart", '♥');
+        let mut term = Terminal::new([20, 2]);
+        term.put_rich([0, 0], "HP [heart] 10".fg(Color::Srgba(RED)), &mapping);
+        assert_eq!("HP ♥ 10", term.get_string([0, 0], 7));
+        assert_eq!('♥', term.get_char([3, 0]));
+        assert_eq!(Color::Srgba(RED), term.get_tile([3, 0]).fg_color);
+
+        term.put_rich([0, 1], "[[heart]] [mana]", &mapping);
+        assert_eq!("[♥] [mana]", term.get_string([0, 1], 10));
+    }
+
+    #[test]
+    fn recolor_content_bg() {
+        let mut term = Terminal::new([5, 1]);
+        term.put_string([0, 0], "ab");
+        term.recolor_content_bg(Color::Srgba(RED));
+        assert_eq!(Color::Srgba(RED), term.get_tile([0, 0]).bg_color);
+        assert_eq!(Color::Srgba(RED), term.get_tile([1, 0]).bg_color);
+        assert_eq!(Tile::DEFAULT_BGCOL, term.get_tile([2, 0]).bg_color);
+        assert!(term.is_clear([4, 0]));
+    }
+
+    #[test]
+    fn convert_pivot() {
+        let term = Terminal::new([5, 5]);
+        let xy = term.convert_pivot([1, -1], Pivot::Center, Pivot::BottomLeft);
+        assert_eq!(IVec2::new(3, 1), xy);
+        assert_eq!(
+            IVec2::new(3, 1),
+            term.local_point([1, -1].pivot(Pivot::Center))
+        );
+
+        let xy = term.convert_pivot([0, 0], Pivot::TopLeft, Pivot::BottomLeft);
+        assert_eq!(IVec2::new(0, 4), xy);
+
+        // Round trips through any pivot
+        for pivot in [
+            Pivot::TopLeft,
+            Pivot::TopRight,
+            Pivot::Center,
+            Pivot::BottomRight,
+        ] {
+            let p = term.convert_pivot([3, 1], Pivot::BottomLeft, pivot);
+            assert_eq!(
+                IVec2::new(3, 1),
+                term.convert_pivot(p, pivot, Pivot::BottomLeft)
+            );
+        }
+    }
+
+    #[test]
+    fn default_pivot() {
+        let mut term = Terminal::new([5, 5]).with_default_pivot(Pivot::Center);
+        term.put_char([0, 0], '@');
+        term.put_char([-1, 0], 'a');
+        assert_eq!("a@", term.get_string([1, 2], 2));
+        assert_eq!(IVec2::new(3, 1), term.local_point([1, -1]));
+
+        // Strings are centered on the position
+        term.put_string([0, 1], "abc");
+        assert_eq!("abc", term.get_string([1, 3], 3));
+
+        // A pivot on the position takes priority
+        term.put_char([0, 0].pivot(Pivot::BottomLeft), 'x');
+        assert_eq!('x', term.get_char([0, 0]));
+
+        term.set_default_pivot(None);
+        term.put_char([0, 0], 'y');
+        assert_eq!('y', term.get_char([0, 0]));
+    }
+
+    #[test]
+    fn put_tile_parts() {
+        let mut term = Terminal::new([5, 5]);
+        term.put_tile_parts([2, 3], 'x', Color::Srgba(RED), Color::WHITE);
+        let tile = term.get_tile([2, 3]);
+        assert_eq!('x', tile.glyph);
+        assert_eq!(Color::Srgba(RED), tile.fg_color);
+        assert_eq!(Color::WHITE, tile.bg_color);
     }
 
-    /// Clear an area of the terminal to the terminal's `clear_tile`.
-    pub fn clear_box(&mut self, xy: impl GridPoint, size: impl Size2d) {
-        let [width, height] = size.as_array();
-        let [x, y] = xy.as_array();
-        for y in y..y + height as i32 {
-            for x in x..x + width as i32 {
-                self.put_tile([x, y], self.clear_tile);
-            }
-        }
+    #[test]
+    fn put_string_truncated() {
+        let mut term = Terminal::new([5, 2]);
+        assert!(!term.put_string_truncated([0, 0], "Hello"));
+        assert!(!term.put_string_truncated([2, 1], "abc"));
+        assert_eq!("Hello", term.get_string([0, 0], 5));
+
+        term.clear();
+        assert!(term.put_string_truncated([0, 0], "Hello!"));
+        assert_eq!("Hello", term.get_string([0, 0], 5));
+        assert!(term.put_string_truncated([3, 0], "xyz"));
+        assert_eq!("Helxy", term.get_string([0, 0], 5));
+        // Nothing wrapped onto the next line
+        assert_eq!("     ", term.get_string([0, 1], 5));
+
+        assert!(!term.put_string_truncated([0, 0].pivot(Pivot::TopRight), "a\nbc"));
+        assert!(term.put_string_truncated([0, 0].pivot(Pivot::TopRight), "abcdef"));
     }
 
-    /// Clear the terminal tiles to the terminal's `clear_tile`.
-    pub fn clear(&mut self) {
-        for t in self.tiles.iter_mut() {
-            *t = self.clear_tile
+    #[test]
+    fn put_string_rtl() {
+        let mut term = Terminal::new([10, 3]);
+        term.put_string_rtl([0, 0].pivot(Pivot::TopRight), "abc\nde");
+        // Both lines end at the right edge and read right to left
+        assert_eq!("   cba", term.get_string([4, 2], 6));
+        assert_eq!("    ed", term.get_string([4, 1], 6));
+
+        term.clear();
+        term.put_string_rtl([5, 0], "xy\nz");
+        // The bottom line starts at the given position
+        assert_eq!("yx", term.get_string([4, 1], 2));
+        assert_eq!(" z", term.get_string([4, 0], 2));
+    }
+
+    #[test]
+    fn put_string_paged() {
+        let mut term = Terminal::new([5, 2]);
+        let rest = term
+            .put_string_paged([0, 1], "aaaa bbbb cccc dd".fg(Color::Srgba(RED)))
+            .unwrap();
+        assert_eq!("aaaa ", term.get_string([0, 1], 5));
+        assert_eq!("bbbb ", term.get_string([0, 0], 5));
+        assert_eq!("cccc dd", rest.string());
+
+        // The color continues on the next page
+        term.clear();
+        assert!(term.put_string_paged([0, 1], rest).is_none());
+        assert_eq!("cccc ", term.get_string([0, 1], 5));
+        assert_eq!("dd   ", term.get_string([0, 0], 5));
+        assert_eq!(Color::Srgba(RED), term.get_tile([0, 1]).fg_color);
+        assert_eq!(Color::Srgba(RED), term.get_tile([1, 0]).fg_color);
+        assert_eq!(Tile::DEFAULT_FGCOL, term.get_tile([2, 0]).fg_color);
+    }
+
+    #[test]
+    fn put_string_flowed() {
+        let mut term = Terminal::new([10, 3]);
+        term.put_string([4, 2], "##");
+        term.put_string([4, 1], "##");
+
+        let unplaced = term.put_string_flowed([0, 2], "aa bb cc dd");
+        assert_eq!(0, unplaced);
+        assert_eq!("aa  ##bb  ", term.get_string([0, 2], 10));
+        assert_eq!("cc  ##dd  ", term.get_string([0, 1], 10));
+
+        // Too many words to fit
+        let unplaced = term.put_string_flowed([0, 0], "abcd efgh ijk lm");
+        assert_eq!("abcd efgh ", term.get_string([0, 0], 10));
+        assert_eq!(2, unplaced);
+    }
+
+    #[test]
+    fn put_string_path() {
+        let mut term = Terminal::new([10, 10]);
+        let path = [[1, 1], [2, 2], [2, 3], [3, 3], [4, 2]].map(IVec2::from);
+        term.put_string_path(&path, "Snake".fg(Color::Srgba(RED)));
+
+        for (p, ch) in path.iter().zip("Snake".chars()) {
+            assert_eq!(ch, term.get_char(*p));
+            assert_eq!(Color::Srgba(RED), term.get_tile(*p).fg_color);
         }
+        assert_eq!(5, term.iter().filter(|t| t.glyph != ' ').count());
+
+        // Clipped and stops at the end of the path
+        term.clear();
+        term.put_string_path(&[IVec2::new(-1, 0), IVec2::new(0, 0)], "abc");
+        assert_eq!("b ", term.get_string([0, 0], 2));
     }
 
-    pub fn clear_line(&mut self, line: usize) {
-        let tile = self.clear_tile;
-        self.iter_row_mut(line).for_each(|t| *t = tile);
+    #[test]
+    fn put_string_highlighted() {
+        let mut term = Terminal::new([20, 10]);
+        let hl = Color::Srgba(RED);
+        term.put_string_highlighted([0, 0], "cat dog cat", "cat", hl);
+
+        let bg: Vec<_> = term
+            .iter_row(0)
+            .take(11)
+            .map(|t| t.bg_color == hl)
+            .collect();
+        let expected: Vec<_> = "cat dog cat".chars().map(|c| "cat".contains(c)).collect();
+        assert_eq!(expected, bg);
+
+        // Overlapping matches
+        term.put_string_highlighted([0, 1], "aaab", "aa", hl);
+        assert!(term.iter_row(1).take(3).all(|t| t.bg_color == hl));
+        assert_ne!(hl, term.get_tile([3, 1]).bg_color);
     }
 
-    /// Returns true if the given position is inside the bounds of the terminal.
-    #[inline]
-    pub fn in_bounds(&self, xy: impl GridPoint) -> bool {
-        self.tiles.in_bounds(xy)
+    #[test]
+    fn iter_neighbors() {
+        let term = Terminal::new([5, 5]);
+        assert_eq!(8, term.iter_neighbors([2, 2], true).count());
+        assert_eq!(4, term.iter_neighbors([2, 2], false).count());
+        // Edge
+        assert_eq!(5, term.iter_neighbors([0, 2], true).count());
+        assert_eq!(3, term.iter_neighbors([0, 2], false).count());
+        // Corner
+        assert_eq!(3, term.iter_neighbors([4, 4], true).count());
+        assert!(term
+            .iter_neighbors([4, 4], true)
+            .all(|(p, _)| term.in_bounds(p) && p != IVec2::new(4, 4)));
     }
 
-    /// An immutable iterator over the tiles of the terminal.
-    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Tile> {
-        self.tiles.iter()
+    #[test]
+    fn cellular_automata_converges() {
+        use rand::{rngs::StdRng, Rng, SeedableRng};
+
+        let mut rng = StdRng::seed_from_u64(7);
+        let mut term = Terminal::new([60, 40]);
+        for t in term.iter_mut() {
+            t.glyph = if rng.gen_bool(0.45) { '#' } else { '.' };
+        }
+
+        let first = term.cellular_automata_step('#', '.', 5, 4);
+        let mut last = first;
+        for _ in 0..5 {
+            last = term.cellular_automata_step('#', '.', 5, 4);
+        }
+        assert!(last < first / 4, "{} changes, then {}", first, last);
+
+        // Noise has been smoothed into open caves surrounded by walls
+        let walls = term.count_glyph('#') as f32 / term.slice().len() as f32;
+        assert!((0.2..0.8).contains(&walls), "{}", walls);
+        // Tiles on the edge count walls outside the terminal as neighbors
+        let interior = |p: &IVec2| p.cmpgt(IVec2::ZERO).all() && p.cmplt(IVec2::new(59, 39)).all();
+        let lonely_walls = term
+            .iter_bounds_positions()
+            .filter(interior)
+            .filter(|p| term.get_char(*p) == '#')
+            .filter(|p| term.iter_neighbors(*p, true).all(|(_, t)| t.glyph != '#'))
+            .count();
+        assert_eq!(0, lonely_walls);
     }
 
-    /// A mutable iterator over the tiles of the terminal.
-    pub fn iter_mut(&mut self) -> impl DoubleEndedIterator<Item = &mut Tile> {
-        self.tiles.iter_mut()
+    #[test]
+    fn iter_perimeter_positions() {
+        let term = Terminal::new([4, 3]);
+        let mut edges: Vec<_> = term.iter_perimeter_positions().collect();
+        edges.sort_by_key(|p| (p.y, p.x));
+
+        let expected: Vec<_> = term
+            .iter_bounds_positions()
+            .filter(|p| p.x == 0 || p.y == 0 || p.x == 3 || p.y == 2)
+            .collect();
+        assert_eq!(expected, edges);
+        assert_eq!(10, edges.len());
+
+        let line: Vec<_> = Terminal::iter_box_perimeter([1, 1], [3, 1]).collect();
+        assert_eq!(3, line.len());
     }
 
-    /// An immutable iterator over an entire row of tiles in the terminal.
-    pub fn iter_row(&self, y: usize) -> impl DoubleEndedIterator<Item = &Tile> {
-        self.tiles.iter_row(y)
+    #[test]
+    fn swap_with() {
+        let mut front = Terminal::new([5, 5]);
+        let mut back = Terminal::new([5, 5]);
+        front.put_char([1, 1], 'a');
+        back.put_char([2, 2], 'b');
+
+        front.swap_with(&mut back);
+
+        assert_eq!('b', front.get_char([2, 2]));
+        assert_eq!(' ', front.get_char([1, 1]));
+        assert_eq!('a', back.get_char([1, 1]));
+        assert_eq!(' ', back.get_char([2, 2]));
+
+        // Effects stay with their tiles
+        back.put_overlay([2, 2], '_', Color::WHITE);
+        back.set_tint([2, 2], 0.5);
+        front.swap_with(&mut back);
+        assert_eq!(Some(('_', Color::WHITE)), front.overlay([2, 2]));
+        assert_eq!(0.5, front.tint([2, 2]));
+        assert!(back.overlay([2, 2]).is_none());
+        assert_eq!(0.0, back.tint([2, 2]));
     }
 
-    /// An immutable iterator over an entire row of tiles in the terminal.
-    pub fn iter_row_mut(&mut self, y: usize) -> impl DoubleEndedIterator<Item = &mut Tile> {
-        self.tiles.iter_row_mut(y)
+    #[test]
+    fn put_formatted() {
+        let mut term = Terminal::new([10, 10]);
+        let style = 'w'.fg(Color::Srgba(RED)).bg(Color::WHITE);
+
+        term.put_formatted([1, 1], &style);
+        term.put_formatted([4, 2], &style);
+        term.put_formatted([7, 9], &style);
+
+        let expected = Tile::from(style);
+        assert_eq!(expected, *term.get_tile([1, 1]));
+        assert_eq!(expected, *term.get_tile([4, 2]));
+        assert_eq!(expected, *term.get_tile([7, 9]));
     }
 
-    /// An immutable iterator over a range of rows in the terminal.
-    ///
-    /// The iterator moves along each row from left to right, where 0 is the
-    /// bottom row and `height - 1` is the top row.
-    pub fn iter_rows(
-        &self,
-        range: impl RangeBounds<usize>,
-    ) -> impl DoubleEndedIterator<Item = &[Tile]> {
-        self.tiles.iter_rows(range)
+    #[test]
+    fn draw_text_box() {
+        let mut term = Terminal::new([20, 10]);
+        let border = Border::single_line().with_title("Hi");
+        let clipped = term.draw_text_box([0, 0], [10, 5], &border, "one two three four five");
+
+        // Title is drawn on the top border
+        assert_eq!("┌Hi──────┐", term.get_string([0, 4], 10));
+        // Body is wrapped within the inner area with padding
+        assert_eq!("│ one    │", term.get_string([0, 3], 10));
+        assert_eq!("│ two    │", term.get_string([0, 2], 10));
+        assert_eq!("│ three  │", term.get_string([0, 1], 10));
+        assert_eq!("└────────┘", term.get_string([0, 0], 10));
+        assert_eq!(2, clipped);
     }
 
-    /// A mutable iterator over a range of rows in the terminal.
-    ///
-    /// The iterator moves along each row from left to right, where 0 is the
-    /// bottom row and `height - 1` is the top row.
-    pub fn iter_rows_mut(
-        &mut self,
-        range: impl RangeBounds<usize>,
-    ) -> impl DoubleEndedIterator<Item = &mut [Tile]> {
-        self.tiles.iter_rows_mut(range)
+    #[test]
+    fn draw_text_box_aligned() {
+        let mut term = Terminal::new([20, 10]);
+        let border = Border::single_line();
+        term.draw_text_box_aligned([0, 0], [10, 6], &border, "one two", VerticalAlign::Center);
+
+        assert_eq!("│        │", term.get_string([0, 4], 10));
+        assert_eq!("│ one    │", term.get_string([0, 3], 10));
+        assert_eq!("│ two    │", term.get_string([0, 2], 10));
+        assert_eq!("│        │", term.get_string([0, 1], 10));
+
+        term.draw_text_box_aligned([0, 0], [10, 6], &border, "one two", VerticalAlign::Bottom);
+        assert_eq!("│        │", term.get_string([0, 3], 10));
+        assert_eq!("│ one    │", term.get_string([0, 2], 10));
+        assert_eq!("│ two    │", term.get_string([0, 1], 10));
     }
 
-    /// An immutable iterator over an entire column of tiles in the terminal.
-    ///
-    /// The iterator moves from bottom to top.
-    pub fn iter_column(&self, x: usize) -> impl DoubleEndedIterator<Item = &Tile> {
-        self.tiles.iter_column(x)
+    #[test]
+    fn tile_approx_eq() {
+        let a = Tile::default();
+        let mut b = a;
+        b.fg_color = Color::srgba(1.0, 1.0, 0.9999, 1.0);
+        assert_ne!(a, b);
+        assert!(a.approx_eq(&b));
+
+        b.fg_color = Color::srgb(0.5, 0.5, 0.5);
+        assert!(!a.approx_eq(&b));
+
+        let mut t = Tile::transparent();
+        t.bg_color = Color::srgba(1.0, 0.0, 0.0, 0.0);
+        assert!(t.approx_eq(&Tile::transparent()));
     }
 
-    /// A mutable iterator over an entire column of tiles in the terminal.
-    ///
-    /// The iterator moves from bottom to top.
-    pub fn iter_column_mut(&mut self, x: usize) -> impl DoubleEndedIterator<Item = &mut Tile> {
-        self.tiles.iter_column_mut(x)
+    #[test]
+    fn is_clear() {
+        let mut term = Terminal::new([5, 5]);
+        assert!(term.is_clear([1, 1]));
+
+        term.get_tile_mut([1, 1]).bg_color = Color::srgba(0.0, 0.0, 0.0001, 1.0);
+        assert!(term.is_clear([1, 1]));
+
+        term.put_char([1, 1], 'a');
+        assert!(!term.is_clear([1, 1]));
     }
 
-    /// Get the index for a given side on the terminal.
-    pub fn side_index(&self, side: Side) -> usize {
-        self.tiles.side_index(side)
+    #[test]
+    fn invert_colors() {
+        let mut term = Terminal::new([10, 5]);
+        term.put_string([0, 2], "Hello".fg(Color::Srgba(RED)));
+        let rect = GridRect::from_bl([0, 2], [10, 1]);
+
+        term.invert_colors(rect);
+        let t = term.get_tile([1, 2]);
+        assert_eq!(Color::Srgba(RED), t.bg_color);
+        assert_eq!(Tile::DEFAULT_BGCOL, t.fg_color);
+        assert_eq!(Tile::DEFAULT_FGCOL, term.get_tile([1, 1]).fg_color);
+
+        term.invert_colors(rect);
+        let t = term.get_tile([1, 2]);
+        assert_eq!(Color::Srgba(RED), t.fg_color);
+        assert_eq!(Tile::DEFAULT_BGCOL, t.bg_color);
+
+        // Clipped to the terminal bounds
+        term.invert_colors(GridRect::from_bl([-5, -5], [20, 20]));
+        assert_eq!(Tile::DEFAULT_FGCOL, term.get_tile([9, 4]).bg_color);
     }
 
-    /// Transform a position from terminal local space (origin bottom left) to
-    /// world space (origin center).
-    #[inline]
-    pub fn transform_ltw(&self, pos: impl GridPoint) -> IVec2 {
-        pos.as_ivec2() - self.size.as_ivec2().sub(1).div(2)
+    #[test]
+    fn blit() {
+        let mut root = Terminal::new([6, 3]);
+        let mut panel = Terminal::new([3, 2]);
+        panel.put_string([0, 1], "abc");
+        panel.put_string([0, 0], "def");
+
+        // Clipped against the right edge
+        root.blit([4, 1], &panel);
+        assert_eq!("    ab", root.get_string([0, 2], 6));
+        assert_eq!("    de", root.get_string([0, 1], 6));
+        assert_eq!("      ", root.get_string([0, 0], 6));
+
+        // Clipped against the bottom left corner
+        root.blit([-1, -1], &panel);
+        assert_eq!("bc    ", root.get_string([0, 0], 6));
+
+        // Skipped tiles leave the destination untouched
+        panel.put_tile([1, 1], Tile::transparent());
+        root.blit_with([0, 1], &panel, Tile::is_invisible);
+        assert_eq!("a c ab", root.get_string([0, 2], 6));
+        assert_eq!("def de", root.get_string([0, 1], 6));
     }
 
-    /// Transform a position from world space (origin center) to terminal local
-    /// space (origin bottom left).
-    #[inline]
-    pub fn transform_wtl(&self, pos: impl GridPoint) -> IVec2 {
-        //println!("P {}, Half size {}", pos.as_ivec2(),  self.size.as_ivec2().sub(1).div(2));
-        pos.as_ivec2() + self.size.as_ivec2().div(2)
+    #[test]
+    fn clone_region() {
+        let mut term = Terminal::new([10, 5])
+            .with_clear_tile(Tile {
+                bg_color: Color::Srgba(RED),
+                ..Default::default()
+            })
+            .with_border(Border::single_line());
+        term.put_string([1, 1], "ab");
+        term.put_string([1, 2], "cd".fg(Color::Srgba(RED)));
+
+        let region = term.clone_region(GridRect::from_bl([1, 1], [2, 2]));
+        assert_eq!(UVec2::new(2, 2), region.size());
+        assert!(region.border().is_none());
+        assert_eq!("ab", region.get_string([0, 0], 2));
+        assert_eq!("cd", region.get_string([0, 1], 2));
+        assert_eq!(term.get_tile([2, 2]), region.get_tile([1, 1]));
+
+        // Outside the terminal is filled with the clear tile
+        let region = term.clone_region(GridRect::from_bl([9, 0], [2, 1]));
+        assert_eq!(term.clear_tile, *region.get_tile([1, 0]));
     }
 
-    pub fn slice(&self) -> &[Tile] {
-        self.tiles.slice()
+    #[test]
+    fn put_border_string() {
+        let mut term = Terminal::new([10, 5]);
+        term.put_border_string(Edge::Bottom, "3/7");
+        assert!(term.border().is_none());
+
+        term.set_border(Border::single_line());
+        term.put_border_string(Edge::Bottom, "3/7".aligned(1.0));
+        let string = term.border().unwrap().edge_string(Edge::Bottom).unwrap();
+        assert_eq!("3/7", string.string);
+        assert_eq!(1.0, string.align);
     }
 
-    pub fn slice_mut(&mut self) -> &mut [Tile] {
-        self.tiles.slice_mut()
+    #[test]
+    fn glyph_histogram() {
+        let mut term = Terminal::new([5, 2]);
+        term.put_string([0, 0], "aab");
+        term.put_string([0, 1], "#####");
+
+        assert_eq!(2, term.count_glyph('a'));
+        assert_eq!(1, term.count_glyph('b'));
+        assert_eq!(0, term.count_glyph('z'));
+
+        let counts = term.glyph_histogram();
+        assert_eq!(4, counts.len());
+        assert_eq!(Some(&2), counts.get(&'a'));
+        assert_eq!(Some(&1), counts.get(&'b'));
+        assert_eq!(Some(&5), counts.get(&'#'));
+        assert_eq!(Some(&2), counts.get(&' '));
     }
 
-    pub fn bounds_with_border(&self) -> GridRect {
-        let bounds = self.bounds();
-        if self.has_border() {
-            bounds.resized([1, 1])
-        } else {
-            bounds
+    #[test]
+    fn shade() {
+        let mut term = Terminal::new([4, 4]).with_clear_tile(
+            ' '.fg(Color::srgb(1.0, 0.5, 0.2))
+                .bg(Color::srgb(0.8, 0.4, 0.0)),
+        );
+        term.shade(&[0.5; 16]);
+
+        for t in term.iter() {
+            assert!(color_approx_eq(t.fg_color, Color::srgb(0.5, 0.25, 0.1)));
+            assert!(color_approx_eq(t.bg_color, Color::srgb(0.4, 0.2, 0.0)));
         }
+
+        term.shade(&[4.0; 16]);
+        let t = term.get_tile([0, 0]);
+        assert!(color_approx_eq(t.fg_color, Color::srgb(1.0, 1.0, 0.4)));
     }
 
-    pub fn bounds(&self) -> GridRect {
-        let mut bounds = self.tiles.bounds();
-        bounds.center -= self.size.as_ivec2() / 2;
-        //println!("TERM BOUNDS {}", bounds);
-        bounds
+    #[test]
+    fn put_icon() {
+        let mapping = UvMapping::default().with_icon(":skull:", '\u{E000}');
+        let mut term = Terminal::new([5, 5]).with_default_pivot(Pivot::TopLeft);
+        term.take_dirty_rect();
+        term.put_icon([1, 1], ":skull:", &mapping);
+        term.put_icon([2, 1], ":missing:", &mapping);
+
+        // Positions use the default pivot like any other write
+        assert_eq!('\u{E000}', term.get_char([1, 3]));
+        assert_eq!(Terminal::FALLBACK_GLYPH, term.get_char([2, 3]));
+        assert!(term.take_dirty_rect().is_some());
     }
-}
- 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use bevy::color::palettes::basic::RED;
 
     #[test]
-    fn put_char() {
-        let mut term = Terminal::new([20, 20]);
+    fn bresenham_visible() {
+        let mut term = Terminal::new([10, 10]);
+        term.put_char([5, 2], '#');
+        let is_wall = |p: IVec2| term.get_char(p) == '#';
 
-        term.put_char([5, 5], 'h');
+        assert!(!term.bresenham_visible([0, 2], [9, 2], is_wall));
+        assert!(term.bresenham_visible([0, 4], [9, 4], is_wall));
+        // End points aren't tested
+        assert!(term.bresenham_visible([0, 2], [5, 2], is_wall));
+    }
 
-        assert_eq!('h', term.get_char([5, 5]));
+    #[test]
+    fn fill_colors() {
+        let mut term = Terminal::new([5, 5]);
+        term.put_string([0, 0], "Hello");
+        term.fill_bg(Color::Srgba(RED));
+        term.fill_fg(Color::BLACK);
 
-        term.put_char([1, 2], 'q'.fg(Color::Srgba(RED)));
+        assert_eq!("Hello", term.get_string([0, 0], 5));
+        assert!(term
+            .iter()
+            .all(|t| t.bg_color == Color::Srgba(RED) && t.fg_color == Color::BLACK));
+    }
 
-        let t = term.get_tile([1, 2]);
-        assert_eq!('q', t.glyph);
-        assert_eq!(Color::Srgba(RED), t.fg_color);
+    #[test]
+    fn wrapped_line_starts() {
+        let term = Terminal::new([8, 5]);
+        let text = "The quick brown fox\njumps";
+        // "The" "quick" "brown" "fox" "jumps"
+        assert_eq!(
+            vec![0, 4, 10, 16, 20],
+            term.wrapped_line_starts([0, 0], text)
+        );
+        // Wrapped to 5 columns from x = 3
+        assert_eq!(
+            vec![0, 4, 10, 16, 20],
+            term.wrapped_line_starts([3, 0], text)
+        );
+        // Character indices, not bytes
+        assert_eq!(
+            vec![0, 4, 8],
+            term.wrapped_line_starts([4, 0], "ab\u{e9}defghi")
+        );
     }
 
     #[test]
-    fn put_string() {
-        let mut term = Terminal::new([20, 20]);
-        // term.put_string([0, 0], "Hello");
-        // assert_eq!("Hello", term.get_string([0, 0], 5));
+    fn try_get_tile() {
+        let mut term = Terminal::new([5, 5]);
+        assert!(term.try_get_tile([4, 4]).is_some());
+        assert!(term.try_get_tile([5, 0]).is_none());
+        assert!(term.try_get_tile([0, -1]).is_none());
+        assert!(term.try_get_tile_mut([-1, 2]).is_none());
+        term.try_get_tile_mut([1, 2]).unwrap().glyph = 'a';
+        assert_eq!('a', term.get_char([1, 2]));
 
-        term.put_string([1, 1], "Hello");
-        assert_eq!("He", term.get_string([1, 1], 2));
+        assert!(term.try_put_char([0, 0], 'b'));
+        assert!(!term.try_put_char([0, 5], 'b'));
+        assert!(term.try_put_tile([4, 0], Tile::from('c')));
+        assert!(!term.try_put_tile([-3, 0], Tile::from('c')));
+        assert_eq!("b   c", term.get_string([0, 0], 5));
+    }
+
+    #[test]
+    fn orientation() {
+        let mut term = Terminal::new([4, 4]);
+        term.set_orientation([1, 1], TileOrientation::Normal);
+        assert!(term.orientations().is_empty());
+
+        term.set_orientation([1, 1], TileOrientation::FlipX);
+        assert_eq!(TileOrientation::FlipX, term.orientation([1, 1]));
+        assert_eq!(TileOrientation::Normal, term.orientation([2, 1]));
+        term.clear_orientations();
+        assert_eq!(TileOrientation::Normal, term.orientation([1, 1]));
+    }
+
+    #[test]
+    fn orientation_uvs() {
+        // Label each corner as top left, bottom left, top right, bottom right
+        let uvs = [[0.0, 0.0], [0.0, 1.0], [1.0, 0.0], [1.0, 1.0]];
+        let [tl, bl, tr, br] = uvs;
+        assert_eq!(uvs, TileOrientation::Normal.apply_to_uvs(uvs));
+        // Rotating clockwise moves the top left of the glyph to the top right
+        assert_eq!(
+            [bl, br, tl, tr],
+            TileOrientation::Rotate90.apply_to_uvs(uvs)
+        );
+        assert_eq!(
+            [br, tr, bl, tl],
+            TileOrientation::Rotate180.apply_to_uvs(uvs)
+        );
+        assert_eq!(
+            [tr, tl, br, bl],
+            TileOrientation::Rotate270.apply_to_uvs(uvs)
+        );
+        assert_eq!([tr, br, tl, bl], TileOrientation::FlipX.apply_to_uvs(uvs));
+        assert_eq!([bl, tl, br, tr], TileOrientation::FlipY.apply_to_uvs(uvs));
+    }
+
+    #[test]
+    fn pulse() {
+        let mut term = Terminal::new([12, 2]);
+        assert!(term.pulses().is_empty());
+        term.put_string([1, 0], "Press Start".pulse());
+        assert!(term.pulse([1, 0]));
+        assert!(term.pulse([11, 0]));
+        assert!(!term.pulse([0, 0]));
+        assert!(!term.pulse([1, 1]));
+
+        term.set_pulse([1, 0], false);
+        assert!(!term.pulse([1, 0]));
+        term.clear_pulses();
+        assert!(term.pulses().is_empty());
+    }
+
+    #[test]
+    fn fill() {
+        let mut term = Terminal::new([4, 3]);
+        term.fill('.'.fg(Color::Srgba(RED)));
+        assert!(term
+            .iter()
+            .all(|t| t.glyph == '.' && t.fg_color == Color::Srgba(RED)));
+        assert_eq!(Tile::default(), term.clear_tile);
+
+        term.fill_box([2, -1], [5, 2], '#');
+        assert_eq!("..##", term.get_string([0, 0], 4));
+        assert_eq!("....", term.get_string([0, 1], 4));
+
+        term.clear();
+        assert!(term.iter().all(|t| *t == Tile::default()));
+    }
+
+    #[test]
+    fn dirty_rect() {
+        let mut term = Terminal::new([10, 10]);
+        let all = term.take_dirty_rect().unwrap();
+        assert_eq!(IVec2::ZERO, all.min_i());
+        assert_eq!(IVec2::new(9, 9), all.max_i());
+        assert!(term.take_dirty_rect().is_none());
+
+        term.put_char([4, 4], 'a');
+        let rect = term.take_dirty_rect().unwrap();
+        assert_eq!(IVec2::new(4, 4), rect.min_i());
+        assert_eq!(IVec2::new(4, 4), rect.max_i());
+
+        term.put_tile([1, 6], Tile::from('b'));
+        term.put_color([7, 2], ColorFormat::BgColor(Color::Srgba(RED)));
+        term.clear_box([3, 0], [2, 2]);
+        let rect = term.take_dirty_rect().unwrap();
+        assert_eq!(IVec2::new(1, 0), rect.min_i());
+        assert_eq!(IVec2::new(7, 6), rect.max_i());
+
+        term.clear();
+        let rect = term.take_dirty_rect().unwrap();
+        assert_eq!(all.min_i(), rect.min_i());
+        assert_eq!(all.max_i(), rect.max_i());
+    }
+
+    #[test]
+    fn dirty_rect_all_writers() {
+        let mut term = Terminal::new([10, 10]).with_default_pivot(Pivot::TopRight);
+        let rect = |term: &mut Terminal| {
+            let rect = term.take_dirty_rect().unwrap();
+            [rect.min_i().to_array(), rect.max_i().to_array()]
+        };
+        term.take_dirty_rect();
+
+        term.put_color([0, 0], Color::Srgba(RED).bg());
+        assert_eq!([[9, 9], [9, 9]], rect(&mut term));
+
+        term.put_string([0, 0].pivot(Pivot::BottomLeft), "abc");
+        assert_eq!([[0, 0], [2, 0]], rect(&mut term));
+
+        term.draw_box([1, 1], [3, 2], BoxGlyphs::single_line());
+        assert_eq!([[1, 1], [3, 2]], rect(&mut term));
+
+        term.clear_string([8, 3], 4);
+        assert_eq!([[0, 3], [9, 4]], rect(&mut term));
+
+        term.get_tile_mut([5, 6]).glyph = 'x';
+        assert_eq!([[5, 6], [5, 6]], rect(&mut term));
+        term.get_tile_mut([0, 0].pivot(Pivot::TopRight)).glyph = 'x';
+        assert_eq!([[9, 9], [9, 9]], rect(&mut term));
+
+        term.iter_column_mut(2).for_each(|t| t.glyph = 'y');
+        assert_eq!([[2, 0], [2, 9]], rect(&mut term));
+
+        term.fill_bg(Color::BLACK);
+        assert_eq!([[0, 0], [9, 9]], rect(&mut term));
+        term.scroll_up(1);
+        assert_eq!([[0, 0], [9, 9]], rect(&mut term));
+        term.clear_non_sticky();
+        assert_eq!([[0, 0], [9, 9]], rect(&mut term));
+
+        let mut other = Terminal::new([10, 10]);
+        other.take_dirty_rect();
+        term.swap_with(&mut other);
+        assert_eq!([[0, 0], [9, 9]], rect(&mut term));
+        assert_eq!([[0, 0], [9, 9]], rect(&mut other));
+    }
+
+    #[test]
+    fn glyph_substitution() {
+        let mut term = Terminal::new([10, 2]).with_glyph_substitution('o', '0');
+        term.put_string([0, 0], "food");
+        assert_eq!("f00d", term.get_string([0, 0], 4));
+        term.put_char([0, 1], 'o'.fg(Color::Srgba(RED)));
+        assert_eq!('0', term.get_char([0, 1]));
+
+        term.clear_glyph_substitutions();
+        term.put_string([0, 0], "food");
+        assert_eq!("food", term.get_string([0, 0], 4));
+    }
+
+    #[cfg(feature = "debug_writes")]
+    #[test]
+    fn write_conflicts() {
+        let mut term = Terminal::new([5, 5]);
+        term.put_char([1, 1], 'a');
+        term.put_string([0, 2], "hi");
+        assert!(term.take_write_conflicts().is_empty());
+
+        // Recoloring a tile isn't a conflict
+        term.put_char([1, 1], 'a'.fg(Color::WHITE));
+        assert!(term.take_write_conflicts().is_empty());
+
+        term.put_string([0, 1], "xy");
+        let conflicts = term.take_write_conflicts();
+        assert_eq!(1, conflicts.len());
+        assert_eq!(IVec2::new(1, 1), conflicts[0].xy);
+        assert_eq!(('a', 'y'), (conflicts[0].first, conflicts[0].second));
+
+        // Printing starts on the top row
+        term.draw_line([0, 4], [4, 4], '-');
+        term.print("ab");
+        term.draw_box([0, 0], [2, 2], BoxGlyphs::single_line());
+        let conflicts = term.take_write_conflicts();
+        assert_eq!(4, conflicts.len());
+        assert_eq!(('-', 'a'), (conflicts[0].first, conflicts[0].second));
+
+        // Rendering starts a new frame
+        term.mark_rendered();
+        term.put_char([1, 1], 'z');
+        assert!(term.take_write_conflicts().is_empty());
+
+        term.fill_box([0, 1], [2, 1], '#');
+        let conflicts = term.take_write_conflicts();
+        assert_eq!(1, conflicts.len());
+        assert_eq!(('z', '#'), (conflicts[0].first, conflicts[0].second));
+    }
+
+    #[test]
+    fn iter_string() {
+        let mut term = Terminal::new([5, 2]);
+        term.put_string([3, 1], "ab");
+        term.put_char([0, 0], 'c');
+
+        let tiles: Vec<_> = term
+            .iter_string([3, 1], 10)
+            .map(|(xy, t)| (xy.to_array(), t.glyph))
+            .collect();
+        // Stops at the edge instead of wrapping to the next row
+        assert_eq!(vec![([3, 1], 'a'), ([4, 1], 'b')], tiles);
+        assert_eq!(1, term.iter_string([1, 0], 1).count());
+    }
+
+    #[test]
+    fn draw_box() {
+        let mut term = Terminal::new([6, 4]);
+        term.draw_box([0, 0], [4, 3], BoxGlyphs::single_line().with_fill('.'));
+        assert_eq!("┌──┐  ", term.get_string([0, 2], 6));
+        assert_eq!("│..│  ", term.get_string([0, 1], 6));
+        assert_eq!("└──┘  ", term.get_string([0, 0], 6));
+
+        // Clipped against the terminal edges
+        term.clear();
+        term.draw_box([3, -1], [5, 3], BoxGlyphs::double_line());
+        assert_eq!("   ╔══", term.get_string([0, 1], 6));
+        assert_eq!("   ║  ", term.get_string([0, 0], 6));
+
+        term.clear();
+        term.draw_box([1, 1], [0, 3], BoxGlyphs::single_line());
+        assert!(term.iter().all(|t| t.glyph == ' '));
+    }
+
+    #[test]
+    fn draw_nine_slice() {
+        let mut term = Terminal::new([6, 6]);
+        let slices = ['1', '2', '3', '4', '5', '6', '7', '8', '9'];
+        term.draw_nine_slice(GridRect::from_bl([1, 1], [4, 4]), slices);
+        assert_eq!("      ", term.get_string([0, 5], 6));
+        assert_eq!(" 1223 ", term.get_string([0, 4], 6));
+        assert_eq!(" 4556 ", term.get_string([0, 3], 6));
+        assert_eq!(" 4556 ", term.get_string([0, 2], 6));
+        assert_eq!(" 7889 ", term.get_string([0, 1], 6));
+        assert_eq!("      ", term.get_string([0, 0], 6));
+
+        // Clipped against the terminal edges
+        term.clear();
+        term.draw_nine_slice(GridRect::from_bl([-1, -1], [3, 3]), slices);
+        assert_eq!("23    ", term.get_string([0, 1], 6));
+        assert_eq!("56    ", term.get_string([0, 0], 6));
+    }
+
+    #[test]
+    fn draw_line() {
+        let mut term = Terminal::new([10, 10]);
+        term.draw_line([2, 2], [2, 2], '*');
+        assert_eq!(1, term.iter().filter(|t| t.glyph == '*').count());
+
+        term.clear();
+        term.draw_line([0, 3], [9, 3], '-');
+        assert_eq!("----------", term.get_string([0, 3], 10));
+
+        term.clear();
+        term.draw_line([4, 9], [4, 0], '|');
+        assert!((0..10).all(|y| term.get_char([4, y]) == '|'));
+        assert_eq!(10, term.iter().filter(|t| t.glyph == '|').count());
+
+        // Steep lines cover one tile per row
+        term.clear();
+        term.draw_line([0, 0], [2, 9], '/');
+        assert_eq!(10, term.iter().filter(|t| t.glyph == '/').count());
+        assert_eq!('/', term.get_char([0, 0]));
+        assert_eq!('/', term.get_char([2, 9]));
+
+        // Clipped to the terminal
+        term.clear();
+        term.draw_line([-5, 5], [15, 5], '=');
+        assert_eq!("==========", term.get_string([0, 5], 10));
+    }
+
+    #[test]
+    fn draw_arrow() {
+        let mut term = Terminal::new([10, 10]);
+        term.draw_arrow([5, 5], [9, 5], '-');
+        assert_eq!("----→", term.get_string([5, 5], 5));
+
+        term.draw_arrow([5, 5], [0, 5], '-');
+        assert_eq!('←', term.get_char([0, 5]));
+        term.draw_arrow([5, 5], [5, 9], '|');
+        assert_eq!('↑', term.get_char([5, 9]));
+        assert_eq!('|', term.get_char([5, 8]));
+        term.draw_arrow([5, 5], [5, 0], '|');
+        assert_eq!('↓', term.get_char([5, 0]));
+
+        // Diagonals use the nearest cardinal arrow
+        term.draw_arrow([0, 0], [3, 1], '*');
+        assert_eq!('→', term.get_char([3, 1]));
+        term.draw_arrow([0, 9], [1, 6], '*');
+        assert_eq!('↓', term.get_char([1, 6]));
+    }
+
+    #[test]
+    fn world_transform_centering() {
+        for (size, center) in [([5, 3], [2, 1]), ([4, 6], [2, 3]), ([1, 2], [0, 1])] {
+            let term = Terminal::new(size);
+            let center = IVec2::from(center);
+            assert_eq!(IVec2::ZERO, term.transform_ltw(center));
+            assert_eq!(center, term.transform_wtl([0, 0]));
+            for p in [[0, 0], [3, 1], [-2, 5]] {
+                let p = IVec2::from(p);
+                assert_eq!(p, term.transform_wtl(term.transform_ltw(p)));
+                assert_eq!(p, term.transform_ltw(term.transform_wtl(p)));
+            }
+        }
     }
 }
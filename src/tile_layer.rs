@@ -0,0 +1,78 @@
+//! Optional per tile values stored alongside a terminal's tiles.
+
+/// A value for every tile of a terminal, like a tint or a depth, which is
+/// only allocated once a tile is set to something other than the default.
+///
+/// Most terminals never use most layers, so an unused layer is just an empty
+/// `Vec`.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct TileLayer<T> {
+    /// The number of tiles in the terminal.
+    len: usize,
+    /// Empty until a tile is set to a non-default value, otherwise one value
+    /// per tile.
+    values: Vec<T>,
+}
+
+impl<T: Copy + Default + PartialEq> TileLayer<T> {
+    pub(crate) fn new(len: usize) -> Self {
+        Self {
+            len,
+            values: Vec::new(),
+        }
+    }
+
+    /// Set the value of the tile at the given index.
+    pub(crate) fn set(&mut self, i: usize, value: T) {
+        if self.values.is_empty() {
+            if value == T::default() {
+                return;
+            }
+            self.values = vec![T::default(); self.len];
+        }
+        self.values[i] = value;
+    }
+
+    /// The value of the tile at the given index.
+    pub(crate) fn get(&self, i: usize) -> T {
+        self.values.get(i).copied().unwrap_or_default()
+    }
+
+    /// Reset every tile to the default value.
+    pub(crate) fn clear(&mut self) {
+        self.values.clear();
+    }
+
+    /// Change the number of tiles, resetting every tile to the default value.
+    pub(crate) fn resize(&mut self, len: usize) {
+        self.len = len;
+        self.clear();
+    }
+
+    /// The value of every tile, or an empty slice if no tiles have been set.
+    pub(crate) fn as_slice(&self) -> &[T] {
+        &self.values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TileLayer;
+
+    #[test]
+    fn allocates_on_first_set() {
+        let mut layer = TileLayer::<f32>::new(4);
+        layer.set(1, 0.0);
+        assert!(layer.as_slice().is_empty());
+
+        layer.set(1, 2.0);
+        assert_eq!(&[0.0, 2.0, 0.0, 0.0], layer.as_slice());
+        assert_eq!(2.0, layer.get(1));
+
+        layer.resize(6);
+        assert!(layer.as_slice().is_empty());
+        assert_eq!(0.0, layer.get(1));
+        layer.set(5, 1.0);
+        assert_eq!(6, layer.as_slice().len());
+    }
+}
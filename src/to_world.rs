@@ -2,26 +2,30 @@
 //! and world space.
 
 use bevy::{
-    math::{IVec2, Mat4, UVec2, Vec2, Vec3},
+    input::ButtonInput,
+    math::{IVec2, Mat4, Rect, UVec2, Vec2, Vec3},
     prelude::{
-        App, Assets, Camera, Changed, Component, Entity, GlobalTransform, Image, Or, Plugin, Query,
-        Res, Update, With,
+        Added, App, Assets, Camera, Changed, Component, Entity, Event, EventWriter,
+        GlobalTransform, Image, MouseButton, Or, Plugin, Query, Res, Update, With,
     },
     render::camera::{ManualTextureViews, RenderTarget},
     window::{PrimaryWindow, Window, WindowRef},
 };
-use sark_grids::GridPoint;
+use sark_grids::{geometry::GridRect, GridPoint};
 
 use crate::{
     renderer::{TerminalLayout, TileScaling},
-    Terminal,
+    Terminal, Tile,
 };
 
 pub(crate) struct ToWorldPlugin;
 
 impl Plugin for ToWorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, (update_from_terminal, update_from_camera));
+        app.add_event::<TerminalLinkClicked>().add_systems(
+            Update,
+            (update_from_terminal, update_from_camera, click_links),
+        );
     }
 }
 
@@ -48,7 +52,7 @@ impl ToWorld {
     pub fn tile_to_world(&self, tile: impl GridPoint) -> Vec3 {
         let term_pos = self.term_pos.truncate();
         let term_offset = self.term_size.as_vec2() * Vec2::from(self.layout.pivot);
-        (tile.as_vec2() + term_pos - term_offset).extend(self.term_pos.z)
+        ((tile.as_vec2() - term_offset) * self.tile_scale() + term_pos).extend(self.term_pos.z)
     }
 
     /// Convert a tile center to it's corresponding world position.
@@ -60,18 +64,88 @@ impl ToWorld {
     pub fn world_to_tile(&self, world: Vec2) -> IVec2 {
         let term_pos = self.term_pos.truncate();
         let term_offset = self.term_size.as_vec2() * Vec2::from(self.layout.pivot);
-        let xy = world - term_pos + term_offset;
+        let xy = (world - term_pos) / self.tile_scale() + term_offset;
         xy.floor().as_ivec2()
     }
 
+    /// The tiles overlapped by a world space [`Rect`], clamped to the bounds of
+    /// the terminal.
+    ///
+    /// A tile is included if any part of it is overlapped by the rect. If the
+    /// rect doesn't overlap the terminal the returned rect will have a size of
+    /// zero.
+    pub fn world_rect_to_tiles(&self, rect: Rect) -> GridRect {
+        let term_pos = self.term_pos.truncate();
+        let term_offset = self.term_size.as_vec2() * Vec2::from(self.layout.pivot);
+        let size = self.term_size.as_ivec2();
+
+        let scale = self.tile_scale();
+        let min = ((rect.min - term_pos) / scale + term_offset)
+            .floor()
+            .as_ivec2();
+        let max = ((rect.max - term_pos) / scale + term_offset)
+            .ceil()
+            .as_ivec2();
+        let min = min.clamp(IVec2::ZERO, size);
+        let max = max.clamp(min, size);
+        GridRect::from_bl(min, max - min)
+    }
+
     /// The size of a single world unit, accounting for `TileScaling`.
     pub fn world_unit(&self) -> Vec2 {
         match self.layout.scaling {
-            TileScaling::World => Vec2::ONE,
+            TileScaling::World => self.layout.tile_size,
             TileScaling::Pixels => self.layout.pixels_per_tile.as_vec2(),
+            TileScaling::Custom(size) => size,
+        }
+    }
+
+    /// How much tile positions are scaled by when converting to world space.
+    /// World scaled tiles are the size of the layout's tiles, which accounts
+    /// for [`TerminalLayout::units_per_tile`] and the font's aspect ratio.
+    fn tile_scale(&self) -> Vec2 {
+        match self.layout.scaling {
+            TileScaling::World => self.layout.tile_size,
+            TileScaling::Pixels => Vec2::ONE,
+            TileScaling::Custom(size) => size,
         }
     }
 
+    /// The tiles that are visible to the camera, clamped to the bounds of the
+    /// terminal. See [`ToWorld::world_rect_to_tiles`].
+    ///
+    /// Useful for skipping work on tiles that are off screen in large
+    /// terminals. Returns `None` if the camera's viewport size isn't known
+    /// yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// use bevy::prelude::*;
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// fn animate_water(mut q_term: Query<(&mut Terminal, &ToWorld)>, time: Res<Time>) {
+    ///     let (mut term, to_world) = q_term.single_mut();
+    ///     let Some(visible) = to_world.visible_tiles() else {
+    ///         return;
+    ///     };
+    ///     let [min, max] = [visible.min_i(), visible.max_i()];
+    ///     for y in min.y..=max.y {
+    ///         for x in min.x..=max.x {
+    ///             // Only update the tiles on screen
+    ///             let glyph = if (x + y + time.elapsed_seconds() as i32) % 2 == 0 { '~' } else { '≈' };
+    ///             term.put_char([x, y], glyph);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn visible_tiles(&self) -> Option<GridRect> {
+        let viewport_size = self.viewport_size?;
+        let min = self.screen_to_world(self.viewport_pos)?;
+        let max = self.screen_to_world(self.viewport_pos + viewport_size)?;
+        Some(self.world_rect_to_tiles(Rect::from_corners(min, max)))
+    }
+
     /// Convert a position from screen space (ie: Cursor position) to world space.
     pub fn screen_to_world(&self, screen_pos: Vec2) -> Option<Vec2> {
         if let Some(viewport_size) = self.viewport_size {
@@ -88,17 +162,108 @@ impl ToWorld {
             None
         }
     }
+
+    /// Convert a position from screen space to the tile position under it.
+    /// The returned position may be outside the terminal.
+    pub fn screen_to_tile(&self, screen_pos: Vec2) -> Option<IVec2> {
+        self.screen_to_world(screen_pos)
+            .map(|world| self.world_to_tile(world))
+    }
+
+    /// The position and contents of the terminal tile at a position in screen
+    /// space, or `None` if the position is outside the terminal.
+    ///
+    /// # Example
+    ///
+    /// ```rust no_run
+    /// use bevy::prelude::*;
+    /// use bevy::window::PrimaryWindow;
+    /// use bevy_ascii_terminal::*;
+    ///
+    /// fn tooltip(
+    ///     q_window: Query<&Window, With<PrimaryWindow>>,
+    ///     q_term: Query<(&Terminal, &ToWorld)>,
+    /// ) {
+    ///     let window = q_window.single();
+    ///     let Some(cursor) = window.cursor_position() else {
+    ///         return;
+    ///     };
+    ///     // Cursor position has the origin at the top left
+    ///     let screen = Vec2::new(cursor.x, window.height() - cursor.y);
+    ///     for (term, to_world) in &q_term {
+    ///         if let Some((xy, tile)) = to_world.pick_tile(screen, term) {
+    ///             info!("'{}' at {}", tile.glyph, xy);
+    ///         }
+    ///     }
+    /// }
+    /// ```
+    pub fn pick_tile(&self, screen_pos: Vec2, term: &Terminal) -> Option<(IVec2, Tile)> {
+        let xy = self.screen_to_tile(screen_pos)?;
+        term.in_bounds(xy).then(|| (xy, *term.get_tile(xy)))
+    }
+}
+
+/// Sent when a link registered with [`Terminal::put_link`] is clicked with the
+/// left mouse button.
+///
+/// Only terminals with a [`ToWorld`] component can be clicked.
+#[derive(Debug, Clone, PartialEq, Eq, Event)]
+pub struct TerminalLinkClicked {
+    /// The terminal entity containing the link.
+    pub terminal: Entity,
+    /// The id the link was registered with.
+    pub id: String,
+}
+
+fn click_links(
+    buttons: Option<Res<ButtonInput<MouseButton>>>,
+    primary_window: Query<&Window, With<PrimaryWindow>>,
+    q_term: Query<(Entity, &Terminal, &ToWorld)>,
+    mut events: EventWriter<TerminalLinkClicked>,
+) {
+    if !buttons.is_some_and(|b| b.just_pressed(MouseButton::Left)) {
+        return;
+    }
+    let Some((window, cursor)) = primary_window
+        .get_single()
+        .ok()
+        .and_then(|w| w.cursor_position().map(|c| (w, c)))
+    else {
+        return;
+    };
+    // Cursor position has the origin at the top left, screen space is bottom left
+    let screen = Vec2::new(cursor.x, window.height() - cursor.y);
+
+    for (entity, term, to_world) in &q_term {
+        let Some((xy, _)) = to_world.pick_tile(screen, term) else {
+            continue;
+        };
+        if let Some(id) = term.link_at(xy) {
+            events.send(TerminalLinkClicked {
+                terminal: entity,
+                id: id.to_string(),
+            });
+        }
+    }
 }
 
+/// Terminal size and position are read from the layout and transform rather
+/// than the terminal itself, so writing to the terminal doesn't cause an
+/// update. The layout only changes when the terminal is resized or its render
+/// settings change.
 #[allow(clippy::type_complexity)]
 fn update_from_terminal(
     mut q_term: Query<
-        (&mut ToWorld, &Terminal, &GlobalTransform, &TerminalLayout),
-        Or<(Changed<Terminal>, Changed<TerminalLayout>)>,
+        (&mut ToWorld, &GlobalTransform, &TerminalLayout),
+        Or<(
+            Added<ToWorld>,
+            Changed<TerminalLayout>,
+            Changed<GlobalTransform>,
+        )>,
     >,
 ) {
-    for (mut to_world, term, transform, layout) in q_term.iter_mut() {
-        to_world.term_size = term.size();
+    for (mut to_world, transform, layout) in q_term.iter_mut() {
+        to_world.term_size = layout.term_size();
         to_world.layout = layout.clone();
         to_world.term_pos = transform.translation();
     }
@@ -170,3 +335,156 @@ fn update_from_camera(
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use bevy::{
+        math::{IVec2, Mat4, Rect, UVec2, Vec2, Vec3},
+        prelude::{App, GlobalTransform, Update},
+    };
+
+    use super::{update_from_terminal, ToWorld};
+    use crate::{renderer::TileScaling, Terminal, TerminalLayout};
+
+    fn to_world() -> ToWorld {
+        ToWorld {
+            term_size: UVec2::new(10, 10),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn world_rect_to_tiles() {
+        let tw = to_world();
+        // Terminal is centered, so it covers -5..5 on both axes
+        let rect = tw.world_rect_to_tiles(Rect::new(-8.0, -8.0, -2.5, 0.5));
+        assert_eq!(IVec2::new(0, 0), rect.min_i());
+        assert_eq!(IVec2::new(2, 5), rect.max_i());
+
+        let rect = tw.world_rect_to_tiles(Rect::new(-5.0, -5.0, 5.0, 5.0));
+        assert_eq!(UVec2::new(10, 10), rect.size().as_uvec2());
+    }
+
+    #[test]
+    fn pick_tile() {
+        let mut term = Terminal::new([10, 10]);
+        term.put_char([1, 2], 'x');
+        // A 100x100 viewport showing world space from -5 to 5
+        let tw = ToWorld {
+            ndc_to_world: Mat4::from_scale(Vec3::new(5.0, 5.0, 1.0)),
+            viewport_size: Some(Vec2::splat(100.0)),
+            ..to_world()
+        };
+
+        let (xy, tile) = tw.pick_tile(Vec2::new(15.0, 25.0), &term).unwrap();
+        assert_eq!(IVec2::new(1, 2), xy);
+        assert_eq!('x', tile.glyph);
+        assert_eq!(None, tw.pick_tile(Vec2::new(-10.0, 25.0), &term));
+    }
+
+    #[test]
+    fn visible_tiles() {
+        // A 100x100 viewport showing 10x10 world units around the camera
+        let view = |x: f32| ToWorld {
+            ndc_to_world: Mat4::from_translation(Vec3::new(x, 0.0, 0.0))
+                * Mat4::from_scale(Vec3::new(5.0, 5.0, 1.0)),
+            viewport_size: Some(Vec2::splat(100.0)),
+            ..to_world()
+        };
+
+        let rect = view(0.0).visible_tiles().unwrap();
+        assert_eq!(IVec2::new(0, 0), rect.min_i());
+        assert_eq!(UVec2::new(10, 10), rect.size().as_uvec2());
+
+        // Panning right leaves the left half of the terminal off screen
+        let rect = view(5.0).visible_tiles().unwrap();
+        assert_eq!(IVec2::new(5, 0), rect.min_i());
+        assert_eq!(UVec2::new(5, 10), rect.size().as_uvec2());
+
+        assert!(to_world().visible_tiles().is_none());
+    }
+
+    #[test]
+    fn units_per_tile() {
+        let mut tw = to_world();
+        tw.layout.units_per_tile = Vec2::splat(0.5);
+        // As set by the renderer for a square font
+        tw.layout.tile_size = Vec2::splat(0.5);
+
+        // Terminal is centered, so it covers -2.5..2.5 on both axes
+        assert_eq!(Vec3::new(-2.5, -2.5, 0.0), tw.tile_to_world([0, 0]));
+        assert_eq!(Vec3::new(0.5, -1.5, 0.0), tw.tile_to_world([6, 2]));
+        assert_eq!(Vec3::new(0.75, -1.25, 0.0), tw.tile_center_to_world([6, 2]));
+        assert_eq!(IVec2::new(6, 2), tw.world_to_tile(Vec2::new(0.7, -1.2)));
+
+        let rect = tw.world_rect_to_tiles(Rect::new(-2.5, -2.5, 2.5, 2.5));
+        assert_eq!(UVec2::new(10, 10), rect.size().as_uvec2());
+    }
+
+    #[test]
+    fn non_square_tiles() {
+        let mut tw = to_world();
+        // A font twice as tall as it is wide
+        tw.layout.tile_size = Vec2::new(0.5, 1.0);
+
+        assert_eq!(Vec2::new(0.5, 1.0), tw.world_unit());
+        // Terminal is centered, so it covers -2.5..2.5 horizontally
+        assert_eq!(Vec3::new(-2.5, -5.0, 0.0), tw.tile_to_world([0, 0]));
+        assert_eq!(Vec3::new(2.5, 0.0, 0.0), tw.tile_to_world([10, 5]));
+        assert_eq!(Vec3::new(0.75, -2.5, 0.0), tw.tile_center_to_world([6, 2]));
+        assert_eq!(IVec2::new(6, 2), tw.world_to_tile(Vec2::new(0.7, -2.2)));
+
+        let rect = tw.world_rect_to_tiles(Rect::new(-2.5, -5.0, 0.0, 0.0));
+        assert_eq!(UVec2::new(5, 5), rect.size().as_uvec2());
+    }
+
+    #[test]
+    fn custom_scaling() {
+        let mut tw = to_world();
+        tw.layout.scaling = TileScaling::Custom(Vec2::new(0.5, 1.0));
+
+        assert_eq!(Vec2::new(0.5, 1.0), tw.world_unit());
+        // Terminal is centered, so it covers -2.5..2.5 horizontally
+        assert_eq!(Vec3::new(-2.5, -5.0, 0.0), tw.tile_to_world([0, 0]));
+        assert_eq!(Vec3::new(0.75, -2.5, 0.0), tw.tile_center_to_world([6, 2]));
+        assert_eq!(IVec2::new(6, 2), tw.world_to_tile(Vec2::new(0.7, -2.2)));
+    }
+
+    #[test]
+    fn content_writes_skip_update() {
+        let mut app = App::new();
+        app.add_systems(Update, update_from_terminal);
+        let term = Terminal::new([10, 5]);
+        let layout = TerminalLayout::from(&term);
+        let entity = app
+            .world_mut()
+            .spawn((term, layout, GlobalTransform::default(), ToWorld::default()))
+            .id();
+        let term_size = |app: &App| app.world().get::<ToWorld>(entity).unwrap().term_size;
+
+        app.update();
+        assert_eq!(UVec2::new(10, 5), term_size(&app));
+
+        // Anything the system writes would overwrite this
+        app.world_mut()
+            .get_mut::<ToWorld>(entity)
+            .unwrap()
+            .term_size = UVec2::ZERO;
+        let mut term = app.world_mut().get_mut::<Terminal>(entity).unwrap();
+        term.put_string([0, 0], "Hello");
+        app.update();
+        assert_eq!(UVec2::ZERO, term_size(&app));
+
+        let mut layout = app.world_mut().get_mut::<TerminalLayout>(entity).unwrap();
+        layout.units_per_tile = Vec2::splat(2.0);
+        app.update();
+        assert_eq!(UVec2::new(10, 5), term_size(&app));
+    }
+
+    #[test]
+    fn world_rect_outside() {
+        let tw = to_world();
+        let rect = tw.world_rect_to_tiles(Rect::new(20.0, 20.0, 30.0, 30.0));
+        assert_eq!(UVec2::ZERO, rect.size().as_uvec2());
+    }
+}
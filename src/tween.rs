@@ -0,0 +1,231 @@
+//! Smoothly move a single glyph between two tiles on a terminal.
+
+use bevy::{
+    math::IVec2,
+    prelude::{
+        App, Commands, Component, Entity, IntoSystemConfigs, Plugin, Query, Res, Time, Update,
+    },
+};
+use sark_grids::GridPoint;
+
+use crate::{formatting::FormattedTile, Terminal, TileFormatter};
+
+pub(crate) struct TweenPlugin;
+
+impl Plugin for TweenPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, stamp_tweens.after(crate::entity::run_draw_fns));
+    }
+}
+
+/// Easing functions for a [`TileTween`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TweenEasing {
+    #[default]
+    Linear,
+    /// Start slow and speed up.
+    EaseIn,
+    /// Start fast and slow down.
+    EaseOut,
+    /// Start and end slow.
+    EaseInOut,
+}
+
+impl TweenEasing {
+    /// Map a linear progress value in the range 0..=1 to an eased one.
+    pub fn ease(&self, t: f32) -> f32 {
+        let t = t.clamp(0.0, 1.0);
+        match self {
+            TweenEasing::Linear => t,
+            TweenEasing::EaseIn => t * t,
+            TweenEasing::EaseOut => t * (2.0 - t),
+            TweenEasing::EaseInOut => t * t * (3.0 - 2.0 * t),
+        }
+    }
+}
+
+/// A component which moves a tile from one position to another on a terminal
+/// over time.
+///
+/// Every frame the tile is written to the `terminal` entity at the current
+/// interpolated position, rounded to the nearest tile. The tween entity is
+/// despawned once the tile has been written at `end`.
+///
+/// The tween only ever writes to the terminal, it doesn't erase the tile from
+/// its previous position. Add [`ClearAfterRender`](crate::ClearAfterRender) to
+/// the terminal or clear it yourself each frame.
+///
+/// # Example
+///
+/// ```rust no_run
+/// use bevy::prelude::*;
+/// use bevy_ascii_terminal::prelude::*;
+///
+/// fn throw(mut commands: Commands, q_term: Query<Entity, With<Terminal>>) {
+///     let term = q_term.single();
+///     commands.spawn(
+///         TileTween::new(term, '*', [1, 1], [15, 6], 0.5).with_easing(TweenEasing::EaseOut),
+///     );
+/// }
+/// ```
+#[derive(Debug, Clone, Component)]
+pub struct TileTween {
+    /// The terminal entity to write to.
+    pub terminal: Entity,
+    pub tile: FormattedTile,
+    pub start: IVec2,
+    pub end: IVec2,
+    /// How long the tween takes to reach `end`, in seconds.
+    pub duration: f32,
+    pub easing: TweenEasing,
+    elapsed: f32,
+}
+
+impl TileTween {
+    pub fn new(
+        terminal: Entity,
+        tile: impl TileFormatter,
+        start: impl GridPoint,
+        end: impl GridPoint,
+        duration: f32,
+    ) -> Self {
+        Self {
+            terminal,
+            tile: tile.format(),
+            start: start.as_ivec2(),
+            end: end.as_ivec2(),
+            duration,
+            easing: TweenEasing::default(),
+            elapsed: 0.0,
+        }
+    }
+
+    pub fn with_easing(mut self, easing: TweenEasing) -> Self {
+        self.easing = easing;
+        self
+    }
+
+    /// Linear progress of the tween, in the range 0..=1.
+    pub fn progress(&self) -> f32 {
+        if self.duration <= 0.0 {
+            return 1.0;
+        }
+        (self.elapsed / self.duration).min(1.0)
+    }
+
+    /// Returns true once the tween has reached `end`.
+    pub fn is_finished(&self) -> bool {
+        self.progress() >= 1.0
+    }
+
+    /// The tile position at the current point along the eased path.
+    pub fn position(&self) -> IVec2 {
+        let t = self.easing.ease(self.progress());
+        let (start, end) = (self.start.as_vec2(), self.end.as_vec2());
+        start.lerp(end, t).round().as_ivec2()
+    }
+
+    /// Advance the tween by the given number of seconds. This is done
+    /// automatically each frame.
+    pub fn tick(&mut self, delta: f32) {
+        self.elapsed += delta;
+    }
+}
+
+fn stamp_tweens(
+    mut commands: Commands,
+    time: Res<Time>,
+    mut q_tween: Query<(Entity, &mut TileTween)>,
+    mut q_term: Query<&mut Terminal>,
+) {
+    for (entity, mut tween) in &mut q_tween {
+        let Ok(mut term) = q_term.get_mut(tween.terminal) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        tween.tick(time.delta_seconds());
+        // Written like any other tile so substitutions, the default pivot
+        // and dirty tracking all apply
+        term.try_put_char(tween.position(), tween.tile.clone());
+        if tween.is_finished() {
+            commands.entity(entity).despawn();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use bevy::prelude::*;
+
+    use super::*;
+    use crate::Pivot;
+
+    #[test]
+    fn follows_eased_path() {
+        let mut tween = TileTween::new(Entity::PLACEHOLDER, '*', [0, 0], [10, 0], 1.0)
+            .with_easing(TweenEasing::EaseIn);
+        assert_eq!(IVec2::new(0, 0), tween.position());
+        tween.tick(0.5);
+        // 0.5 * 0.5 * 10
+        assert_eq!(IVec2::new(3, 0), tween.position());
+        tween.tick(0.25);
+        assert_eq!(IVec2::new(6, 0), tween.position());
+        tween.tick(1.0);
+        assert_eq!(IVec2::new(10, 0), tween.position());
+        assert!(tween.is_finished());
+    }
+
+    #[test]
+    fn stamps_and_despawns() {
+        let mut app = App::new();
+        app.init_resource::<Time>().add_plugins(TweenPlugin);
+        let term = app.world_mut().spawn(Terminal::new([10, 3])).id();
+        let tween = app
+            .world_mut()
+            .spawn(TileTween::new(term, '*', [0, 1], [4, 1], 0.2))
+            .id();
+
+        app.update();
+        assert_eq!(
+            '*',
+            app.world().get::<Terminal>(term).unwrap().get_char([0, 1])
+        );
+
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(0.1));
+        app.update();
+        assert_eq!(
+            '*',
+            app.world().get::<Terminal>(term).unwrap().get_char([2, 1])
+        );
+
+        app.world_mut()
+            .resource_mut::<Time>()
+            .advance_by(Duration::from_secs_f32(0.15));
+        app.update();
+        assert_eq!(
+            '*',
+            app.world().get::<Terminal>(term).unwrap().get_char([4, 1])
+        );
+        assert!(app.world().get_entity(tween).is_none());
+    }
+
+    #[test]
+    fn stamps_with_substitution() {
+        let mut app = App::new();
+        app.init_resource::<Time>().add_plugins(TweenPlugin);
+        let term = Terminal::new([5, 5])
+            .with_glyph_substitution('*', '+')
+            .with_default_pivot(Pivot::Center);
+        let term = app.world_mut().spawn(term).id();
+        app.world_mut()
+            .spawn(TileTween::new(term, '*', [1, 0], [1, 0], 1.0));
+
+        app.update();
+        let term = app.world().get::<Terminal>(term).unwrap();
+        assert_eq!('+', term.get_char([3, 2]));
+    }
+}